@@ -43,6 +43,6 @@ fn main() {
 
     println!(
         "{}",
-        to_string_pretty(&breakdowns, PrettyConfig::new()).unwrap()
+        to_string_pretty(&breakdowns.to_state(), PrettyConfig::new()).unwrap()
     );
 }
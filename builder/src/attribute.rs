@@ -1,5 +1,6 @@
 //! Represents each attribute that a character can have
 mod constructors;
+mod limits;
 mod to_attribute;
 mod traits;
 
@@ -80,6 +81,12 @@ pub enum Attribute {
     /// The different skills available in the game.
     #[serde(rename = "skl", alias = "Skill")]
     Skill(Skill),
+    /// The number of ranks spent into a skill. Only skill points spent on a [`Character`] add
+    /// ranks, so this is `0` for bonuses inserted without a character.
+    ///
+    /// [`Character`]: crate::character::Character
+    #[serde(rename = "skr", alias = "SkillRanks")]
+    SkillRanks(Skill),
     /// Both simple and complex saving throws.
     #[serde(rename = "st", alias = "sav", alias = "Save", alias = "SavingThrow")]
     SavingThrow(SavingThrow),
@@ -135,6 +142,9 @@ pub enum Attribute {
     /// Total Character Level
     #[serde(rename = "tlvl", alias = "TotalCharacterLevel")]
     TotalCharacterLevel,
+    /// Base Attack Bonus
+    #[serde(rename = "bab", alias = "BaseAttackBonus")]
+    BaseAttackBonus,
     /// Summoned Creature Bonuses
     #[serde(rename = "smn", alias = "summon", alias = "SummonedAttribute")]
     SummonedAttribute(SummonedAttribute),
@@ -184,6 +194,7 @@ impl Display for Attribute {
             Self::Ability(ability) => write!(f, "Ability Score: {ability}"),
             Self::AbilityModifier(ability) => write!(f, "Ability Modifier: {ability}"),
             Self::Skill(skill) => write!(f, "Skill: {skill}"),
+            Self::SkillRanks(skill) => write!(f, "Skill Ranks: {skill}"),
             Self::Toggle(toggle) => write!(f, "Toggle: {toggle}"),
             Self::SpellPower(sp) => write!(f, "Spell Power: {sp}"),
             Self::SpellCriticalChance(sp) => write!(f, "Spell Critical Chance: {sp}"),
@@ -205,6 +216,7 @@ impl Display for Attribute {
             Self::Health(health) => health.fmt(f),
             Self::SpellPoints(sp) => sp.fmt(f),
             Self::TotalCharacterLevel => write!(f, "Total Character Level"),
+            Self::BaseAttackBonus => write!(f, "Base Attack Bonus"),
             Self::SummonedAttribute(attribute) => write!(f, "Summoned Creatures: {attribute}"),
             Self::ArmorCheckPenalty => write!(f, "Armor Check Penalty"),
             Self::ItemSet(set) => write!(f, "Item Set: {set}"),
@@ -281,6 +293,7 @@ impl StaticValues for Attribute {
                 Self::SpellResistance,
                 Self::SpellPenetration,
                 Self::TotalCharacterLevel,
+                Self::BaseAttackBonus,
                 Self::ArmorCheckPenalty,
                 Self::MovementSpeed,
                 Self::MeleePower,
@@ -307,6 +320,7 @@ impl StaticValues for Attribute {
             }),
             Slider::values().flat_map(|slider| [Self::Slider(slider), Self::SliderMax(slider)]),
             toattr!(Skill),
+            Skill::values().map(Self::SkillRanks),
             toattr!(SavingThrow),
             toattr!(Toggle),
             toattr!(Flag),
@@ -0,0 +1,38 @@
+use crate::{
+    bonus::{ToValue, Value},
+    types::{absorption::Absorption, dodge::Dodge, sheltering::Sheltering},
+    val,
+};
+
+use super::Attribute;
+
+/// Attribute Limits
+impl Attribute {
+    /// Returns the maximum value of the attribute, if it has one.
+    ///
+    /// The maximum is applied after all bonuses to the attribute have been stacked.
+    #[must_use]
+    pub fn maximum(&self) -> Option<Value> {
+        match self {
+            Self::Dodge(Dodge::Bonus) => Some(Dodge::cap()),
+            Self::Dodge(Dodge::Total) => Some(val!(95)),
+            Self::Sheltering(Sheltering::MagicalTotal) => Some(Sheltering::MagicalCap.to_value()),
+            Self::Absorption(Absorption::Total(_)) => Some(Value::ONE),
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum value of the attribute, if it has one.
+    ///
+    /// The minimum is applied after all bonuses to the attribute have been stacked, and after the
+    /// [`maximum`] is applied.
+    ///
+    /// [`maximum`]: Attribute::maximum
+    #[must_use]
+    pub const fn minimum(&self) -> Option<Value> {
+        match self {
+            Self::Absorption(Absorption::Total(_)) => Some(Value::ZERO),
+            _ => None,
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! A Bonus is an individual bonus to an attribute, increasing or decreasing it by a certain amount.
 mod bonus_type;
 mod condition;
+mod expression;
+mod simplify;
 mod source;
+mod stacking_policy;
 mod template;
 mod traits;
 mod value;
@@ -23,15 +26,20 @@ use crate::{
 
 pub use bonus_type::*;
 pub use condition::*;
+pub use expression::*;
 pub use source::*;
+pub use stacking_policy::*;
 pub use template::*;
 pub use traits::*;
 pub use value::*;
 
 /// Represents a given bonus to some [`Attribute`].
 ///
-/// A bonus contains the [`Attribute`], a [`BonusType`], a [`Value`], a [`BonusSource`], and
-/// an optional [`Condition`].
+/// A bonus contains the [`Attribute`], a [`BonusType`], a [`Value`], a [`BonusSource`], an
+/// optional [`Condition`], and an optional effect name.
+///
+/// Bonuses to the same [`Attribute`] that share an effect name never stack with each other, even
+/// if they have different [`BonusType`]s. Only the highest of them is applied.
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub struct Bonus {
     #[serde(rename = "a", alias = "attr", alias = "attribute")]
@@ -55,6 +63,8 @@ pub struct Bonus {
         skip_serializing_if = "Option::is_none"
     )]
     display_source: Option<BonusSource>,
+    #[serde(rename = "n", alias = "name", skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 impl Bonus {
@@ -76,6 +86,7 @@ impl Bonus {
             source: source.into(),
             condition: None,
             display_source: None,
+            name: None,
         }
     }
 
@@ -271,6 +282,24 @@ impl Bonus {
             ..self
         }
     }
+
+    /// Sets the effect name. Bonuses with the same effect name do not stack with each other.
+    #[must_use]
+    pub fn with_name<N>(self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Clears the effect name
+    #[must_use]
+    pub fn without_name(self) -> Self {
+        Self { name: None, ..self }
+    }
 }
 
 /// Paramter Values
@@ -371,6 +400,21 @@ impl Bonus {
         self.display_source().unwrap_or(&self.source)
     }
 
+    /// Returns the effect name of the bonus, if there is one.
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{bonus::{Bonus, BonusType, BonusSource, Value}, attribute::Attribute};
+    ///
+    /// let bonus = Bonus::new(Attribute::Dummy, BonusType::Insightful, Value::from(3),
+    /// BonusSource::Base).with_name("Insightful Constitution");
+    /// assert_eq!(bonus.name(), Some("Insightful Constitution"));
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Clones all the bonuses values, replacing the attribute.
     ///
     /// Returns a new [`Bonus`] instance.
@@ -402,6 +446,7 @@ impl Bonus {
             source: self.source.clone(),
             display_source: self.display_source.clone(),
             condition: self.condition.clone(),
+            name: self.name.clone(),
         }
     }
 }
@@ -415,6 +460,15 @@ impl HasDice for Bonus {
 impl AttributeDependencies for Bonus {
     fn has_attr_dependency(&self, attribute: &Attribute) -> bool {
         self.value.has_attr_dependency(attribute)
+            || self.r#type.stacking_policy().has_attr_dependency(attribute)
+            || self
+                .attribute
+                .maximum()
+                .is_some_and(|max| max.has_attr_dependency(attribute))
+            || self
+                .attribute
+                .minimum()
+                .is_some_and(|min| min.has_attr_dependency(attribute))
             || self
                 .condition
                 .as_ref()
@@ -423,6 +477,13 @@ impl AttributeDependencies for Bonus {
 
     fn include_attr_dependency(&self, set: &mut HashSet<Attribute>) {
         self.value.include_attr_dependency(set);
+        self.r#type.stacking_policy().include_attr_dependency(set);
+        for limit in [self.attribute.maximum(), self.attribute.minimum()]
+            .into_iter()
+            .flatten()
+        {
+            limit.include_attr_dependency(set);
+        }
         if let Some(condition) = &self.condition {
             condition.include_attr_dependency(set);
         }
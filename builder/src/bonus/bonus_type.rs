@@ -5,10 +5,15 @@ use fmt::Display;
 use serde::{Deserialize, Serialize};
 use utils::enums::StaticValues;
 
+use crate::types::dodge::Dodge;
+
+use super::StackingPolicy;
+
 /// Describes the stacking-type of a bonus.
 ///
-/// Bonuses with the same [`BonusType`] will not stack, meaning that only the highest value will be
-/// added. However, bonuses of different [`BonusType`] will stack.
+/// Bonuses with the same [`BonusType`] combine based on the type's [`StackingPolicy`]. Most types
+/// will not stack, meaning that only the highest value will be added. However, bonuses of
+/// different [`BonusType`] will stack.
 ///
 /// Any bonus with a type of [`BonusType::Stacking`] will always stack no matter what.
 #[derive(
@@ -43,6 +48,10 @@ pub enum BonusType {
     #[serde(rename = "dfl", alias = "Deflection")]
     Deflection,
     /// Dodge
+    ///
+    /// Dodge bonuses stack with each other, up to [`Dodge::cap`].
+    ///
+    /// [`Dodge::cap`]: crate::types::dodge::Dodge::cap
     #[serde(rename = "dge", alias = "Dodge")]
     Dodge,
     /// Enhancement bonus
@@ -222,6 +231,25 @@ impl BonusType {
     pub const fn is_stacking(&self) -> bool {
         matches!(self, Self::Stacking)
     }
+
+    /// Returns how bonuses of this type combine with each other
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use builder::bonus::{BonusType, StackingPolicy};
+    ///
+    /// assert_eq!(BonusType::Stacking.stacking_policy(), StackingPolicy::Sum);
+    /// assert_eq!(BonusType::Enhancement.stacking_policy(), StackingPolicy::HighestOnly);
+    /// ```
+    #[must_use]
+    pub fn stacking_policy(&self) -> StackingPolicy {
+        match self {
+            Self::Stacking => StackingPolicy::Sum,
+            Self::Dodge => StackingPolicy::SumCapped(Dodge::cap()),
+            _ => StackingPolicy::HighestOnly,
+        }
+    }
 }
 
 impl StaticValues for BonusType {
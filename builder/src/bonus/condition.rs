@@ -48,6 +48,15 @@ pub enum Condition {
     /// Exclusive Or gate, requires that only one is true
     #[serde(rename = "x", alias = "Xor")]
     Xor(Box<Self>, Box<Self>),
+    /// Requires that every condition is satisfied. Satisfied if there are no conditions
+    #[serde(rename = "al", alias = "All")]
+    All(Vec<Self>),
+    /// Requires that at least one condition is satisfied. Not satisfied if there are no conditions
+    #[serde(rename = "an", alias = "Any")]
+    Any(Vec<Self>),
+    /// Requires that at least the given number of conditions are satisfied
+    #[serde(rename = "at", alias = "AtLeast")]
+    AtLeast(usize, Vec<Self>),
 }
 
 impl Default for Condition {
@@ -90,6 +99,33 @@ impl Condition {
         Self::has(Attribute::Flag(flag.into()))
     }
 
+    /// Requires that every condition is satisfied
+    #[must_use]
+    pub fn all<I>(conditions: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::All(conditions.into_iter().collect())
+    }
+
+    /// Requires that at least one condition is satisfied
+    #[must_use]
+    pub fn any<I>(conditions: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::Any(conditions.into_iter().collect())
+    }
+
+    /// Requires that at least `count` of the conditions are satisfied
+    #[must_use]
+    pub fn at_least<I>(count: usize, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::AtLeast(count, conditions.into_iter().collect())
+    }
+
     /// Condition that returns true if the provided toggle is on
     #[must_use]
     pub fn toggled<T>(toggle: T) -> Self
@@ -196,6 +232,11 @@ impl AttributeDependencies for Condition {
             | Self::LessEqualTo(a, b) => {
                 a.has_attr_dependency(attribute) || b.has_attr_dependency(attribute)
             }
+            Self::All(conditions) | Self::Any(conditions) | Self::AtLeast(_, conditions) => {
+                conditions
+                    .iter()
+                    .any(|cond| cond.has_attr_dependency(attribute))
+            }
             Self::Constant(_) => false,
         }
     }
@@ -215,6 +256,11 @@ impl AttributeDependencies for Condition {
                 a.include_attr_dependency(set);
                 b.include_attr_dependency(set);
             }
+            Self::All(conditions) | Self::Any(conditions) | Self::AtLeast(_, conditions) => {
+                for cond in conditions {
+                    cond.include_attr_dependency(set);
+                }
+            }
             Self::Constant(_) => {}
         }
     }
@@ -231,6 +277,9 @@ impl HasDice for Condition {
             | Self::LessEqualTo(a, b) => a.has_dice() || b.has_dice(),
             Self::Constant(_) => false,
             Self::And(a, b) | Self::Or(a, b) | Self::Xor(a, b) => a.has_dice() || b.has_dice(),
+            Self::All(conditions) | Self::Any(conditions) | Self::AtLeast(_, conditions) => {
+                conditions.iter().any(HasDice::has_dice)
+            }
         }
     }
 }
@@ -246,6 +295,9 @@ impl Depth for Condition {
             | Self::GreaterEqualTo(a, b)
             | Self::LessEqualTo(a, b) => a.get_depth().max(b.get_depth()),
             Self::And(a, b) | Self::Or(a, b) | Self::Xor(a, b) => a.get_depth().max(b.get_depth()),
+            Self::All(conditions) | Self::Any(conditions) | Self::AtLeast(_, conditions) => {
+                conditions.iter().map(Depth::get_depth).max().unwrap_or(0)
+            }
         }
     }
 }
@@ -263,6 +315,11 @@ impl Display for Condition {
             Self::Xor(a, b) => write!(f, "({a}) != ({b})"),
             Self::GreaterEqualTo(a, b) => write!(f, "({a}) >= ({b})"),
             Self::LessEqualTo(a, b) => write!(f, "({a}) <= ({b})"),
+            Self::All(conditions) => write!(f, "All of [{}]", conditions.iter().join(", ")),
+            Self::Any(conditions) => write!(f, "Any of [{}]", conditions.iter().join(", ")),
+            Self::AtLeast(count, conditions) => {
+                write!(f, "At least {count} of [{}]", conditions.iter().join(", "))
+            }
         }
     }
 }
@@ -333,11 +390,21 @@ where
     I: IntoIterator<Item = Condition>,
 {
     fn cond_any(self) -> Option<Condition> {
-        self.into_iter().tree_reduce(|a, b| a | b)
+        let mut conditions = self.into_iter().collect::<Vec<_>>();
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(Condition::Any(conditions)),
+        }
     }
 
     fn cond_all(self) -> Option<Condition> {
-        self.into_iter().tree_reduce(|a, b| a & b)
+        let mut conditions = self.into_iter().collect::<Vec<_>>();
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(Condition::All(conditions)),
+        }
     }
 
     fn cond_none(self) -> Option<Condition> {
@@ -499,88 +566,48 @@ mod tests {
 
         #[test]
         fn cond_any() {
-            fn test_condition(condition: Condition) {
-                match condition {
-                    Condition::Constant(_) => {}
-                    Condition::Or(a, b) => {
-                        test_condition(*a);
-                        test_condition(*b);
-                    }
-                    cond => panic!("Found illegal condition: {cond}"),
-                }
-            }
-
             let condition = [Condition::FALSE; 100]
                 .cond_any()
                 .expect("Expected Some(condition)");
 
-            test_condition(condition);
+            assert_eq!(condition, Condition::Any(vec![Condition::FALSE; 100]));
         }
 
         #[test]
-        fn cond_all() {
-            fn test_condition(condition: Condition) {
-                match condition {
-                    Condition::Constant(_) => {}
-                    Condition::And(a, b) => {
-                        test_condition(*a);
-                        test_condition(*b);
-                    }
-                    cond => panic!("Found illegal condition: {cond}"),
-                }
-            }
+        fn cond_any_single() {
+            assert_eq!([Condition::TRUE].cond_any(), Some(Condition::TRUE));
+        }
 
+        #[test]
+        fn cond_all() {
             let condition = [Condition::FALSE; 100]
                 .cond_all()
                 .expect("Expected Some(condition)");
 
-            test_condition(condition);
+            assert_eq!(condition, Condition::All(vec![Condition::FALSE; 100]));
         }
 
         #[test]
-        fn cond_none() {
-            fn test_condition(condition: Condition) {
-                match condition {
-                    Condition::Constant(_) => {}
-                    Condition::Or(a, b) => {
-                        test_condition(*a);
-                        test_condition(*b);
-                    }
-                    cond => panic!("Found illegal condition: {cond}"),
-                }
-            }
+        fn cond_all_single() {
+            assert_eq!([Condition::TRUE].cond_all(), Some(Condition::TRUE));
+        }
 
+        #[test]
+        fn cond_none() {
             let condition = [Condition::FALSE; 100]
                 .cond_none()
                 .expect("Expected Some(condition)");
 
-            match condition {
-                Condition::Not(condition) => test_condition(*condition),
-                condition => panic!("Condition does not start with Not: {condition}"),
-            }
+            assert_eq!(condition, !Condition::Any(vec![Condition::FALSE; 100]));
         }
 
         #[test]
         fn cond_not_all() {
-            fn test_condition(condition: Condition) {
-                match condition {
-                    Condition::Constant(_) => {}
-                    Condition::And(a, b) => {
-                        test_condition(*a);
-                        test_condition(*b);
-                    }
-                    cond => panic!("Found illegal condition: {cond}"),
-                }
-            }
-
             let condition = [Condition::FALSE; 100]
                 .cond_not_all()
                 .expect("Expected Some(condition)");
 
-            match condition {
-                Condition::Not(condition) => test_condition(*condition),
-                condition => panic!("Condition does not start with Not: {condition}"),
-            }
+            assert_eq!(condition, !Condition::All(vec![Condition::FALSE; 100]));
         }
     }
 }
@@ -0,0 +1,221 @@
+//! A human-readable text representation of [`Value`] and [`Condition`].
+//!
+//! Values are written as arithmetic, such as `floor((Ability(Strength) - 10) / 2)`, and conditions
+//! are written with logical keywords, such as `has(Flag(Race(Elf))) and Toggle(Blocking)`.
+//!
+//! # Values
+//! - Numbers: `5`, `-2.5`
+//! - Attributes, using their RON representation: `Ability(Strength)`, `GuildLevel`. Attributes
+//!   that start with a lower case letter must be wrapped in backticks, such as `` `a(s)` ``
+//! - Operators: `+`, `-`, `*`, `/`, `%`, and unary `-`
+//! - Functions: `min(a, b)`, `max(a, b)`, `floor(a)`, `ceil(a)`, `round(a)`, `abs(a)`,
+//!   `sign(a)`, `pow(a, b)`, `clamp(a, min, max)`, `dice(count, size)`
+//! - Lists: `sum(a, b, c)`, `product(a, b, c)`
+//! - Tables: `table(key, 1 => a, 11 => b, 21 => c)`
+//! - Conditionals: `if condition then a else b`
+//!
+//! # Conditions
+//! - Constants: `true`, `false`
+//! - Comparisons: `a > b`, `a >= b`, `a < b`, `a <= b`, `a == b`
+//! - Attributes: `has(Toggle(Blocking))`, or just `Toggle(Blocking)`
+//! - Lists: `all(a, b, c)`, `any(a, b, c)`, and `at_least(2, a, b, c)`
+//! - Logic, from lowest to highest precedence: `or`, `xor`, `and`, `not`
+//!
+//! Expressions can be nested up to [`MAX_EXPRESSION_DEPTH`] levels deep.
+mod lexer;
+mod parser;
+mod printer;
+
+use core::{
+    error::Error,
+    fmt::{self, Display},
+    ops::Range,
+    str::FromStr,
+};
+
+use super::{Condition, Value};
+
+/// How deeply parentheses, function calls, negations and other parts of an expression can nest.
+///
+/// Deeper expressions fail with [`ExpressionErrorKind::TooDeep`], rather than overflowing the
+/// stack while parsing.
+pub const MAX_EXPRESSION_DEPTH: usize = 100;
+
+/// Describes why an expression could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExpressionError {
+    kind: ExpressionErrorKind,
+    span: Range<usize>,
+}
+
+impl ExpressionError {
+    pub(super) const fn new(kind: ExpressionErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    /// The kind of error that was encountered
+    #[must_use]
+    pub const fn kind(&self) -> &ExpressionErrorKind {
+        &self.kind
+    }
+
+    /// The byte range of the expression that caused the error
+    #[must_use]
+    pub const fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl Error for ExpressionError {}
+
+/// The different kinds of [`ExpressionError`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpressionErrorKind {
+    /// A character that is not part of the expression language
+    UnexpectedCharacter(char),
+    /// An attribute with unclosed parentheses or backticks
+    UnterminatedAttribute,
+    /// A number that could not be represented
+    InvalidNumber(String),
+    /// An attribute that could not be parsed, with the reason it could not be parsed
+    InvalidAttribute(String),
+    /// A token that was not expected
+    UnexpectedToken {
+        /// The token that was found
+        found: String,
+        /// What was expected instead
+        expected: &'static str,
+    },
+    /// The expression ended early
+    UnexpectedEnd {
+        /// What was expected instead
+        expected: &'static str,
+    },
+    /// A function that does not exist
+    UnknownFunction(String),
+    /// A function was called with the wrong number of arguments
+    ArgumentCount {
+        /// The name of the function
+        function: String,
+        /// The number of arguments the function takes
+        expected: usize,
+        /// The number of arguments that were provided
+        found: usize,
+    },
+    /// The expression is nested deeper than [`MAX_EXPRESSION_DEPTH`]
+    TooDeep,
+}
+
+impl Display for ExpressionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedCharacter(char) => write!(f, "Unexpected character '{char}'"),
+            Self::UnterminatedAttribute => write!(f, "Unterminated attribute"),
+            Self::InvalidNumber(number) => write!(f, "Invalid number '{number}'"),
+            Self::InvalidAttribute(reason) => write!(f, "Invalid attribute: {reason}"),
+            Self::UnexpectedToken { found, expected } => {
+                write!(f, "Expected {expected}, found {found}")
+            }
+            Self::UnexpectedEnd { expected } => {
+                write!(f, "Expected {expected}, found end of expression")
+            }
+            Self::UnknownFunction(function) => write!(f, "Unknown function '{function}'"),
+            Self::ArgumentCount {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Function '{function}' takes {expected} arguments, found {found}"
+            ),
+            Self::TooDeep => write!(
+                f,
+                "Expression is nested more than {MAX_EXPRESSION_DEPTH} levels deep"
+            ),
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = ExpressionError;
+
+    /// Parses a value from the expression language.
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::Value, types::ability::Ability};
+    ///
+    /// let value: Value = "floor((Ability(Strength) - 10) / 2)".parse().unwrap();
+    /// assert_eq!(
+    ///     value,
+    ///     ((Value::Attribute(Attribute::Ability(Ability::Strength)) - Value::TEN) / Value::TWO)
+    ///         .floor()
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::Parser::new(s)?.parse_value()
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ExpressionError;
+
+    /// Parses a condition from the expression language.
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{
+    ///     attribute::Attribute,
+    ///     bonus::Condition,
+    ///     types::{flag::Flag, race::Race, toggle::Toggle},
+    /// };
+    ///
+    /// let condition: Condition = "has(Flag(Race(Elf))) and Toggle(Blocking)".parse().unwrap();
+    /// assert_eq!(
+    ///     condition,
+    ///     Condition::has(Attribute::Flag(Flag::Race(Race::Elf)))
+    ///         & Condition::has(Attribute::Toggle(Toggle::Blocking))
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::Parser::new(s)?.parse_condition()
+    }
+}
+
+/// Expression Language
+impl Value {
+    /// Writes the value in the expression language, which can be parsed back with
+    /// [`str::parse`].
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::Value, types::ability::Ability};
+    ///
+    /// let value = (Value::Attribute(Attribute::Ability(Ability::Strength)) - Value::TEN) / Value::TWO;
+    /// assert_eq!(value.to_expression(), "(Ability(Strength) - 10) / 2");
+    /// ```
+    #[must_use]
+    pub fn to_expression(&self) -> String {
+        let mut expression = String::new();
+        printer::write_value(&mut expression, self, printer::TOP);
+        expression
+    }
+}
+
+/// Expression Language
+impl Condition {
+    /// Writes the condition in the expression language, which can be parsed back with
+    /// [`str::parse`].
+    #[must_use]
+    pub fn to_expression(&self) -> String {
+        let mut expression = String::new();
+        printer::write_condition(&mut expression, self, printer::TOP);
+        expression
+    }
+}
@@ -0,0 +1,208 @@
+use core::{
+    fmt::{self, Display},
+    iter::Peekable,
+    ops::Range,
+    str::FromStr,
+};
+
+use rust_decimal::Decimal;
+
+use crate::attribute::Attribute;
+
+use super::{ExpressionError, ExpressionErrorKind};
+
+/// A single piece of an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token {
+    Number(Decimal),
+    Attribute(Attribute),
+    /// Keywords and function names
+    Word(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    LeftParen,
+    RightParen,
+    Comma,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Equal,
+    Arrow,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "'{number}'"),
+            Self::Attribute(attribute) => write!(f, "attribute '{attribute}'"),
+            Self::Word(word) => write!(f, "'{word}'"),
+            Self::Plus => write!(f, "'+'"),
+            Self::Minus => write!(f, "'-'"),
+            Self::Star => write!(f, "'*'"),
+            Self::Slash => write!(f, "'/'"),
+            Self::Percent => write!(f, "'%'"),
+            Self::LeftParen => write!(f, "'('"),
+            Self::RightParen => write!(f, "')'"),
+            Self::Comma => write!(f, "','"),
+            Self::Greater => write!(f, "'>'"),
+            Self::GreaterEqual => write!(f, "'>='"),
+            Self::Less => write!(f, "'<'"),
+            Self::LessEqual => write!(f, "'<='"),
+            Self::Equal => write!(f, "'=='"),
+            Self::Arrow => write!(f, "'=>'"),
+        }
+    }
+}
+
+/// Splits an expression into tokens, each paired with the byte range it was read from
+pub(super) fn tokenize(source: &str) -> Result<Vec<(Token, Range<usize>)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, char)) = chars.next() {
+        let symbol = match char {
+            char if char.is_whitespace() => continue,
+            '+' => Some(Token::Plus),
+            '-' => Some(Token::Minus),
+            '*' => Some(Token::Star),
+            '/' => Some(Token::Slash),
+            '%' => Some(Token::Percent),
+            '(' => Some(Token::LeftParen),
+            ')' => Some(Token::RightParen),
+            ',' => Some(Token::Comma),
+            '=' if chars.next_if(|(_, next)| *next == '>').is_some() => Some(Token::Arrow),
+            '>' | '<' | '=' => {
+                let equals = chars.next_if(|(_, next)| *next == '=').is_some();
+                match (char, equals) {
+                    ('>', false) => Some(Token::Greater),
+                    ('>', true) => Some(Token::GreaterEqual),
+                    ('<', false) => Some(Token::Less),
+                    ('<', true) => Some(Token::LessEqual),
+                    (_, true) => Some(Token::Equal),
+                    (_, false) => {
+                        return Err(ExpressionError::new(
+                            ExpressionErrorKind::UnexpectedCharacter(char),
+                            start..start + 1,
+                        ))
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(symbol) = symbol {
+            let end = chars.peek().map_or(source.len(), |(index, _)| *index);
+            tokens.push((symbol, start..end));
+            continue;
+        }
+
+        let end = if char.is_ascii_digit() {
+            take_while(&mut chars, source.len(), |char| {
+                char.is_ascii_digit() || char == '.'
+            })
+        } else if char.is_alphabetic() {
+            let end = take_while(&mut chars, source.len(), |char| {
+                char.is_alphanumeric() || char == '_'
+            });
+
+            if char.is_uppercase() && chars.next_if(|(_, next)| *next == '(').is_some() {
+                close_parentheses(&mut chars, start..source.len())?
+            } else {
+                end
+            }
+        } else if char == '`' {
+            chars
+                .find(|(_, char)| *char == '`')
+                .map(|(index, _)| index + 1)
+                .ok_or_else(|| {
+                    ExpressionError::new(
+                        ExpressionErrorKind::UnterminatedAttribute,
+                        start..source.len(),
+                    )
+                })?
+        } else {
+            return Err(ExpressionError::new(
+                ExpressionErrorKind::UnexpectedCharacter(char),
+                start..start + char.len_utf8(),
+            ));
+        };
+
+        let text = &source[start..end];
+        let token = if char.is_ascii_digit() {
+            Token::Number(Decimal::from_str(text).map_err(|_| {
+                ExpressionError::new(
+                    ExpressionErrorKind::InvalidNumber(text.to_owned()),
+                    start..end,
+                )
+            })?)
+        } else if char == '`' {
+            Token::Attribute(parse_attribute(&text[1..text.len() - 1], start..end)?)
+        } else if char.is_uppercase() {
+            Token::Attribute(parse_attribute(text, start..end)?)
+        } else {
+            Token::Word(text.to_owned())
+        };
+
+        tokens.push((token, start..end));
+    }
+
+    Ok(tokens)
+}
+
+/// Consumes characters while they match, returning the end of the consumed text
+fn take_while<I, F>(chars: &mut Peekable<I>, length: usize, predicate: F) -> usize
+where
+    I: Iterator<Item = (usize, char)>,
+    F: Fn(char) -> bool,
+{
+    while chars.next_if(|(_, char)| predicate(*char)).is_some() {}
+    chars.peek().map_or(length, |(index, _)| *index)
+}
+
+/// Consumes characters until the opening parenthesis of an attribute is closed, skipping over any
+/// strings. Returns the end of the attribute, or an error spanning `remaining` if it is not closed
+fn close_parentheses<I>(
+    chars: &mut Peekable<I>,
+    remaining: Range<usize>,
+) -> Result<usize, ExpressionError>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    let mut depth = 1usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, char) in chars.by_ref() {
+        match (in_string, char) {
+            (true, _) if escaped => escaped = false,
+            (true, '\\') => escaped = true,
+            (_, '"') => in_string = !in_string,
+            (false, '(') => depth += 1,
+            (false, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(ExpressionError::new(
+        ExpressionErrorKind::UnterminatedAttribute,
+        remaining,
+    ))
+}
+
+fn parse_attribute(text: &str, span: Range<usize>) -> Result<Attribute, ExpressionError> {
+    ron::from_str(text).map_err(|error| {
+        ExpressionError::new(
+            ExpressionErrorKind::InvalidAttribute(error.to_string()),
+            span,
+        )
+    })
+}
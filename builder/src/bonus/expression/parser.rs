@@ -0,0 +1,462 @@
+use core::ops::Range;
+
+use crate::bonus::{Condition, Value};
+
+use super::{
+    lexer::{tokenize, Token},
+    ExpressionError, ExpressionErrorKind, MAX_EXPRESSION_DEPTH,
+};
+
+/// Recursive descent parser over the tokens of an expression
+pub(super) struct Parser {
+    tokens: Vec<(Token, Range<usize>)>,
+    position: usize,
+    length: usize,
+    /// How many values and conditions are currently being parsed within each other
+    depth: usize,
+}
+
+impl Parser {
+    pub(super) fn new(source: &str) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            position: 0,
+            length: source.len(),
+            depth: 0,
+        })
+    }
+
+    /// Parses the entire expression as a [`Value`]
+    pub(super) fn parse_value(mut self) -> Result<Value, ExpressionError> {
+        let value = self.value()?;
+        self.finish()?;
+        Ok(value)
+    }
+
+    /// Parses the entire expression as a [`Condition`]
+    pub(super) fn parse_condition(mut self) -> Result<Condition, ExpressionError> {
+        let condition = self.condition()?;
+        self.finish()?;
+        Ok(condition)
+    }
+
+    fn finish(&self) -> Result<(), ExpressionError> {
+        match self.peek() {
+            Some(_) => Err(self.unexpected("end of expression")),
+            None => Ok(()),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn span(&self) -> Range<usize> {
+        self.tokens
+            .get(self.position)
+            .map_or(self.length..self.length, |(_, span)| span.clone())
+    }
+
+    /// The end of the last consumed token
+    fn previous_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .and_then(|position| self.tokens.get(position))
+            .map_or(0, |(_, span)| span.end)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Consumes the next token if it matches
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    /// Consumes the next token if it is the keyword
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matches = matches!(self.peek(), Some(Token::Word(word)) if word == keyword);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: &Token, expected: &'static str) -> Result<(), ExpressionError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn expect_keyword(
+        &mut self,
+        keyword: &str,
+        expected: &'static str,
+    ) -> Result<(), ExpressionError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    /// Runs `parse` one level deeper into the expression, failing once the expression is nested
+    /// deeper than [`MAX_EXPRESSION_DEPTH`]
+    fn nested<T, F>(&mut self, parse: F) -> Result<T, ExpressionError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ExpressionError>,
+    {
+        if self.depth >= MAX_EXPRESSION_DEPTH {
+            return Err(ExpressionError::new(
+                ExpressionErrorKind::TooDeep,
+                self.span(),
+            ));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Creates an error for the next token
+    fn unexpected(&self, expected: &'static str) -> ExpressionError {
+        let kind = self
+            .peek()
+            .map_or(ExpressionErrorKind::UnexpectedEnd { expected }, |token| {
+                ExpressionErrorKind::UnexpectedToken {
+                    found: token.to_string(),
+                    expected,
+                }
+            });
+        ExpressionError::new(kind, self.span())
+    }
+}
+
+/// Values
+impl Parser {
+    fn value(&mut self) -> Result<Value, ExpressionError> {
+        self.nested(Self::sum)
+    }
+
+    fn sum(&mut self) -> Result<Value, ExpressionError> {
+        let mut value = self.product()?;
+
+        loop {
+            if self.eat(&Token::Plus) {
+                value += self.product()?;
+            } else if self.eat(&Token::Minus) {
+                value -= self.product()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Value, ExpressionError> {
+        let mut value = self.unary()?;
+
+        loop {
+            if self.eat(&Token::Star) {
+                value *= self.unary()?;
+            } else if self.eat(&Token::Slash) {
+                value /= self.unary()?;
+            } else if self.eat(&Token::Percent) {
+                value %= self.unary()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Value, ExpressionError> {
+        if !self.eat(&Token::Minus) {
+            return self.primary();
+        }
+
+        if let Some(Token::Number(number)) = self.peek() {
+            let number = -*number;
+            self.position += 1;
+            Ok(Value::Const(number))
+        } else {
+            Ok(-self.nested(Self::unary)?)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Value, ExpressionError> {
+        let span = self.span();
+
+        match self.advance() {
+            Some(Token::Number(number)) => Ok(Value::Const(number)),
+            Some(Token::Attribute(attribute)) => Ok(Value::Attribute(attribute)),
+            Some(Token::LeftParen) => {
+                let value = self.value()?;
+                self.expect(&Token::RightParen, "')'")?;
+                Ok(value)
+            }
+            Some(Token::Word(word)) if word == "if" => {
+                let condition = self.condition()?;
+                self.expect_keyword("then", "'then'")?;
+                let if_true = self.value()?;
+                self.expect_keyword("else", "'else'")?;
+                let if_false = self.value()?;
+                Ok(Value::condition(condition, if_true, if_false))
+            }
+            Some(Token::Word(word)) => self.function(word, span),
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("a value"))
+            }
+        }
+    }
+
+    fn function(&mut self, name: String, span: Range<usize>) -> Result<Value, ExpressionError> {
+        match name.as_str() {
+            "table" => return self.table(),
+            "sum" => return Ok(Value::Sum(self.arguments()?)),
+            "product" => return Ok(Value::Product(self.arguments()?)),
+            _ => {}
+        }
+
+        let arity = match name.as_str() {
+            "floor" | "ceil" | "round" | "abs" | "sign" => 1,
+            "min" | "max" | "dice" | "pow" => 2,
+            "clamp" => 3,
+            _ => {
+                return Err(ExpressionError::new(
+                    ExpressionErrorKind::UnknownFunction(name),
+                    span,
+                ))
+            }
+        };
+
+        let arguments = self.arguments()?;
+
+        Ok(match (name.as_str(), arguments.as_slice()) {
+            ("floor", [value]) => value.clone().floor(),
+            ("ceil", [value]) => value.clone().ceil(),
+            ("round", [value]) => value.clone().round(),
+            ("abs", [value]) => value.clone().abs(),
+            ("sign", [value]) => value.clone().sign(),
+            ("pow", [base, exponent]) => base.clone().pow(exponent.clone()),
+            ("clamp", [value, min, max]) => value.clone().clamp(min.clone(), max.clone()),
+            ("min", [a, b]) => a.clone().min(b.clone()),
+            ("max", [a, b]) => a.clone().max(b.clone()),
+            ("dice", [count, size]) => Value::dice(count.clone(), size.clone()),
+            _ => {
+                return Err(ExpressionError::new(
+                    ExpressionErrorKind::ArgumentCount {
+                        found: arguments.len(),
+                        function: name,
+                        expected: arity,
+                    },
+                    span.start..self.previous_end(),
+                ))
+            }
+        })
+    }
+
+    /// Parses the key and steps of a table, such as `(key, 1 => a, 10 => b)`
+    fn table(&mut self) -> Result<Value, ExpressionError> {
+        self.expect(&Token::LeftParen, "'('")?;
+        let key = self.value()?;
+
+        let mut steps = Vec::new();
+        while self.eat(&Token::Comma) {
+            let negative = self.eat(&Token::Minus);
+            let Some(Token::Number(threshold)) = self.peek().cloned() else {
+                return Err(self.unexpected("a number"));
+            };
+            self.position += 1;
+            let threshold = if negative { -threshold } else { threshold };
+            self.expect(&Token::Arrow, "'=>'")?;
+            steps.push((threshold, self.value()?));
+        }
+
+        self.expect(&Token::RightParen, "',' or ')'")?;
+        Ok(Value::Table {
+            key: Box::new(key),
+            steps,
+        })
+    }
+
+    /// Parses a parenthesized, comma separated list of values
+    fn arguments(&mut self) -> Result<Vec<Value>, ExpressionError> {
+        self.expect(&Token::LeftParen, "'('")?;
+
+        let mut arguments = Vec::new();
+        if self.eat(&Token::RightParen) {
+            return Ok(arguments);
+        }
+
+        loop {
+            arguments.push(self.value()?);
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightParen, "',' or ')'")?;
+                return Ok(arguments);
+            }
+        }
+    }
+}
+
+/// Conditions
+impl Parser {
+    fn condition(&mut self) -> Result<Condition, ExpressionError> {
+        self.nested(Self::or)
+    }
+
+    fn or(&mut self) -> Result<Condition, ExpressionError> {
+        let mut condition = self.xor()?;
+        while self.eat_keyword("or") {
+            condition |= self.xor()?;
+        }
+        Ok(condition)
+    }
+
+    fn xor(&mut self) -> Result<Condition, ExpressionError> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("xor") {
+            condition ^= self.and()?;
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, ExpressionError> {
+        let mut condition = self.not()?;
+        while self.eat_keyword("and") {
+            condition &= self.not()?;
+        }
+        Ok(condition)
+    }
+
+    fn not(&mut self) -> Result<Condition, ExpressionError> {
+        if self.eat_keyword("not") {
+            Ok(!self.nested(Self::not)?)
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<Condition, ExpressionError> {
+        if self.eat_keyword("true") {
+            return Ok(Condition::TRUE);
+        }
+        if self.eat_keyword("false") {
+            return Ok(Condition::FALSE);
+        }
+        if self.eat_keyword("all") {
+            self.expect(&Token::LeftParen, "'('")?;
+            return Ok(Condition::All(self.conditions()?));
+        }
+        if self.eat_keyword("any") {
+            self.expect(&Token::LeftParen, "'('")?;
+            return Ok(Condition::Any(self.conditions()?));
+        }
+        if self.eat_keyword("at_least") {
+            self.expect(&Token::LeftParen, "'('")?;
+            let count = self.count()?;
+            if self.eat(&Token::RightParen) {
+                return Ok(Condition::AtLeast(count, Vec::new()));
+            }
+            self.expect(&Token::Comma, "','")?;
+            return Ok(Condition::AtLeast(count, self.conditions()?));
+        }
+        if self.eat_keyword("has") {
+            self.expect(&Token::LeftParen, "'('")?;
+            let value = self.value()?;
+            self.expect(&Token::RightParen, "')'")?;
+            return Ok(value.greater_than(Value::ZERO));
+        }
+
+        // A parenthesis can either start a value in a comparison, or a grouped condition
+        let start = self.position;
+        let comparison = match self.comparison() {
+            Ok(comparison) => return Ok(comparison),
+            Err(error) => error,
+        };
+
+        self.position = start;
+        if !self.eat(&Token::LeftParen) {
+            return Err(comparison);
+        }
+
+        let group = self.condition().and_then(|condition| {
+            self.expect(&Token::RightParen, "')'")?;
+            Ok(condition)
+        });
+
+        // Report whichever attempt got further into the expression
+        group.map_err(|group| {
+            if group.span().start >= comparison.span().start {
+                group
+            } else {
+                comparison
+            }
+        })
+    }
+
+    /// Parses a comma separated list of conditions, after the opening parenthesis
+    fn conditions(&mut self) -> Result<Vec<Condition>, ExpressionError> {
+        let mut conditions = Vec::new();
+        if self.eat(&Token::RightParen) {
+            return Ok(conditions);
+        }
+
+        loop {
+            conditions.push(self.condition()?);
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightParen, "',' or ')'")?;
+                return Ok(conditions);
+            }
+        }
+    }
+
+    /// Parses a whole, non-negative number
+    fn count(&mut self) -> Result<usize, ExpressionError> {
+        let span = self.span();
+        let Some(Token::Number(number)) = self.peek() else {
+            return Err(self.unexpected("a number"));
+        };
+
+        let count = number
+            .is_integer()
+            .then(|| usize::try_from(*number).ok())
+            .flatten()
+            .ok_or_else(|| {
+                ExpressionError::new(ExpressionErrorKind::InvalidNumber(number.to_string()), span)
+            })?;
+
+        self.position += 1;
+        Ok(count)
+    }
+
+    fn comparison(&mut self) -> Result<Condition, ExpressionError> {
+        let value = self.value()?;
+
+        let comparison: fn(Value, Value) -> Condition = match self.peek() {
+            Some(Token::Greater) => Condition::GreaterThan,
+            Some(Token::GreaterEqual) => Condition::GreaterEqualTo,
+            Some(Token::Less) => Condition::LessThan,
+            Some(Token::LessEqual) => Condition::LessEqualTo,
+            Some(Token::Equal) => Condition::EqualTo,
+            _ => {
+                return match value {
+                    Value::Attribute(attribute) => Ok(Condition::has(attribute)),
+                    _ => Err(self.unexpected("a comparison")),
+                }
+            }
+        };
+        self.position += 1;
+
+        Ok(comparison(value, self.value()?))
+    }
+}
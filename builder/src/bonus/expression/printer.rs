@@ -0,0 +1,198 @@
+use core::fmt::Write;
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Condition, Value},
+};
+
+/// Precedence where any expression can be written without parentheses
+pub(super) const TOP: u8 = 0;
+
+// Value precedences
+const SUM: u8 = 1;
+const PRODUCT: u8 = 2;
+const ATOM: u8 = 3;
+
+// Condition precedences
+const XOR: u8 = 1;
+const AND: u8 = 2;
+const NOT: u8 = 3;
+const COMPARISON: u8 = 4;
+
+/// Writes a value, wrapping it in parentheses if it binds looser than `precedence`
+pub(super) fn write_value(out: &mut String, value: &Value, precedence: u8) {
+    let own = match value {
+        Value::If { .. } => TOP,
+        Value::Add(..) | Value::Sub(..) => SUM,
+        Value::Mul(..) | Value::Div(..) | Value::Rem(..) => PRODUCT,
+        _ => ATOM,
+    };
+
+    let wrap = own < precedence;
+    if wrap {
+        out.push('(');
+    }
+
+    match value {
+        Value::Const(value) => {
+            let _ = write!(out, "{value}");
+        }
+        Value::Attribute(attribute) => write_attribute(out, attribute),
+        Value::Add(a, b) => write_operation(out, a, " + ", b, SUM),
+        Value::Sub(a, b) => write_operation(out, a, " - ", b, SUM),
+        Value::Mul(a, b) => write_operation(out, a, " * ", b, PRODUCT),
+        Value::Div(a, b) => write_operation(out, a, " / ", b, PRODUCT),
+        Value::Rem(a, b) => write_operation(out, a, " % ", b, PRODUCT),
+        Value::Min(a, b) => write_function(out, "min", &[a, b]),
+        Value::Max(a, b) => write_function(out, "max", &[a, b]),
+        Value::Floor(value) => write_function(out, "floor", &[value]),
+        Value::Ceil(value) => write_function(out, "ceil", &[value]),
+        Value::Round(value) => write_function(out, "round", &[value]),
+        Value::Abs(value) => write_function(out, "abs", &[value]),
+        Value::Dice { count, size } => write_function(out, "dice", &[count, size]),
+        Value::Pow(base, exponent) => write_function(out, "pow", &[base, exponent]),
+        Value::Clamp(value, min, max) => write_function(out, "clamp", &[value, min, max]),
+        Value::Sign(value) => write_function(out, "sign", &[value]),
+        Value::Sum(values) => write_function(out, "sum", &values.iter().collect::<Vec<_>>()),
+        Value::Product(values) => {
+            write_function(out, "product", &values.iter().collect::<Vec<_>>());
+        }
+        Value::Table { key, steps } => {
+            out.push_str("table(");
+            write_value(out, key, TOP);
+            for (threshold, value) in steps {
+                let _ = write!(out, ", {threshold} => ");
+                write_value(out, value, TOP);
+            }
+            out.push(')');
+        }
+        Value::If {
+            condition,
+            if_true,
+            if_false,
+        } => {
+            out.push_str("if ");
+            write_condition(out, condition, TOP);
+            out.push_str(" then ");
+            write_value(out, if_true, TOP);
+            out.push_str(" else ");
+            write_value(out, if_false, TOP);
+        }
+    }
+
+    if wrap {
+        out.push(')');
+    }
+}
+
+/// Writes a left-associative operation. The right side is wrapped in parentheses if it has the
+/// same precedence so that the tree structure is kept.
+fn write_operation(out: &mut String, a: &Value, operator: &str, b: &Value, precedence: u8) {
+    write_value(out, a, precedence);
+    out.push_str(operator);
+    write_value(out, b, precedence + 1);
+}
+
+fn write_function(out: &mut String, name: &str, arguments: &[&Value]) {
+    out.push_str(name);
+    out.push('(');
+    for (index, argument) in arguments.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        write_value(out, argument, TOP);
+    }
+    out.push(')');
+}
+
+/// Writes the attribute using its full variant names if they can be read back, otherwise writes
+/// its serialized form in backticks. If the attribute cannot be serialized, its display name is
+/// written instead, which may not be read back.
+fn write_attribute(out: &mut String, attribute: &Attribute) {
+    let readable = format!("{attribute:?}");
+
+    if ron::from_str::<Attribute>(&readable).is_ok_and(|parsed| parsed.eq(attribute)) {
+        out.push_str(&readable);
+    } else if let Ok(serialized) = ron::to_string(attribute) {
+        let _ = write!(out, "`{serialized}`");
+    } else {
+        let _ = write!(out, "{attribute}");
+    }
+}
+
+/// Writes a condition, wrapping it in parentheses if it binds looser than `precedence`
+pub(super) fn write_condition(out: &mut String, condition: &Condition, precedence: u8) {
+    let own = match condition {
+        Condition::Or(..) => TOP,
+        Condition::Xor(..) => XOR,
+        Condition::And(..) => AND,
+        Condition::Not(..) => NOT,
+        _ => COMPARISON,
+    };
+
+    let wrap = own < precedence;
+    if wrap {
+        out.push('(');
+    }
+
+    match condition {
+        Condition::Constant(value) => {
+            let _ = write!(out, "{value}");
+        }
+        Condition::Not(condition) => {
+            out.push_str("not ");
+            write_condition(out, condition, NOT);
+        }
+        Condition::Or(a, b) => write_logic(out, a, " or ", b, TOP),
+        Condition::Xor(a, b) => write_logic(out, a, " xor ", b, XOR),
+        Condition::And(a, b) => write_logic(out, a, " and ", b, AND),
+        Condition::GreaterThan(Value::Attribute(attribute), zero) if zero.eq(&Value::ZERO) => {
+            out.push_str("has(");
+            write_attribute(out, attribute);
+            out.push(')');
+        }
+        Condition::GreaterThan(a, b) => write_comparison(out, a, " > ", b),
+        Condition::GreaterEqualTo(a, b) => write_comparison(out, a, " >= ", b),
+        Condition::LessThan(a, b) => write_comparison(out, a, " < ", b),
+        Condition::LessEqualTo(a, b) => write_comparison(out, a, " <= ", b),
+        Condition::EqualTo(a, b) => write_comparison(out, a, " == ", b),
+        Condition::All(conditions) => write_conditions(out, "all", conditions),
+        Condition::Any(conditions) => write_conditions(out, "any", conditions),
+        Condition::AtLeast(count, conditions) => {
+            let _ = write!(out, "at_least({count}");
+            for condition in conditions {
+                out.push_str(", ");
+                write_condition(out, condition, TOP);
+            }
+            out.push(')');
+        }
+    }
+
+    if wrap {
+        out.push(')');
+    }
+}
+
+fn write_logic(out: &mut String, a: &Condition, operator: &str, b: &Condition, precedence: u8) {
+    write_condition(out, a, precedence);
+    out.push_str(operator);
+    write_condition(out, b, precedence + 1);
+}
+
+fn write_conditions(out: &mut String, name: &str, conditions: &[Condition]) {
+    out.push_str(name);
+    out.push('(');
+    for (index, condition) in conditions.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        write_condition(out, condition, TOP);
+    }
+    out.push(')');
+}
+
+fn write_comparison(out: &mut String, a: &Value, operator: &str, b: &Value) {
+    write_value(out, a, SUM);
+    out.push_str(operator);
+    write_value(out, b, SUM);
+}
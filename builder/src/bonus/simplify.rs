@@ -0,0 +1,384 @@
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+use rust_decimal::{Decimal, MathematicalOps};
+
+use super::{Bonus, Condition, Value};
+
+/// Simplification
+impl Value {
+    /// Returns an equivalent value with constants folded and identity operations removed.
+    ///
+    /// This will:
+    /// - Calculate any operation where every input is a constant
+    /// - Remove identity operations, such as adding 0 or multiplying by 1
+    /// - Replace [`Value::If`] with one of its branches if the condition is constant, or if both
+    ///   branches are the same and the condition cannot fail
+    /// - Replace [`Value::Table`] with the chosen step if the key is constant
+    /// - Combine the constants of [`Value::Sum`] and [`Value::Product`], replacing them with their
+    ///   only value if just one remains
+    ///
+    /// Operations that would fail to evaluate (such as dividing by zero) are kept as-is, so that
+    /// the error is still reported when evaluated. Parts that would be evaluated are never removed
+    /// if they could fail. [`Value::Dice`] is never folded, since its
+    /// result depends on the [`DiceStrategy`].
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::Value};
+    ///
+    /// let value = (Value::ZERO + Value::Attribute(Attribute::Dummy)) * (Value::ONE + Value::ONE);
+    /// assert_eq!(
+    ///     value.simplify(),
+    ///     Value::Attribute(Attribute::Dummy) * Value::TWO
+    /// );
+    /// ```
+    ///
+    /// [`DiceStrategy`]: crate::breakdowns::DiceStrategy
+    #[must_use]
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Const(_) | Self::Attribute(_) => self,
+            Self::Min(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => Self::Const(a.min(b)),
+                (a, b) => a.min(b),
+            },
+            Self::Max(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => Self::Const(a.max(b)),
+                (a, b) => a.max(b),
+            },
+            Self::Floor(val) => match val.simplify() {
+                Self::Const(val) => Self::Const(val.floor()),
+                val => val.floor(),
+            },
+            Self::Ceil(val) => match val.simplify() {
+                Self::Const(val) => Self::Const(val.ceil()),
+                val => val.ceil(),
+            },
+            Self::Round(val) => match val.simplify() {
+                Self::Const(val) => Self::Const(val.round()),
+                val => val.round(),
+            },
+            Self::Abs(val) => match val.simplify() {
+                Self::Const(val) => Self::Const(val.abs()),
+                val => val.abs(),
+            },
+            Self::Add(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, Decimal::checked_add, Self::add),
+                (Self::Const(zero), val) | (val, Self::Const(zero)) if zero.is_zero() => val,
+                (a, b) => a + b,
+            },
+            Self::Sub(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, Decimal::checked_sub, Self::sub),
+                (val, Self::Const(zero)) if zero.is_zero() => val,
+                (a, b) => a - b,
+            },
+            Self::Mul(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, Decimal::checked_mul, Self::mul),
+                (Self::Const(one), val) | (val, Self::Const(one)) if one == Decimal::ONE => val,
+                (a, b) => a * b,
+            },
+            Self::Div(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, Decimal::checked_div, Self::div),
+                (val, Self::Const(one)) if one == Decimal::ONE => val,
+                (a, b) => a / b,
+            },
+            Self::Rem(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, Decimal::checked_rem, Self::rem),
+                (a, b) => a % b,
+            },
+            Self::If {
+                condition,
+                if_true,
+                if_false,
+            } => match (
+                condition.simplify(),
+                if_true.simplify(),
+                if_false.simplify(),
+            ) {
+                (Condition::Constant(true), if_true, _) => if_true,
+                (Condition::Constant(false), _, if_false) => if_false,
+                (condition, if_true, if_false) if if_true == if_false && !condition.can_fail() => {
+                    if_true
+                }
+                (condition, if_true, if_false) => Self::condition(condition, if_true, if_false),
+            },
+            Self::Pow(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Const(a), Self::Const(b)) => fold(a, b, checked_pow, Self::pow),
+                (val, Self::Const(one)) if one == Decimal::ONE => val,
+                (a, b) => a.pow(b),
+            },
+            Self::Clamp(val, min, max) => match (val.simplify(), min.simplify(), max.simplify()) {
+                (Self::Const(val), Self::Const(min), Self::Const(max)) => {
+                    Self::Const(val.max(min).min(max))
+                }
+                (val, min, max) => val.clamp(min, max),
+            },
+            Self::Sign(val) => match val.simplify() {
+                Self::Const(val) if val.is_zero() => Self::ZERO,
+                Self::Const(val) if val.is_sign_negative() => Self::NEGATIVE_ONE,
+                Self::Const(_) => Self::ONE,
+                val => val.sign(),
+            },
+            Self::Sum(values) => fold_all(values, Decimal::ZERO, Decimal::checked_add, Self::Sum),
+            Self::Product(values) => {
+                fold_all(values, Decimal::ONE, Decimal::checked_mul, Self::Product)
+            }
+            Self::Dice { count, size } => Self::dice(count.simplify(), size.simplify()),
+            Self::Table { key, steps } => {
+                let steps = steps
+                    .into_iter()
+                    .map(|(threshold, value)| (threshold, value.simplify()))
+                    .collect::<Vec<_>>();
+
+                match key.simplify() {
+                    Self::Const(key) => {
+                        Self::table_step(&steps, key).cloned().unwrap_or(Self::ZERO)
+                    }
+                    key => Self::table(key, steps),
+                }
+            }
+        }
+    }
+}
+
+/// Calculates an operation on two constants. If the operation fails, the operation is kept
+fn fold<F, O>(a: Decimal, b: Decimal, calculate: F, operation: O) -> Value
+where
+    F: Fn(Decimal, Decimal) -> Option<Decimal>,
+    O: Fn(Value, Value) -> Value,
+{
+    calculate(a, b).map_or_else(|| operation(Value::Const(a), Value::Const(b)), Value::Const)
+}
+
+/// Raises `a` to the power of `b`, returning [`None`] if the result is undefined or overflows
+fn checked_pow(a: Decimal, b: Decimal) -> Option<Decimal> {
+    if (a.is_zero() && b.is_sign_negative()) || (a.is_sign_negative() && !b.is_integer()) {
+        None
+    } else {
+        a.checked_powd(b)
+    }
+}
+
+/// Simplifies each value of an n-ary operation, combining every constant into a single constant.
+/// The `identity` is removed, and the operation is replaced with its value if only one remains.
+fn fold_all<F, O>(values: Vec<Value>, identity: Decimal, calculate: F, operation: O) -> Value
+where
+    F: Fn(Decimal, Decimal) -> Option<Decimal>,
+    O: Fn(Vec<Value>) -> Value,
+{
+    let mut constant = identity;
+    let mut remaining = Vec::new();
+
+    for value in values.into_iter().map(Value::simplify) {
+        if let Value::Const(val) = value {
+            if let Some(result) = calculate(constant, val) {
+                constant = result;
+                continue;
+            }
+        }
+        remaining.push(value);
+    }
+
+    if constant != identity {
+        remaining.push(Value::Const(constant));
+    }
+
+    match remaining.len() {
+        0 => Value::Const(identity),
+        1 => remaining.pop().unwrap_or(Value::Const(identity)),
+        _ => operation(remaining),
+    }
+}
+
+/// Simplification
+impl Condition {
+    /// Returns an equivalent condition with constants folded.
+    ///
+    /// This will:
+    /// - Simplify every [`Value`] within the condition
+    /// - Calculate comparisons where both values are constant
+    /// - Remove constants from [`Condition::And`], [`Condition::Or`], and [`Condition::Xor`]
+    /// - Remove constants from [`Condition::All`], [`Condition::Any`], and [`Condition::AtLeast`],
+    ///   using the simplest of the three for the remaining conditions
+    ///
+    /// Conditions that are evaluated before the result is known are only removed if they cannot
+    /// fail, so that errors such as dividing by zero are still reported.
+    /// - Collapse double negation, such as `Not(Not(x))`
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::{Condition, Value}};
+    ///
+    /// let condition = !!Condition::has(Attribute::Dummy) & Condition::TRUE;
+    /// assert_eq!(condition.simplify(), Condition::has(Attribute::Dummy));
+    /// ```
+    #[must_use]
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Constant(_) => self,
+            Self::Not(cond) => match cond.simplify() {
+                Self::Constant(value) => Self::Constant(!value),
+                Self::Not(cond) => *cond,
+                cond => !cond,
+            },
+            Self::GreaterThan(a, b) => compare(a, b, |a, b| a > b, Self::GreaterThan),
+            Self::GreaterEqualTo(a, b) => compare(a, b, |a, b| a >= b, Self::GreaterEqualTo),
+            Self::LessThan(a, b) => compare(a, b, |a, b| a < b, Self::LessThan),
+            Self::LessEqualTo(a, b) => compare(a, b, |a, b| a <= b, Self::LessEqualTo),
+            Self::EqualTo(a, b) => compare(a, b, |a, b| a == b, Self::EqualTo),
+            Self::And(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Constant(false), _) => Self::FALSE,
+                (cond, Self::Constant(false)) if !cond.can_fail() => Self::FALSE,
+                (Self::Constant(true), cond) | (cond, Self::Constant(true)) => cond,
+                (a, b) => a & b,
+            },
+            Self::Or(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Constant(true), _) => Self::TRUE,
+                (cond, Self::Constant(true)) if !cond.can_fail() => Self::TRUE,
+                (Self::Constant(false), cond) | (cond, Self::Constant(false)) => cond,
+                (a, b) => a | b,
+            },
+            Self::Xor(a, b) => match (a.simplify(), b.simplify()) {
+                (Self::Constant(false), cond) | (cond, Self::Constant(false)) => cond,
+                (Self::Constant(true), cond) | (cond, Self::Constant(true)) => (!cond).simplify(),
+                (a, b) => a ^ b,
+            },
+            Self::All(conditions) => at_least(conditions.len(), conditions),
+            Self::Any(conditions) => at_least(1, conditions),
+            Self::AtLeast(count, conditions) => at_least(count, conditions),
+        }
+    }
+}
+
+/// Simplifies a condition that requires `count` of the conditions to be true.
+///
+/// If any condition could fail, the conditions are only simplified individually, since removing
+/// constants would change which conditions are evaluated.
+fn at_least(count: usize, conditions: Vec<Condition>) -> Condition {
+    let conditions = conditions
+        .into_iter()
+        .map(Condition::simplify)
+        .collect::<Vec<_>>();
+
+    if conditions.iter().any(Condition::can_fail) {
+        return match count {
+            count if count == conditions.len() => Condition::All(conditions),
+            1 => Condition::Any(conditions),
+            count => Condition::AtLeast(count, conditions),
+        };
+    }
+
+    let (constants, mut conditions): (Vec<_>, Vec<_>) = conditions
+        .into_iter()
+        .partition(|condition| matches!(condition, Condition::Constant(_)));
+
+    let satisfied = constants
+        .iter()
+        .filter(|condition| condition.eq(&&Condition::TRUE))
+        .count();
+    let needed = count.saturating_sub(satisfied);
+
+    if needed == 1 && conditions.len() == 1 {
+        if let Some(condition) = conditions.pop() {
+            return condition;
+        }
+    }
+
+    match needed {
+        0 => Condition::TRUE,
+        needed if needed > conditions.len() => Condition::FALSE,
+        needed if needed == conditions.len() => Condition::All(conditions),
+        1 => Condition::Any(conditions),
+        needed => Condition::AtLeast(needed, conditions),
+    }
+}
+
+/// Simplifies both values of a comparison, calculating the result if both are constant
+fn compare<F, C>(a: Value, b: Value, comparison: F, condition: C) -> Condition
+where
+    F: Fn(Decimal, Decimal) -> bool,
+    C: Fn(Value, Value) -> Condition,
+{
+    match (a.simplify(), b.simplify()) {
+        (Value::Const(a), Value::Const(b)) => Condition::Constant(comparison(a, b)),
+        (a, b) => condition(a, b),
+    }
+}
+
+/// Simplification
+impl Bonus {
+    /// Simplifies the value and condition of the bonus. A condition that is always true is
+    /// removed.
+    ///
+    /// See [`Value::simplify`] and [`Condition::simplify`]
+    #[must_use]
+    pub fn simplify(self) -> Self {
+        Self {
+            value: self.value.simplify(),
+            condition: simplify_condition(self.condition),
+            ..self
+        }
+    }
+}
+
+/// Simplifies a bonus condition, removing it if it is always true
+pub(super) fn simplify_condition(condition: Option<Condition>) -> Option<Condition> {
+    condition
+        .map(Condition::simplify)
+        .filter(|condition| !condition.eq(&Condition::TRUE))
+}
+
+/// Fallibility
+impl Value {
+    /// Returns `true` if evaluating the value could return an error, such as from dividing by zero
+    /// or an overflow. Values that could fail are never removed by [`Self::simplify`].
+    fn can_fail(&self) -> bool {
+        match self {
+            Self::Const(_) | Self::Attribute(_) => false,
+            Self::Min(a, b) | Self::Max(a, b) => a.can_fail() || b.can_fail(),
+            Self::Floor(val)
+            | Self::Ceil(val)
+            | Self::Round(val)
+            | Self::Abs(val)
+            | Self::Sign(val) => val.can_fail(),
+            Self::Clamp(val, min, max) => val.can_fail() || min.can_fail() || max.can_fail(),
+            Self::If {
+                condition,
+                if_true,
+                if_false,
+            } => condition.can_fail() || if_true.can_fail() || if_false.can_fail(),
+            Self::Table { key, steps } => {
+                key.can_fail() || steps.iter().any(|(_, value)| value.can_fail())
+            }
+            Self::Add(..)
+            | Self::Sub(..)
+            | Self::Mul(..)
+            | Self::Div(..)
+            | Self::Rem(..)
+            | Self::Pow(..)
+            | Self::Sum(_)
+            | Self::Product(_)
+            | Self::Dice { .. } => true,
+        }
+    }
+}
+
+/// Fallibility
+impl Condition {
+    /// Returns `true` if evaluating the condition could return an error from one of its values
+    fn can_fail(&self) -> bool {
+        match self {
+            Self::Constant(_) => false,
+            Self::Not(cond) => cond.can_fail(),
+            Self::GreaterThan(a, b)
+            | Self::GreaterEqualTo(a, b)
+            | Self::LessThan(a, b)
+            | Self::LessEqualTo(a, b)
+            | Self::EqualTo(a, b) => a.can_fail() || b.can_fail(),
+            Self::And(a, b) | Self::Or(a, b) | Self::Xor(a, b) => a.can_fail() || b.can_fail(),
+            Self::All(conditions) | Self::Any(conditions) | Self::AtLeast(_, conditions) => {
+                conditions.iter().any(Self::can_fail)
+            }
+        }
+    }
+}
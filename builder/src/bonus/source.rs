@@ -7,6 +7,7 @@ use utils::enums::StaticValues;
 
 use crate::{
     attribute::{Attribute, ToAttribute},
+    character::CharacterSource,
     types::{slider::Slider, toggle_group::ToggleGroup},
 };
 
@@ -21,6 +22,10 @@ pub enum BonusSource {
     ToggleGroup(ToggleGroup),
     /// Individual sliders
     Slider(Slider),
+    /// Choices made for a [`Character`]
+    ///
+    /// [`Character`]: crate::character::Character
+    Character(CharacterSource),
     /// Dictates any custom bonuses for general uses. When possible, do not use this source
     Custom(String),
     /// Used for debugging purposes.
@@ -34,6 +39,7 @@ impl Display for BonusSource {
         match self {
             Self::Slider(slider) => write!(f, "Slider: {slider}"),
             Self::Attribute(attr) => write!(f, "Attribute: {attr}"),
+            Self::Character(source) => write!(f, "Character: {source}"),
             Self::Custom(string) => write!(f, "{string}"),
             Self::Debug(num) => write!(f, "Debug: {num}"),
             Self::Base => write!(f, "Base"),
@@ -84,6 +90,7 @@ impl StaticValues for BonusSource {
         chain!(
             [Self::Base],
             Attribute::values().map(Self::Attribute),
+            CharacterSource::values().map(Self::Character),
             Slider::values().map(Self::Slider)
         )
     }
@@ -0,0 +1,77 @@
+use core::fmt::{self, Display};
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::attribute::{Attribute, AttributeDependencies};
+
+use super::Value;
+
+/// Describes how bonuses of the same [`BonusType`] combine with each other.
+///
+/// Each [`BonusType`] declares its policy with [`BonusType::stacking_policy`]. Bonuses of
+/// different [`BonusType`]s always stack.
+///
+/// [`BonusType`]: super::BonusType
+/// [`BonusType::stacking_policy`]: super::BonusType::stacking_policy
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum StackingPolicy {
+    /// Only the highest bonus and the lowest penalty are applied
+    #[serde(rename = "h", alias = "HighestOnly")]
+    HighestOnly,
+    /// Every bonus is added together
+    #[serde(rename = "s", alias = "Sum")]
+    Sum,
+    /// Every bonus is added together, up to a maximum value
+    #[serde(rename = "sc", alias = "SumCapped")]
+    SumCapped(Value),
+}
+
+impl StackingPolicy {
+    /// Returns `true` if bonuses following this policy are added together
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use builder::bonus::StackingPolicy;
+    ///
+    /// assert!(StackingPolicy::Sum.is_sum());
+    /// assert!(!StackingPolicy::HighestOnly.is_sum());
+    /// ```
+    #[must_use]
+    pub const fn is_sum(&self) -> bool {
+        !matches!(self, Self::HighestOnly)
+    }
+
+    /// Returns the maximum value, if the policy has one
+    #[must_use]
+    pub const fn cap(&self) -> Option<&Value> {
+        match self {
+            Self::SumCapped(cap) => Some(cap),
+            _ => None,
+        }
+    }
+}
+
+impl AttributeDependencies for StackingPolicy {
+    fn has_attr_dependency(&self, attribute: &Attribute) -> bool {
+        self.cap()
+            .is_some_and(|cap| cap.has_attr_dependency(attribute))
+    }
+
+    fn include_attr_dependency(&self, set: &mut HashSet<Attribute>) {
+        if let Some(cap) = self.cap() {
+            cap.include_attr_dependency(set);
+        }
+    }
+}
+
+impl Display for StackingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HighestOnly => write!(f, "Highest Only"),
+            Self::Sum => write!(f, "Sum"),
+            Self::SumCapped(cap) => write!(f, "Sum up to {cap}"),
+        }
+    }
+}
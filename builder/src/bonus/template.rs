@@ -9,7 +9,7 @@ use crate::{
     types::{flag::Flag, slider::Slider, toggle::Toggle},
 };
 
-use super::{Bonus, BonusSource, BonusType, Condition, Value};
+use super::{simplify::simplify_condition, Bonus, BonusSource, BonusType, Condition, Value};
 
 /// Represents a template of a bonus. In other words, a bonus without it's bonus source.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
@@ -33,6 +33,8 @@ pub struct BonusTemplate {
         skip_serializing_if = "Option::is_none"
     )]
     display_source: Option<BonusSource>,
+    #[serde(rename = "n", alias = "name", skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 /// Fetching Types
@@ -67,6 +69,12 @@ impl BonusTemplate {
     pub const fn display_source(&self) -> Option<&BonusSource> {
         self.display_source.as_ref()
     }
+
+    /// Returns the effect name, if there is one
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 impl BonusTemplate {
@@ -87,6 +95,7 @@ impl BonusTemplate {
             value: value.into(),
             condition: None,
             display_source: None,
+            name: None,
         }
     }
 
@@ -262,9 +271,40 @@ impl BonusTemplate {
             ..self
         }
     }
+
+    /// Sets the effect name. Bonuses with the same effect name do not stack with each other.
+    #[must_use]
+    pub fn with_name<N>(self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Clears the effect name
+    #[must_use]
+    pub fn without_name(self) -> Self {
+        Self { name: None, ..self }
+    }
 }
 
 impl BonusTemplate {
+    /// Simplifies the value and condition of the bonus. A condition that is always true is
+    /// removed.
+    ///
+    /// See [`Value::simplify`] and [`Condition::simplify`]
+    #[must_use]
+    pub fn simplify(self) -> Self {
+        Self {
+            value: self.value.simplify(),
+            condition: simplify_condition(self.condition),
+            ..self
+        }
+    }
+
     /// Converts this [`BonusTemplate`] into a [`Bonus`]
     pub fn to_bonus<S>(self, source: S) -> Bonus
     where
@@ -276,6 +316,7 @@ impl BonusTemplate {
             value: self.value,
             condition: self.condition,
             display_source: self.display_source,
+            name: self.name,
             source: source.into(),
         }
     }
@@ -289,6 +330,7 @@ impl From<Bonus> for BonusTemplate {
             value: value.value,
             condition: value.condition,
             display_source: value.display_source,
+            name: value.name,
         }
     }
 }
@@ -84,6 +84,36 @@ pub enum Value {
         #[serde(rename = "s", alias = "size")]
         size: Box<Self>,
     },
+    /// Returns the value of the step with the highest threshold that `key` has reached.
+    ///
+    /// Returns 0 if `key` is below every threshold. Steps do not need to be sorted, and the later
+    /// step is used if two steps share a threshold.
+    #[serde(rename = "t", alias = "Table")]
+    Table {
+        /// The value that is compared against each threshold
+        #[serde(rename = "k", alias = "key")]
+        key: Box<Self>,
+        /// Each threshold, and the value returned once `key` is at least that threshold
+        #[serde(rename = "s", alias = "steps")]
+        steps: Vec<(Decimal, Self)>,
+    },
+    /// Raises the first value to the power of the second value
+    #[serde(rename = "po", alias = "Pow")]
+    Pow(Box<Self>, Box<Self>),
+    /// Limits the first value to be at least the second value, and at most the third value.
+    ///
+    /// If the minimum is greater than the maximum, the maximum is returned.
+    #[serde(rename = "cl", alias = "Clamp")]
+    Clamp(Box<Self>, Box<Self>, Box<Self>),
+    /// Returns -1 if the value is negative, 1 if it is positive, and 0 if it is zero
+    #[serde(rename = "sg", alias = "Sign")]
+    Sign(Box<Self>),
+    /// Adds all of the values together, returning 0 if there are none
+    #[serde(rename = "su", alias = "Sum")]
+    Sum(Vec<Self>),
+    /// Multiplies all of the values together, returning 1 if there are none
+    #[serde(rename = "pr", alias = "Product")]
+    Product(Vec<Self>),
 }
 
 /// Constants
@@ -145,6 +175,42 @@ impl Value {
         }
     }
 
+    /// Shortcut for [`Value::Table`]
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::Value};
+    ///
+    /// // 1 from level 1, 2 from level 11, and 3 from level 21
+    /// let value = Value::table(Attribute::TotalCharacterLevel, [(1, 1), (11, 2), (21, 3)]);
+    /// ```
+    pub fn table<K, I, T, V>(key: K, steps: I) -> Self
+    where
+        K: Into<Self>,
+        I: IntoIterator<Item = (T, V)>,
+        T: Into<Decimal>,
+        V: Into<Self>,
+    {
+        Self::Table {
+            key: Box::new(key.into()),
+            steps: steps
+                .into_iter()
+                .map(|(threshold, value)| (threshold.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    /// Returns the step of a [`Value::Table`] that is used for the given key, which is the step
+    /// with the highest threshold that is at most `key`
+    #[must_use]
+    pub fn table_step(steps: &[(Decimal, Self)], key: Decimal) -> Option<&Self> {
+        steps
+            .iter()
+            .filter(|(threshold, _)| *threshold <= key)
+            .max_by_key(|(threshold, _)| *threshold)
+            .map(|(_, value)| value)
+    }
+
     /// Shortcut for [`Value::If`]
     #[must_use]
     pub fn condition<C, T, F>(condition: C, if_true: T, if_false: F) -> Self
@@ -160,6 +226,41 @@ impl Value {
         }
     }
 
+    /// Shortcut for [`Value::Sum`]
+    ///
+    /// Unlike [`Value::iter_sum`], this creates a single flat sum and accepts an empty iterator.
+    pub fn sum_all<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        Self::Sum(iter.into_iter().map(Into::into).collect())
+    }
+
+    /// Shortcut for [`Value::Product`]
+    ///
+    /// Unlike [`Value::iter_product`], this creates a single flat product and accepts an empty
+    /// iterator.
+    ///
+    /// # Example
+    /// ```
+    /// use builder::{attribute::Attribute, bonus::Value};
+    ///
+    /// // Absorption stacks multiplicatively, as 1 - (1 - a)(1 - b)
+    /// let absorption = Value::ONE
+    ///     - Value::product_all([
+    ///         Value::ONE - Value::Attribute(Attribute::Debug(0)),
+    ///         Value::ONE - Value::Attribute(Attribute::Debug(1)),
+    ///     ]);
+    /// ```
+    pub fn product_all<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        Self::Product(iter.into_iter().map(Into::into).collect())
+    }
+
     /// Calculates the mean of some list or set
     ///
     /// # Panics
@@ -259,6 +360,24 @@ impl Value {
         Self::Abs(Box::new(self))
     }
 
+    /// Shortcut for [`Value::Pow`]
+    #[must_use]
+    pub fn pow(self, exponent: Self) -> Self {
+        Self::Pow(Box::new(self), Box::new(exponent))
+    }
+
+    /// Shortcut for [`Value::Clamp`]
+    #[must_use]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Self::Clamp(Box::new(self), Box::new(min), Box::new(max))
+    }
+
+    /// Shortcut for [`Value::Sign`]
+    #[must_use]
+    pub fn sign(self) -> Self {
+        Self::Sign(Box::new(self))
+    }
+
     /// Returns the reciprocal
     ///
     /// The reciprocal of value `x` is equivalent to `1 / x`
@@ -315,8 +434,15 @@ impl Depth for Value {
             | Self::Sub(a, b)
             | Self::Mul(a, b)
             | Self::Div(a, b)
-            | Self::Rem(a, b) => a.get_depth().max(b.get_depth()),
-            Self::Round(a) | Self::Abs(a) | Self::Floor(a) | Self::Ceil(a) => a.get_depth(),
+            | Self::Rem(a, b)
+            | Self::Pow(a, b) => a.get_depth().max(b.get_depth()),
+            Self::Round(a) | Self::Abs(a) | Self::Floor(a) | Self::Ceil(a) | Self::Sign(a) => {
+                a.get_depth()
+            }
+            Self::Clamp(val, min, max) => val.get_depth().max(min.get_depth()).max(max.get_depth()),
+            Self::Sum(values) | Self::Product(values) => {
+                values.iter().map(Depth::get_depth).max().unwrap_or(0)
+            }
             Self::If {
                 condition,
                 if_true,
@@ -326,6 +452,10 @@ impl Depth for Value {
                 .max(if_true.get_depth())
                 .max(if_false.get_depth()),
             Self::Dice { count, size } => count.get_depth().max(size.get_depth()),
+            Self::Table { key, steps } => steps
+                .iter()
+                .map(|(_, value)| value.get_depth())
+                .fold(key.get_depth(), usize::max),
         }
     }
 }
@@ -340,16 +470,24 @@ impl HasDice for Value {
             | Self::Sub(a, b)
             | Self::Mul(a, b)
             | Self::Div(a, b)
-            | Self::Rem(a, b) => a.has_dice() || b.has_dice(),
-            Self::Floor(val) | Self::Ceil(val) | Self::Round(val) | Self::Abs(val) => {
-                val.has_dice()
-            }
+            | Self::Rem(a, b)
+            | Self::Pow(a, b) => a.has_dice() || b.has_dice(),
+            Self::Floor(val)
+            | Self::Ceil(val)
+            | Self::Round(val)
+            | Self::Abs(val)
+            | Self::Sign(val) => val.has_dice(),
+            Self::Clamp(val, min, max) => val.has_dice() || min.has_dice() || max.has_dice(),
+            Self::Sum(values) | Self::Product(values) => values.iter().any(HasDice::has_dice),
             Self::If {
                 condition,
                 if_true,
                 if_false,
             } => condition.has_dice() || if_true.has_dice() || if_false.has_dice(),
             Self::Dice { .. } => true,
+            Self::Table { key, steps } => {
+                key.has_dice() || steps.iter().any(|(_, value)| value.has_dice())
+            }
         }
     }
 }
@@ -370,6 +508,11 @@ impl Display for Value {
             Self::Ceil(val) => write!(f, "Ceil({val})"),
             Self::Abs(val) => write!(f, "|{val}|"),
             Self::Round(val) => write!(f, "Round({val})"),
+            Self::Pow(a, b) => write!(f, "({a} ^ {b})"),
+            Self::Clamp(val, min, max) => write!(f, "Clamp({val}, {min}, {max})"),
+            Self::Sign(val) => write!(f, "Sign({val})"),
+            Self::Sum(values) => write!(f, "Sum({})", values.iter().join(", ")),
+            Self::Product(values) => write!(f, "Product({})", values.iter().join(", ")),
             Self::If {
                 condition,
                 if_true,
@@ -378,6 +521,14 @@ impl Display for Value {
                 write!(f, "If ({condition}) then ({if_true}) else ({if_false})")
             }
             Self::Dice { count, size } => write!(f, "({count})d({size})"),
+            Self::Table { key, steps } => write!(
+                f,
+                "Table({key}: {})",
+                steps
+                    .iter()
+                    .map(|(threshold, value)| format!("{threshold} => {value}"))
+                    .join(", ")
+            ),
         }
     }
 }
@@ -391,14 +542,25 @@ impl AttributeDependencies for Value {
             | Self::Div(a, b)
             | Self::Rem(a, b)
             | Self::Max(a, b)
-            | Self::Min(a, b) => {
+            | Self::Min(a, b)
+            | Self::Pow(a, b) => {
                 a.has_attr_dependency(attribute) || b.has_attr_dependency(attribute)
             }
             Self::Const(_) => false,
             Self::Attribute(attr) => attribute.eq(attr),
-            Self::Round(val) | Self::Abs(val) | Self::Ceil(val) | Self::Floor(val) => {
+            Self::Round(val)
+            | Self::Abs(val)
+            | Self::Ceil(val)
+            | Self::Floor(val)
+            | Self::Sign(val) => val.has_attr_dependency(attribute),
+            Self::Clamp(val, min, max) => {
                 val.has_attr_dependency(attribute)
+                    || min.has_attr_dependency(attribute)
+                    || max.has_attr_dependency(attribute)
             }
+            Self::Sum(values) | Self::Product(values) => values
+                .iter()
+                .any(|value| value.has_attr_dependency(attribute)),
             Self::If {
                 condition,
                 if_true,
@@ -411,6 +573,12 @@ impl AttributeDependencies for Value {
             Self::Dice { count, size } => {
                 count.has_attr_dependency(attribute) || size.has_attr_dependency(attribute)
             }
+            Self::Table { key, steps } => {
+                key.has_attr_dependency(attribute)
+                    || steps
+                        .iter()
+                        .any(|(_, value)| value.has_attr_dependency(attribute))
+            }
         }
     }
 
@@ -423,16 +591,31 @@ impl AttributeDependencies for Value {
             | Self::Div(a, b)
             | Self::Rem(a, b)
             | Self::Min(a, b)
-            | Self::Max(a, b) => {
+            | Self::Max(a, b)
+            | Self::Pow(a, b) => {
                 a.include_attr_dependency(set);
                 b.include_attr_dependency(set);
             }
             Self::Attribute(attr) => {
                 set.insert(attr.clone());
             }
-            Self::Round(val) | Self::Abs(val) | Self::Ceil(val) | Self::Floor(val) => {
+            Self::Round(val)
+            | Self::Abs(val)
+            | Self::Ceil(val)
+            | Self::Floor(val)
+            | Self::Sign(val) => {
                 val.include_attr_dependency(set);
             }
+            Self::Clamp(val, min, max) => {
+                val.include_attr_dependency(set);
+                min.include_attr_dependency(set);
+                max.include_attr_dependency(set);
+            }
+            Self::Sum(values) | Self::Product(values) => {
+                for value in values {
+                    value.include_attr_dependency(set);
+                }
+            }
             Self::If {
                 condition,
                 if_true,
@@ -446,6 +629,12 @@ impl AttributeDependencies for Value {
                 count.include_attr_dependency(set);
                 size.include_attr_dependency(set);
             }
+            Self::Table { key, steps } => {
+                key.include_attr_dependency(set);
+                for (_, value) in steps {
+                    value.include_attr_dependency(set);
+                }
+            }
         }
     }
 }
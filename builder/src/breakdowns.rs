@@ -3,18 +3,37 @@
 mod base;
 mod breakdown;
 mod buffer;
+mod character;
+mod cycles;
+mod diagnostics;
+mod diff;
+mod distribution;
 mod dynamic;
 mod evaluation;
+mod explain;
 mod inserting;
+mod overlay;
+mod sampling;
+mod state;
+mod watch;
 
 use core::fmt::{self, Display};
-use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-use im::OrdSet;
+use im::{HashMap, HashSet, OrdSet};
 use rust_decimal::Decimal;
 
 pub use breakdown::*;
+pub use cycles::*;
+pub use diagnostics::*;
+pub use diff::*;
+pub use distribution::*;
+pub use explain::*;
+pub use overlay::*;
+pub use sampling::*;
 use serde::{Deserialize, Serialize};
+pub use state::*;
+pub use watch::*;
 
 use crate::{
     attribute::Attribute,
@@ -35,8 +54,10 @@ pub struct Breakdowns {
     #[serde(flatten)]
     cache: BreakdownCache,
     children: HashMap<BonusSource, Vec<Attribute>>,
+    dependants: HashMap<Attribute, Vec<Bonus>>,
     dynamic_bonuses: HashMap<Attribute, Vec<BonusTemplate>>,
     dice_strategy: DiceStrategy,
+    cycles: Vec<CycleError>,
 }
 
 /// Determines the strategy used when evaluating dice in bonuses
@@ -48,10 +69,22 @@ pub enum DiceStrategy {
     Average,
     /// Dice will always roll the highest value possible
     Maximum,
+    /// Each attribute with dice in its bonuses is calculated `samples` times, with every dice
+    /// rolled randomly. The attribute evaluates to the mean of the samples, and the spread is
+    /// available from [`Breakdowns::sample_statistics`].
+    ///
+    /// The same `seed` always rolls the same dice. Attributes that depend on a sampled attribute
+    /// use its mean, and dice evaluated outside of an attribute's bonuses use the average roll.
+    Sampled {
+        /// The seed used to roll the dice
+        seed: u64,
+        /// The number of times each attribute is calculated, up to [`MAX_SAMPLES`]
+        samples: u32,
+    },
 }
 
 impl DiceStrategy {
-    /// All of the possible dice strategies
+    /// All of the dice strategies that do not need any configuration
     pub const VALUES: [Self; 3] = [Self::Minimum, Self::Average, Self::Maximum];
 }
 
@@ -61,18 +94,73 @@ impl Display for DiceStrategy {
             Self::Minimum => write!(f, "Minimum"),
             Self::Average => write!(f, "Average"),
             Self::Maximum => write!(f, "Maximum"),
+            Self::Sampled { seed, samples } => {
+                write!(f, "Sampled ({samples} samples, seed {seed})")
+            }
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 struct BreakdownCache {
-    value: HashMap<Value, Decimal>,
-    condition: HashMap<Condition, bool>,
-    attribute: HashMap<Attribute, Decimal>,
+    /// Filled in as values are evaluated, which can happen through a shared reference
+    #[serde(flatten)]
+    evaluated: RwLock<EvaluatedCache>,
     breakdowns: HashMap<Attribute, AttributeBreakdown>,
     toggles: OrdSet<Toggle>,
     sliders: OrdSet<Slider>,
+    watched: HashSet<Attribute>,
+    /// The value of each watched attribute before its first change since the last
+    /// [`Breakdowns::take_changes`]
+    changes: HashMap<Attribute, Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct EvaluatedCache {
+    value: HashMap<Value, Decimal>,
+    condition: HashMap<Condition, bool>,
+    attribute: HashMap<Attribute, Decimal>,
+    diagnostics: HashMap<Attribute, Vec<EvalDiagnostic>>,
+    samples: HashMap<Attribute, SampleStatistics>,
+}
+
+impl BreakdownCache {
+    /// Locks the evaluated cache for reading.
+    ///
+    /// The cache only ever holds values that were fully evaluated, so it is still used if another
+    /// thread panicked while holding the lock.
+    fn read(&self) -> RwLockReadGuard<'_, EvaluatedCache> {
+        self.evaluated
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the evaluated cache for writing
+    fn write(&self) -> RwLockWriteGuard<'_, EvaluatedCache> {
+        self.evaluated
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the evaluated cache without locking, since it is already borrowed mutably
+    fn evaluated_mut(&mut self) -> &mut EvaluatedCache {
+        self.evaluated
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for BreakdownCache {
+    fn clone(&self) -> Self {
+        Self {
+            evaluated: RwLock::new(self.read().clone()),
+            breakdowns: self.breakdowns.clone(),
+            toggles: self.toggles.clone(),
+            sliders: self.sliders.clone(),
+            watched: self.watched.clone(),
+            changes: self.changes.clone(),
+        }
+    }
 }
 
 /// Simple methods for creating new instances, and obtaining a list of bonuses or attributes
@@ -92,8 +180,10 @@ impl Breakdowns {
             bonuses: HashMap::new(),
             cache: BreakdownCache::default(),
             children: HashMap::new(),
+            dependants: HashMap::new(),
             dynamic_bonuses: HashMap::new(),
             dice_strategy: DiceStrategy::Average,
+            cycles: Vec::new(),
         };
 
         breakdowns.insert_bonuses(get_base_bonuses());
@@ -107,7 +197,7 @@ impl Breakdowns {
     }
 
     /// Returns an iterator of attributes and their values
-    pub fn iter_attributes(&mut self) -> impl Iterator<Item = (Attribute, Decimal)> + '_ {
+    pub fn iter_attributes(&self) -> impl Iterator<Item = (Attribute, Decimal)> + '_ {
         let attributes = self.bonuses.keys().cloned().collect::<Vec<_>>();
 
         attributes
@@ -122,7 +212,7 @@ impl Breakdowns {
     }
 
     /// Returns the list of toggles that are turned on
-    pub fn get_active_toggles(&mut self) -> impl Iterator<Item = Toggle> + '_ {
+    pub fn get_active_toggles(&self) -> impl Iterator<Item = Toggle> + '_ {
         let toggles = self.get_displayed_toggles().clone();
 
         toggles
@@ -137,7 +227,7 @@ impl Breakdowns {
     }
 
     /// Returns a list of sliders and their current values
-    pub fn get_active_sliders(&mut self) -> impl Iterator<Item = (Slider, Decimal)> + '_ {
+    pub fn get_active_sliders(&self) -> impl Iterator<Item = (Slider, Decimal)> + '_ {
         let sliders = self.get_displayed_sliders().clone();
 
         sliders
@@ -155,8 +245,10 @@ impl Breakdowns {
     pub fn set_dice_strategy(&mut self, strategy: DiceStrategy) {
         self.dice_strategy = strategy;
 
-        self.cache.value.retain(|val, _| !val.has_dice());
-        self.cache.condition.retain(|val, _| !val.has_dice());
+        let cache = self.cache.evaluated_mut();
+        cache.value.retain(|val, _| !val.has_dice());
+        cache.condition.retain(|val, _| !val.has_dice());
+        cache.samples.clear();
 
         let attributes = self
             .get_bonuses()
@@ -239,9 +239,7 @@ fn sheltering() -> impl IntoIterator<Item = BonusTemplate> {
         BonusTemplate::new(
             Sheltering::MagicalTotal,
             BonusType::Stacking,
-            Sheltering::Magical
-                .to_value()
-                .min(Sheltering::MagicalCap.to_value()),
+            Sheltering::Magical,
         ),
         BonusTemplate::new(
             Sheltering::PhysicalTotal,
@@ -429,13 +427,11 @@ fn melee_fighting_styles() -> impl IntoIterator<Item = BonusTemplate> {
 
 fn dodge() -> impl Iterator<Item = BonusTemplate> {
     once(
-        BonusTemplate::new(Dodge::Total, BonusType::Stacking, {
-            let dodge_cap =
-                Dodge::Cap.to_value() + ArmorClass::MaxDexBonus.to_value().min(val!(25));
-            let capped_dodge = Dodge::Bonus.to_value().min(dodge_cap);
-            let temporary_dodge = capped_dodge + Dodge::Uncapped.to_value();
-            temporary_dodge.min(val!(95))
-        })
+        BonusTemplate::new(
+            Dodge::Total,
+            BonusType::Stacking,
+            Dodge::Bonus.to_value() + Dodge::Uncapped.to_value(),
+        )
         .with_display_source(Dodge::Bonus),
     )
 }
@@ -1,15 +1,17 @@
-use std::collections::HashMap;
-
+use im::HashMap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utils::{from_into::FromInto, hashmap::IntoGroupedHashMap};
 
 use crate::{
     attribute::Attribute,
-    bonus::{Bonus, BonusType},
+    bonus::{Bonus, BonusType, Condition, StackingPolicy, Value},
 };
 
-use super::Breakdowns;
+use super::{
+    evaluation::{collapse_named, stack_values},
+    Breakdowns,
+};
 
 /// Provides the value breakdown of a particular attribute
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
@@ -17,6 +19,10 @@ pub struct AttributeBreakdown {
     stacking: Vec<BonusEntry>,
     disabled_stacking: Vec<BonusEntry>,
     bonuses: Vec<BonusTypeEntry>,
+    suppressed: Vec<BonusEntry>,
+    uncapped_value: Decimal,
+    maximum: Option<Decimal>,
+    minimum: Option<Decimal>,
     value: Decimal,
 }
 
@@ -40,6 +46,43 @@ impl AttributeBreakdown {
         &self.bonuses
     }
 
+    /// List of bonuses that are not applied, because another bonus with the same effect name has
+    /// a higher value
+    #[must_use]
+    pub const fn suppressed(&self) -> &Vec<BonusEntry> {
+        &self.suppressed
+    }
+
+    /// The total of all applied bonuses, before the [`Self::maximum`] and [`Self::minimum`] are
+    /// applied
+    #[must_use]
+    pub const fn uncapped_value(&self) -> &Decimal {
+        &self.uncapped_value
+    }
+
+    /// The maximum value of the attribute, if it has one
+    ///
+    /// See [`Attribute::maximum`]
+    #[must_use]
+    pub const fn maximum(&self) -> Option<&Decimal> {
+        self.maximum.as_ref()
+    }
+
+    /// The minimum value of the attribute, if it has one
+    ///
+    /// See [`Attribute::minimum`]
+    #[must_use]
+    pub const fn minimum(&self) -> Option<&Decimal> {
+        self.minimum.as_ref()
+    }
+
+    /// The amount lost to the [`Self::maximum`]. This is negative if the value was raised by the
+    /// [`Self::minimum`]
+    #[must_use]
+    pub fn lost_to_limits(&self) -> Decimal {
+        self.uncapped_value.saturating_sub(self.value)
+    }
+
     /// The final reuslitng value of the attribute
     #[must_use]
     pub const fn value(&self) -> &Decimal {
@@ -51,9 +94,13 @@ impl AttributeBreakdown {
 #[derive(PartialEq, Eq, Clone, Hash, Debug, Serialize, Deserialize)]
 pub struct BonusTypeEntry {
     bonus_type: BonusType,
+    stacking_policy: StackingPolicy,
     applied: Option<BonusEntry>,
+    applied_penalty: Option<BonusEntry>,
+    stacked: Vec<BonusEntry>,
     overwritten: Vec<BonusEntry>,
     disabled: Vec<BonusEntry>,
+    value: Decimal,
 }
 
 impl BonusTypeEntry {
@@ -63,13 +110,38 @@ impl BonusTypeEntry {
         self.bonus_type
     }
 
-    /// The final applied bonus for this bonus type. This is the highest value of this bonus type
+    /// How the bonuses of this bonus type combine with each other
+    #[must_use]
+    pub const fn stacking_policy(&self) -> &StackingPolicy {
+        &self.stacking_policy
+    }
+
+    /// The final applied bonus for this bonus type. This is the highest positive value of this
+    /// bonus type.
+    ///
+    /// This is only used with [`StackingPolicy::HighestOnly`]
     #[must_use]
     pub const fn applied(&self) -> &Option<BonusEntry> {
         &self.applied
     }
 
-    /// Bonsues that do not provide the highest value
+    /// The final applied penalty for this bonus type. This is the lowest negative value of this
+    /// bonus type, and is applied alongside [`Self::applied`].
+    ///
+    /// This is only used with [`StackingPolicy::HighestOnly`]
+    #[must_use]
+    pub const fn applied_penalty(&self) -> &Option<BonusEntry> {
+        &self.applied_penalty
+    }
+
+    /// Bonuses that are added together, for any stacking policy other than
+    /// [`StackingPolicy::HighestOnly`]
+    #[must_use]
+    pub const fn stacked(&self) -> &Vec<BonusEntry> {
+        &self.stacked
+    }
+
+    /// Bonsues that do not provide the highest value or the lowest penalty
     #[must_use]
     pub const fn overwritten(&self) -> &Vec<BonusEntry> {
         &self.overwritten
@@ -80,6 +152,13 @@ impl BonusTypeEntry {
     pub const fn disabled(&self) -> &Vec<BonusEntry> {
         &self.disabled
     }
+
+    /// The total value that this bonus type adds to the attribute, after applying the stacking
+    /// policy
+    #[must_use]
+    pub const fn value(&self) -> &Decimal {
+        &self.value
+    }
 }
 
 /// Provides the details regarding the breakdown of a particular bonus
@@ -121,7 +200,7 @@ impl Breakdowns {
         A: Into<Attribute>,
     {
         let attribute = Attribute::from_into(attribute);
-        let breakdown = self.build_breakdown(&attribute);
+        let breakdown = build_breakdown(self, &attribute);
         self.cache.breakdowns.insert(attribute, breakdown);
     }
 
@@ -140,62 +219,161 @@ impl Breakdowns {
     pub fn remove_breakdown(&mut self, attribute: &Attribute) -> Option<AttributeBreakdown> {
         self.cache.breakdowns.remove(attribute)
     }
+}
+
+/// Read access to bonuses and evaluated values, so that a breakdown can be built from either a
+/// [`Breakdowns`] or an [`Overlay`](super::Overlay)
+pub(super) trait Evaluator {
+    /// Returns the bonuses to the attribute
+    fn attribute_bonuses(&self, attribute: &Attribute) -> Option<&Vec<Bonus>>;
+
+    /// Evaluates the attribute, defaulting to [`Decimal::ZERO`]
+    fn evaluate_attribute(&self, attribute: &Attribute) -> Decimal;
+
+    /// Evaluates the value, defaulting to [`Decimal::ZERO`]
+    fn evaluate_value(&self, value: &Value) -> Decimal;
+
+    /// Evaluates the condition, defaulting to `true` if there is no condition
+    fn evaluate_condition(&self, condition: Option<&Condition>) -> bool;
+}
+
+impl Evaluator for Breakdowns {
+    fn attribute_bonuses(&self, attribute: &Attribute) -> Option<&Vec<Bonus>> {
+        self.bonuses.get(attribute)
+    }
+
+    fn evaluate_attribute(&self, attribute: &Attribute) -> Decimal {
+        Self::evaluate_attribute(self, attribute)
+    }
+
+    fn evaluate_value(&self, value: &Value) -> Decimal {
+        Self::evaluate_value(self, value)
+    }
+
+    fn evaluate_condition(&self, condition: Option<&Condition>) -> bool {
+        Self::evaluate_condition(self, condition)
+    }
+}
 
-    pub(super) fn build_breakdown(&mut self, attribute: &Attribute) -> AttributeBreakdown {
-        let value = self.evaluate_attribute(attribute);
-
-        let mut attribute_bonuses = self
-            .bonuses
-            .get(attribute)
-            .cloned()
-            .into_iter()
-            .flatten()
-            .map(|bonus| {
-                (
-                    *bonus.bonus_type(),
-                    BonusEntry {
-                        value: self.evaluate_value(bonus.value()),
-                        condition: self.evaluate_condition(bonus.condition()),
-                        bonus,
-                    },
-                )
-            })
-            .into_grouped_hash_map();
-
-        let (stacking, disabled_stacking) = attribute_bonuses
-            .remove(&BonusType::Stacking)
-            .unwrap_or_default()
-            .into_iter()
-            .partition(BonusEntry::condition);
-
-        let bonuses = attribute_bonuses
-            .into_iter()
-            .map(|(bonus_type, bonuses)| {
-                let (mut bonuses, disabled): (Vec<_>, Vec<_>) =
-                    bonuses.into_iter().partition(BonusEntry::condition);
-
-                let max = bonuses
-                    .iter()
-                    .enumerate()
-                    .max_by_key(|(_, bonus)| bonus.value)
-                    .map(|(index, _)| index);
-
-                let applied = max.map(|index| bonuses.swap_remove(index));
-
-                BonusTypeEntry {
-                    overwritten: bonuses,
-                    bonus_type,
-                    applied,
-                    disabled,
+/// Builds the breakdown of an attribute from the bonuses and values within `evaluator`
+pub(super) fn build_breakdown<E>(evaluator: &E, attribute: &Attribute) -> AttributeBreakdown
+where
+    E: Evaluator,
+{
+    let value = evaluator.evaluate_attribute(attribute);
+
+    let (enabled, disabled): (Vec<_>, Vec<_>) = evaluator
+        .attribute_bonuses(attribute)
+        .cloned()
+        .into_iter()
+        .flatten()
+        .map(|bonus| BonusEntry {
+            value: evaluator.evaluate_value(bonus.value()),
+            condition: evaluator.evaluate_condition(bonus.condition()),
+            bonus,
+        })
+        .partition(BonusEntry::condition);
+
+    let (enabled, suppressed) =
+        collapse_named(enabled, |entry| entry.bonus.name(), |entry| entry.value);
+
+    let mut attribute_bonuses = enabled
+        .into_iter()
+        .chain(disabled)
+        .map(|entry| (*entry.bonus.bonus_type(), entry))
+        .into_grouped_hash_map();
+
+    let (stacking, disabled_stacking): (Vec<_>, Vec<_>) = attribute_bonuses
+        .remove(&BonusType::Stacking)
+        .unwrap_or_default()
+        .into_iter()
+        .partition(BonusEntry::condition);
+
+    let bonuses: Vec<BonusTypeEntry> = attribute_bonuses
+        .into_iter()
+        .map(|(bonus_type, bonuses)| {
+            let (bonuses, disabled): (Vec<_>, Vec<_>) =
+                bonuses.into_iter().partition(BonusEntry::condition);
+
+            let stacking_policy = bonus_type.stacking_policy();
+            let cap = stacking_policy
+                .cap()
+                .map(|cap| evaluator.evaluate_value(cap));
+            let value = stack_values(
+                &stacking_policy,
+                cap,
+                bonuses.iter().map(|entry| entry.value),
+            );
+
+            let (applied, applied_penalty, stacked, overwritten) = match stacking_policy {
+                StackingPolicy::HighestOnly => {
+                    let (applied, applied_penalty, overwritten) = split_highest(bonuses);
+                    (applied, applied_penalty, Vec::new(), overwritten)
                 }
-            })
-            .collect();
+                StackingPolicy::Sum | StackingPolicy::SumCapped(_) => {
+                    (None, None, bonuses, Vec::new())
+                }
+            };
+
+            BonusTypeEntry {
+                bonus_type,
+                stacking_policy,
+                applied,
+                applied_penalty,
+                stacked,
+                overwritten,
+                disabled,
+                value,
+            }
+        })
+        .collect();
 
-        AttributeBreakdown {
-            stacking,
-            disabled_stacking,
-            bonuses,
-            value,
-        }
+    let uncapped_value = stacking
+        .iter()
+        .map(|entry| entry.value)
+        .chain(bonuses.iter().map(|entry| entry.value))
+        .fold(Decimal::ZERO, Decimal::saturating_add);
+
+    let maximum = attribute
+        .maximum()
+        .map(|maximum| evaluator.evaluate_value(&maximum));
+    let minimum = attribute
+        .minimum()
+        .map(|minimum| evaluator.evaluate_value(&minimum));
+
+    AttributeBreakdown {
+        stacking,
+        disabled_stacking,
+        bonuses,
+        suppressed,
+        uncapped_value,
+        maximum,
+        minimum,
+        value,
     }
 }
+
+/// Splits bonuses into the highest bonus, the lowest penalty, and the remaining bonuses
+fn split_highest(
+    mut bonuses: Vec<BonusEntry>,
+) -> (Option<BonusEntry>, Option<BonusEntry>, Vec<BonusEntry>) {
+    let min = bonuses
+        .iter()
+        .enumerate()
+        .filter(|(_, bonus)| bonus.value < Decimal::ZERO)
+        .min_by_key(|(_, bonus)| bonus.value)
+        .map(|(index, _)| index);
+
+    let applied_penalty = min.map(|index| bonuses.swap_remove(index));
+
+    let max = bonuses
+        .iter()
+        .enumerate()
+        .filter(|(_, bonus)| bonus.value >= Decimal::ZERO)
+        .max_by_key(|(_, bonus)| bonus.value)
+        .map(|(index, _)| index);
+
+    let applied = max.map(|index| bonuses.swap_remove(index));
+
+    (applied, applied_penalty, bonuses)
+}
@@ -8,6 +8,16 @@ use crate::{
     bonus::{Bonus, BonusSource, CloneBonus},
 };
 
+/// Expands a bonus into itself and any bonuses cloned from it
+pub fn expand_bonus(bonus: Bonus) -> impl Iterator<Item = Bonus> {
+    bonus
+        .attribute()
+        .clone_bonus(&bonus)
+        .into_iter()
+        .flatten()
+        .chain(once(bonus))
+}
+
 #[derive(Default)]
 pub struct Buffer {
     attributes: BinaryHeap<Reverse<Attribute>>,
@@ -36,15 +46,6 @@ impl Buffer {
     where
         I: IntoIterator<Item = Bonus>,
     {
-        fn expand_bonus(bonus: Bonus) -> impl Iterator<Item = Bonus> {
-            bonus
-                .attribute()
-                .clone_bonus(&bonus)
-                .into_iter()
-                .flatten()
-                .chain(once(bonus))
-        }
-
         let bonuses = bonuses
             .into_iter()
             .flat_map(expand_bonus)
@@ -63,6 +64,18 @@ impl Buffer {
         self.bonuses.extend(bonuses);
     }
 
+    /// Inserts bonuses that have already been expanded, without replacing any bonuses currently
+    /// in the buffer
+    pub fn insert_expanded_bonuses<I>(&mut self, bonuses: I)
+    where
+        I: IntoIterator<Item = Bonus>,
+    {
+        for bonus in bonuses {
+            self.attributes.push(Reverse(bonus.attribute().clone()));
+            self.bonuses.push(bonus);
+        }
+    }
+
     pub const fn get_bonuses(&self) -> &Vec<Bonus> {
         &self.bonuses
     }
@@ -0,0 +1,38 @@
+use crate::{
+    bonus::{Bonus, BonusSource},
+    character::{Character, CharacterSource},
+};
+use utils::enums::StaticValues;
+
+use super::Breakdowns;
+
+/// Character API
+impl Breakdowns {
+    /// Creates a new [`Breakdowns`] with the bonuses from a [`Character`]
+    #[must_use]
+    pub fn from_character(character: &Character) -> Self {
+        let mut breakdowns = Self::new();
+        breakdowns.insert_character(character);
+        breakdowns
+    }
+
+    /// Replaces the bonuses from any previous [`Character`] with the bonuses from this character.
+    ///
+    /// The character controls every toggle, so any toggles that were turned on outside of the
+    /// character are turned off.
+    pub fn insert_character(&mut self, character: &Character) {
+        let toggles = self
+            .get_bonuses()
+            .map(Bonus::source)
+            .filter(|source| matches!(source, BonusSource::ToggleGroup(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.remove_sources(
+            CharacterSource::values()
+                .map(BonusSource::Character)
+                .chain(toggles),
+        );
+        self.insert_bonuses(character.to_bonuses());
+    }
+}
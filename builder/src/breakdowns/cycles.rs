@@ -0,0 +1,244 @@
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    attribute::{Attribute, AttributeDependencies},
+    bonus::{Bonus, BonusSource},
+};
+
+use super::{buffer::expand_bonus, Breakdowns};
+
+/// Describes a circular dependency between attributes that a [`Bonus`] would have created.
+///
+/// Bonuses that would create a circular dependency are never added to the [`Breakdowns`], since
+/// they would cause evaluation to loop forever.
+///
+/// The loop is described by [`Self::attributes`] and [`Self::sources`]. The attribute at index
+/// `i` has a bonus from the source at index `i` that depends on the attribute at index `i + 1`.
+/// The last attribute depends on the first attribute, closing the loop.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CycleError {
+    bonus: Box<Bonus>,
+    attributes: Vec<Attribute>,
+    sources: Vec<BonusSource>,
+}
+
+impl CycleError {
+    /// The bonus that would have closed the loop
+    #[must_use]
+    pub const fn bonus(&self) -> &Bonus {
+        &self.bonus
+    }
+
+    /// The attributes that make up the loop, starting with the attribute of [`Self::bonus`]
+    #[must_use]
+    pub const fn attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+
+    /// The sources of each bonus in the loop, starting with the source of [`Self::bonus`]
+    #[must_use]
+    pub const fn sources(&self) -> &Vec<BonusSource> {
+        &self.sources
+    }
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Circular dependency: {}",
+            self.attributes
+                .iter()
+                .zip(&self.sources)
+                .map(|(attribute, source)| format!("{attribute} ({source})"))
+                .chain(self.attributes.first().map(ToString::to_string))
+                .join(" -> ")
+        )
+    }
+}
+
+impl Error for CycleError {}
+
+/// Returns every attribute that the bonus depends on, including the attribute that created it.
+fn bonus_dependencies(bonus: &Bonus) -> HashSet<Attribute> {
+    let mut dependencies = bonus.get_attr_dependencies();
+
+    if let BonusSource::Attribute(attribute) = bonus.source() {
+        dependencies.insert(attribute.clone());
+    }
+
+    dependencies
+}
+
+/// Cycle Detection
+impl Breakdowns {
+    /// Returns the list of bonuses that were not added because they would have created a circular
+    /// dependency.
+    ///
+    /// These bonuses are retried whenever a source is removed or replaced, and are cleared whenever
+    /// their own source is removed or replaced.
+    #[must_use]
+    pub const fn cycle_errors(&self) -> &Vec<CycleError> {
+        &self.cycles
+    }
+
+    /// Inserts several bonuses into the breakdowns, only if none of them would create a circular
+    /// dependency. This also removes all bonuses that have the same bonus source.
+    ///
+    /// # Errors
+    /// Returns the first [`CycleError`] found, in which case no bonuses are inserted.
+    pub fn try_insert_bonuses<I>(&mut self, bonuses: I) -> Result<(), CycleError>
+    where
+        I: IntoIterator<Item = Bonus>,
+    {
+        let bonuses = bonuses.into_iter().collect::<Vec<_>>();
+
+        let replaced = bonuses
+            .iter()
+            .map(Bonus::source)
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let mut pending = Vec::new();
+
+        for bonus in bonuses.iter().cloned().flat_map(expand_bonus) {
+            if let Some(error) = self.find_cycle(&bonus, &pending, &replaced) {
+                return Err(error);
+            }
+            pending.push(bonus);
+        }
+
+        self.insert_bonuses(bonuses);
+        Ok(())
+    }
+
+    /// Returns `true` if any of the bonuses for `target` depend on `target`, either directly or
+    /// through bonuses currently in the breakdowns. This checks a whole batch of bonuses at once,
+    /// so that [`Self::find_cycle`] only needs to run for batches that close a loop.
+    pub(super) fn may_create_cycle(&self, target: &Attribute, bonuses: &[Bonus]) -> bool {
+        let mut visited = bonuses
+            .iter()
+            .flat_map(bonus_dependencies)
+            .collect::<HashSet<_>>();
+        let mut queue = visited.iter().cloned().collect::<VecDeque<_>>();
+
+        while let Some(attribute) = queue.pop_front() {
+            if attribute.eq(target) {
+                return true;
+            }
+
+            for child in self.bonuses.get(&attribute).into_iter().flatten() {
+                for dependency in bonus_dependencies(child) {
+                    if visited.insert(dependency.clone()) {
+                        queue.push_back(dependency);
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Checks if adding the bonus would create a circular dependency, considering the bonuses
+    /// currently in the breakdowns and any `pending` bonuses. Bonuses with a source within
+    /// `replaced` are ignored.
+    pub(super) fn find_cycle(
+        &self,
+        bonus: &Bonus,
+        pending: &[Bonus],
+        replaced: &HashSet<BonusSource>,
+    ) -> Option<CycleError> {
+        find_cycle(
+            |attribute| self.bonuses.get(attribute),
+            bonus,
+            pending,
+            replaced,
+        )
+    }
+}
+
+/// Checks if adding the bonus would create a circular dependency, considering the bonuses returned
+/// by `get_bonuses` and any `pending` bonuses. Bonuses with a source within `replaced` are ignored.
+pub(super) fn find_cycle<'a, F>(
+    get_bonuses: F,
+    bonus: &Bonus,
+    pending: &[Bonus],
+    replaced: &HashSet<BonusSource>,
+) -> Option<CycleError>
+where
+    F: Fn(&Attribute) -> Option<&'a Vec<Bonus>>,
+{
+    let target = bonus.attribute();
+    let dependencies = bonus_dependencies(bonus);
+
+    if dependencies.is_empty() {
+        return None;
+    }
+
+    // Maps each visited attribute to the attribute (and source) that depends on it
+    let mut parents: HashMap<Attribute, (Attribute, BonusSource)> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for dependency in dependencies {
+        parents.insert(dependency.clone(), (target.clone(), bonus.source().clone()));
+        queue.push_back(dependency);
+    }
+
+    while let Some(attribute) = queue.pop_front() {
+        if attribute.eq(target) {
+            let mut attributes = Vec::new();
+            let mut sources = Vec::new();
+
+            let mut current = attribute;
+            loop {
+                let (parent, source) = parents.get(&current)?.clone();
+                attributes.push(parent.clone());
+                sources.push(source);
+                if parent.eq(target) {
+                    break;
+                }
+                current = parent;
+            }
+
+            attributes.reverse();
+            sources.reverse();
+
+            return Some(CycleError {
+                bonus: Box::new(bonus.clone()),
+                attributes,
+                sources,
+            });
+        }
+
+        let bonuses = get_bonuses(&attribute)
+            .into_iter()
+            .flatten()
+            .filter(|bonus| !replaced.contains(bonus.source()))
+            .chain(
+                pending
+                    .iter()
+                    .filter(|bonus| bonus.attribute().eq(&attribute)),
+            );
+
+        for child in bonuses {
+            for dependency in bonus_dependencies(child) {
+                if !parents.contains_key(&dependency) {
+                    parents.insert(
+                        dependency.clone(),
+                        (attribute.clone(), child.source().clone()),
+                    );
+                    queue.push_back(dependency);
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,170 @@
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Bonus, BonusSource, BonusType, Value},
+};
+
+use super::Breakdowns;
+
+/// Describes why a [`Value`] could not be evaluated.
+///
+/// Each variant contains the expression that failed to evaluate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EvalError {
+    /// The expression divided by zero, or took the remainder of a division by zero
+    DivisionByZero(Value),
+    /// The expression resulted in a number too large to be represented
+    Overflow(Value),
+    /// The expression has no real result, such as raising a negative number to a fractional power
+    Undefined(Value),
+    /// The expression rolled more than [`MAX_DICE_COUNT`] dice while sampling
+    ///
+    /// [`MAX_DICE_COUNT`]: super::MAX_DICE_COUNT
+    TooManyDice(Value),
+}
+
+impl EvalError {
+    /// The expression that failed to evaluate
+    #[must_use]
+    pub const fn expression(&self) -> &Value {
+        match self {
+            Self::DivisionByZero(value)
+            | Self::Overflow(value)
+            | Self::Undefined(value)
+            | Self::TooManyDice(value) => value,
+        }
+    }
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DivisionByZero(value) => write!(f, "Division by zero in {value}"),
+            Self::Overflow(value) => write!(f, "Overflow in {value}"),
+            Self::Undefined(value) => write!(f, "Undefined result in {value}"),
+            Self::TooManyDice(value) => write!(f, "Too many dice rolled in {value}"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+/// The part of an attribute's calculation that failed to evaluate
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DiagnosticOrigin {
+    /// A bonus from this source
+    Bonus(BonusSource),
+    /// The attribute's maximum, from [`Attribute::maximum`]
+    Maximum(Value),
+    /// The attribute's minimum, from [`Attribute::minimum`]
+    Minimum(Value),
+    /// The cap of a bonus type's stacking policy
+    Cap(BonusType, Value),
+}
+
+impl Display for DiagnosticOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bonus(source) => write!(f, "Bonus from {source}"),
+            Self::Maximum(limit) => write!(f, "Maximum {limit}"),
+            Self::Minimum(limit) => write!(f, "Minimum {limit}"),
+            Self::Cap(bonus_type, limit) => write!(f, "{bonus_type} cap {limit}"),
+        }
+    }
+}
+
+/// Records part of an attribute that failed to evaluate.
+///
+/// A bonus whose value fails to evaluate is treated as if its value was [`Decimal::ZERO`], and a
+/// bonus whose condition fails to evaluate is treated as if its condition was `false`. A limit
+/// that fails to evaluate is ignored.
+///
+/// [`Decimal::ZERO`]: rust_decimal::Decimal::ZERO
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EvalDiagnostic {
+    attribute: Attribute,
+    origin: DiagnosticOrigin,
+    error: EvalError,
+}
+
+impl EvalDiagnostic {
+    pub(super) fn new(bonus: &Bonus, error: EvalError) -> Self {
+        Self {
+            attribute: bonus.attribute().clone(),
+            origin: DiagnosticOrigin::Bonus(bonus.source().clone()),
+            error,
+        }
+    }
+
+    pub(super) const fn limit(
+        attribute: Attribute,
+        origin: DiagnosticOrigin,
+        error: EvalError,
+    ) -> Self {
+        Self {
+            attribute,
+            origin,
+            error,
+        }
+    }
+
+    /// The attribute that failed
+    #[must_use]
+    pub const fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    /// The part of the attribute that failed
+    #[must_use]
+    pub const fn origin(&self) -> &DiagnosticOrigin {
+        &self.origin
+    }
+
+    /// The source of the bonus that failed, or [`None`] if a limit failed
+    #[must_use]
+    pub const fn source(&self) -> Option<&BonusSource> {
+        match &self.origin {
+            DiagnosticOrigin::Bonus(source) => Some(source),
+            _ => None,
+        }
+    }
+
+    /// The error that was encountered
+    #[must_use]
+    pub const fn error(&self) -> &EvalError {
+        &self.error
+    }
+}
+
+impl Display for EvalDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.origin {
+            DiagnosticOrigin::Bonus(source) => write!(
+                f,
+                "Bonus to {} from {source}: {}",
+                self.attribute, self.error
+            ),
+            origin => write!(f, "{origin} of {}: {}", self.attribute, self.error),
+        }
+    }
+}
+
+impl Breakdowns {
+    /// Returns every bonus that currently fails to evaluate
+    pub fn diagnostics(&self) -> impl Iterator<Item = EvalDiagnostic> {
+        self.cache
+            .read()
+            .diagnostics
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
@@ -0,0 +1,319 @@
+use core::fmt::{self, Display};
+use std::collections::HashSet;
+
+use rust_decimal::Decimal;
+
+use crate::{attribute::Attribute, bonus::Bonus};
+
+use super::{
+    breakdown::{build_breakdown, Evaluator},
+    AttributeBreakdown, BonusEntry, Breakdowns,
+};
+
+/// Describes how a bonus was used when calculating an attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BonusStatus {
+    /// The bonus was added to the attribute
+    Applied,
+    /// The bonus was not added, because a better bonus (or worse penalty) of the same type was
+    /// applied
+    Overwritten,
+    /// The bonus was not added, because its condition is false
+    Disabled,
+    /// The bonus was not added, because another bonus with the same effect name was applied
+    Suppressed,
+}
+
+impl Display for BonusStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Applied => write!(f, "Applied"),
+            Self::Overwritten => write!(f, "Overwritten"),
+            Self::Disabled => write!(f, "Disabled"),
+            Self::Suppressed => write!(f, "Suppressed"),
+        }
+    }
+}
+
+impl AttributeBreakdown {
+    /// Returns every bonus entry within the breakdown, along with how it was used
+    pub fn entries(&self) -> impl Iterator<Item = (&BonusEntry, BonusStatus)> {
+        let stacking = self
+            .stacking()
+            .iter()
+            .map(|entry| (entry, BonusStatus::Applied));
+        let disabled_stacking = self
+            .disabled_stacking()
+            .iter()
+            .map(|entry| (entry, BonusStatus::Disabled));
+
+        let bonuses = self.bonuses().iter().flat_map(|bonus_type| {
+            let applied = bonus_type
+                .applied()
+                .iter()
+                .chain(bonus_type.applied_penalty())
+                .chain(bonus_type.stacked())
+                .map(|entry| (entry, BonusStatus::Applied));
+            let overwritten = bonus_type
+                .overwritten()
+                .iter()
+                .map(|entry| (entry, BonusStatus::Overwritten));
+            let disabled = bonus_type
+                .disabled()
+                .iter()
+                .map(|entry| (entry, BonusStatus::Disabled));
+            applied.chain(overwritten).chain(disabled)
+        });
+
+        let suppressed = self
+            .suppressed()
+            .iter()
+            .map(|entry| (entry, BonusStatus::Suppressed));
+
+        stacking
+            .chain(disabled_stacking)
+            .chain(bonuses)
+            .chain(suppressed)
+    }
+}
+
+/// The value and status of a bonus at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BonusState {
+    value: Decimal,
+    status: BonusStatus,
+}
+
+impl BonusState {
+    /// What the value of the bonus evaluated to
+    #[must_use]
+    pub const fn value(&self) -> &Decimal {
+        &self.value
+    }
+
+    /// How the bonus was used
+    #[must_use]
+    pub const fn status(&self) -> BonusStatus {
+        self.status
+    }
+}
+
+/// Describes how a single bonus changed between two [`Breakdowns`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BonusDiff {
+    bonus: Bonus,
+    before: Option<BonusState>,
+    after: Option<BonusState>,
+}
+
+impl BonusDiff {
+    /// The bonus that changed
+    #[must_use]
+    pub const fn bonus(&self) -> &Bonus {
+        &self.bonus
+    }
+
+    /// The state of the bonus before the change, or [`None`] if the bonus was added
+    #[must_use]
+    pub const fn before(&self) -> Option<&BonusState> {
+        self.before.as_ref()
+    }
+
+    /// The state of the bonus after the change, or [`None`] if the bonus was removed
+    #[must_use]
+    pub const fn after(&self) -> Option<&BonusState> {
+        self.after.as_ref()
+    }
+}
+
+/// Describes how a single attribute changed between two [`Breakdowns`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeDiff {
+    attribute: Attribute,
+    before: Decimal,
+    after: Decimal,
+    bonuses: Vec<BonusDiff>,
+}
+
+impl AttributeDiff {
+    /// The attribute that changed
+    #[must_use]
+    pub const fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    /// The value of the attribute before the change
+    #[must_use]
+    pub const fn before(&self) -> &Decimal {
+        &self.before
+    }
+
+    /// The value of the attribute after the change
+    #[must_use]
+    pub const fn after(&self) -> &Decimal {
+        &self.after
+    }
+
+    /// The difference between the value after and the value before the change
+    #[must_use]
+    pub fn delta(&self) -> Decimal {
+        self.after.saturating_sub(self.before)
+    }
+
+    /// Every bonus that was added, removed, or changed
+    #[must_use]
+    pub const fn bonuses(&self) -> &Vec<BonusDiff> {
+        &self.bonuses
+    }
+
+    /// Bonuses that were added
+    pub fn added(&self) -> impl Iterator<Item = &BonusDiff> {
+        self.bonuses.iter().filter(|diff| diff.before.is_none())
+    }
+
+    /// Bonuses that were removed
+    pub fn removed(&self) -> impl Iterator<Item = &BonusDiff> {
+        self.bonuses.iter().filter(|diff| diff.after.is_none())
+    }
+
+    /// Bonuses that exist in both, but had their value or status changed
+    pub fn changed(&self) -> impl Iterator<Item = &BonusDiff> {
+        self.bonuses
+            .iter()
+            .filter(|diff| diff.before.is_some() && diff.after.is_some())
+    }
+}
+
+impl Display for AttributeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({:+})",
+            self.attribute,
+            self.before,
+            self.after,
+            self.delta()
+        )
+    }
+}
+
+/// Describes every attribute that changed between two [`Breakdowns`]
+///
+/// Created with [`Breakdowns::diff`] or [`Overlay::diff`](super::Overlay::diff)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BreakdownsDiff {
+    attributes: Vec<AttributeDiff>,
+}
+
+impl BreakdownsDiff {
+    /// Every attribute whose value changed, sorted by attribute
+    #[must_use]
+    pub const fn attributes(&self) -> &Vec<AttributeDiff> {
+        &self.attributes
+    }
+
+    /// Returns the change to the given attribute, if it changed
+    #[must_use]
+    pub fn get(&self, attribute: &Attribute) -> Option<&AttributeDiff> {
+        self.attributes
+            .iter()
+            .find(|diff| diff.attribute.eq(attribute))
+    }
+
+    /// Returns `true` if no attribute values changed
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl Breakdowns {
+    /// Compares this [`Breakdowns`] (before) with another [`Breakdowns`] (after), and returns every
+    /// attribute whose value changed.
+    pub fn diff(&self, other: &Self) -> BreakdownsDiff {
+        let attributes = self
+            .bonuses
+            .keys()
+            .chain(other.bonuses.keys())
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        diff_attributes(self, other, attributes)
+    }
+}
+
+/// Compares the value of each attribute in `before` and `after`, and returns every attribute whose
+/// value changed
+pub(super) fn diff_attributes<B, A, I>(before: &B, after: &A, attributes: I) -> BreakdownsDiff
+where
+    B: Evaluator,
+    A: Evaluator,
+    I: IntoIterator<Item = Attribute>,
+{
+    let mut attributes = attributes.into_iter().collect::<Vec<_>>();
+
+    attributes.sort();
+
+    let attributes = attributes
+        .into_iter()
+        .filter_map(|attribute| {
+            let before_value = before.evaluate_attribute(&attribute);
+            let after_value = after.evaluate_attribute(&attribute);
+
+            (before_value != after_value).then(|| AttributeDiff {
+                bonuses: diff_bonuses(
+                    &build_breakdown(before, &attribute),
+                    &build_breakdown(after, &attribute),
+                ),
+                attribute,
+                before: before_value,
+                after: after_value,
+            })
+        })
+        .collect();
+
+    BreakdownsDiff { attributes }
+}
+
+/// Matches up the bonuses in two breakdowns, returning bonuses that were added, removed, or
+/// changed
+fn diff_bonuses(before: &AttributeBreakdown, after: &AttributeBreakdown) -> Vec<BonusDiff> {
+    let mut remaining = after
+        .entries()
+        .map(|(entry, status)| (entry, state(entry, status)))
+        .collect::<Vec<_>>();
+
+    let mut diffs = Vec::new();
+
+    for (entry, status) in before.entries() {
+        let before = state(entry, status);
+
+        let index = remaining
+            .iter()
+            .position(|(other, _)| other.bonus().eq(entry.bonus()));
+
+        match index.map(|index| remaining.swap_remove(index)) {
+            Some((_, after)) if after == before => {}
+            after => diffs.push(BonusDiff {
+                bonus: entry.bonus().clone(),
+                before: Some(before),
+                after: after.map(|(_, after)| after),
+            }),
+        }
+    }
+
+    diffs.extend(remaining.into_iter().map(|(entry, after)| BonusDiff {
+        bonus: entry.bonus().clone(),
+        before: None,
+        after: Some(after),
+    }));
+
+    diffs
+}
+
+const fn state(entry: &BonusEntry, status: BonusStatus) -> BonusState {
+    BonusState {
+        value: *entry.value(),
+        status,
+    }
+}
@@ -0,0 +1,398 @@
+use rust_decimal::{Decimal, MathematicalOps};
+
+use crate::bonus::{HasDice, Value};
+
+use super::{
+    evaluation::{divide, power, remainder, sign},
+    Breakdowns, EvalError,
+};
+
+/// The most possible results a [`Distribution`] keeps before neighbouring results are combined
+const MAX_OUTCOMES: usize = 256;
+
+/// Every possible result of a [`Value`] that contains dice, along with how likely each result is.
+///
+/// Distributions with more than 256 possible results are compressed by combining neighbouring
+/// results into their weighted mean. This keeps the mean of the distribution exact, while
+/// slightly underestimating the variance and approximating percentiles.
+///
+/// Created with [`Breakdowns::evaluate_distribution`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    /// Each result and its probability, sorted by result
+    outcomes: Vec<(Decimal, Decimal)>,
+}
+
+impl Distribution {
+    /// A distribution that always results in the same value
+    #[must_use]
+    pub fn constant(value: Decimal) -> Self {
+        Self {
+            outcomes: vec![(value, Decimal::ONE)],
+        }
+    }
+
+    /// Creates a distribution from each result and its probability. Results that appear multiple
+    /// times are combined, and results with no probability are removed.
+    pub fn new<I>(outcomes: I) -> Self
+    where
+        I: IntoIterator<Item = (Decimal, Decimal)>,
+    {
+        let mut sorted = outcomes
+            .into_iter()
+            .filter(|(_, probability)| probability.is_sign_positive() && !probability.is_zero())
+            .collect::<Vec<_>>();
+        sorted.sort_by_key(|(value, _)| *value);
+
+        let mut outcomes: Vec<(Decimal, Decimal)> = Vec::with_capacity(sorted.len());
+        for (value, probability) in sorted {
+            match outcomes.last_mut() {
+                Some((last, total)) if *last == value => {
+                    *total = total.saturating_add(probability);
+                }
+                _ => outcomes.push((value, probability)),
+            }
+        }
+
+        Self { outcomes }.compress()
+    }
+
+    /// The distribution of rolling `count` dice with `size` sides, and adding them together
+    fn dice(count: Decimal, size: Decimal, value: &Value) -> Result<Self, EvalError> {
+        if !count.is_integer() || !size.is_integer() {
+            return Err(EvalError::Undefined(value.clone()));
+        }
+        if count <= Decimal::ZERO || size <= Decimal::ZERO {
+            return Ok(Self::constant(Decimal::ZERO));
+        }
+
+        let mut die = Self::uniform(size, value)?;
+        let mut total = Self::constant(Decimal::ZERO);
+        let mut remaining = count;
+
+        // Doubles up the dice, adding them to the total for each binary digit of the count
+        loop {
+            if remaining % Decimal::TWO == Decimal::ONE {
+                total = total.combine(&die, |a, b| {
+                    a.checked_add(b)
+                        .ok_or_else(|| EvalError::Overflow(value.clone()))
+                })?;
+            }
+            remaining = (remaining / Decimal::TWO).floor();
+            if remaining.is_zero() {
+                return Ok(total);
+            }
+            die = die.combine(&die, |a, b| {
+                a.checked_add(b)
+                    .ok_or_else(|| EvalError::Overflow(value.clone()))
+            })?;
+        }
+    }
+
+    /// The distribution of a single die, with each result from 1 to `size` equally likely
+    fn uniform(size: Decimal, value: &Value) -> Result<Self, EvalError> {
+        let overflow = || EvalError::Overflow(value.clone());
+        let max_outcomes = Decimal::from(MAX_OUTCOMES);
+        let width = (size / max_outcomes).ceil().max(Decimal::ONE);
+
+        let mut outcomes = Vec::new();
+        let mut start = Decimal::ONE;
+        loop {
+            // The last group ends at `size`, even if a full group would go past the largest decimal
+            let end = start
+                .checked_add(width - Decimal::ONE)
+                .map_or(size, |end| end.min(size));
+            let count = (end - start)
+                .checked_add(Decimal::ONE)
+                .ok_or_else(overflow)?;
+            outcomes.push(((end - start) / Decimal::TWO + start, count / size));
+
+            if end >= size {
+                return Ok(Self { outcomes });
+            }
+            start = end.checked_add(Decimal::ONE).ok_or_else(overflow)?;
+        }
+    }
+
+    /// Combines neighbouring results until there are at most [`MAX_OUTCOMES`] results
+    fn compress(self) -> Self {
+        if self.outcomes.len() <= MAX_OUTCOMES {
+            return self;
+        }
+
+        let size = self.outcomes.len().div_ceil(MAX_OUTCOMES);
+        let outcomes = self
+            .outcomes
+            .chunks(size)
+            .map(|chunk| {
+                let probability = chunk
+                    .iter()
+                    .fold(Decimal::ZERO, |total, (_, p)| total.saturating_add(*p));
+                let mean = chunk.iter().fold(Decimal::ZERO, |total, (value, p)| {
+                    total.saturating_add(value.saturating_mul(*p))
+                });
+                (mean.checked_div(probability).unwrap_or(mean), probability)
+            })
+            .collect();
+
+        Self { outcomes }
+    }
+
+    /// Applies a function to every result
+    fn map<F>(&self, function: F) -> Result<Self, EvalError>
+    where
+        F: Fn(Decimal) -> Result<Decimal, EvalError>,
+    {
+        let outcomes = self
+            .outcomes
+            .iter()
+            .map(|(value, probability)| Ok((function(*value)?, *probability)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(outcomes))
+    }
+
+    /// Combines every pair of results from two independent distributions
+    fn combine<F>(&self, other: &Self, function: F) -> Result<Self, EvalError>
+    where
+        F: Fn(Decimal, Decimal) -> Result<Decimal, EvalError>,
+    {
+        let mut outcomes = Vec::with_capacity(self.outcomes.len() * other.outcomes.len());
+
+        for (a, p_a) in &self.outcomes {
+            for (b, p_b) in &other.outcomes {
+                outcomes.push((function(*a, *b)?, p_a.saturating_mul(*p_b)));
+            }
+        }
+
+        Ok(Self::new(outcomes))
+    }
+
+    /// Combines distributions, where each distribution is chosen with the paired probability
+    fn mixture<I>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = (Decimal, Self)>,
+    {
+        Self::new(parts.into_iter().flat_map(|(weight, distribution)| {
+            distribution
+                .outcomes
+                .into_iter()
+                .map(move |(value, probability)| (value, probability.saturating_mul(weight)))
+        }))
+    }
+
+    /// Each possible result and its probability, sorted from the lowest to highest result
+    #[must_use]
+    pub fn outcomes(&self) -> &[(Decimal, Decimal)] {
+        &self.outcomes
+    }
+
+    /// The lowest possible result
+    #[must_use]
+    pub fn min(&self) -> Decimal {
+        self.outcomes
+            .first()
+            .map_or(Decimal::ZERO, |(value, _)| *value)
+    }
+
+    /// The highest possible result
+    #[must_use]
+    pub fn max(&self) -> Decimal {
+        self.outcomes
+            .last()
+            .map_or(Decimal::ZERO, |(value, _)| *value)
+    }
+
+    /// The average result
+    #[must_use]
+    pub fn mean(&self) -> Decimal {
+        self.outcomes
+            .iter()
+            .fold(Decimal::ZERO, |total, (value, probability)| {
+                total.saturating_add(value.saturating_mul(*probability))
+            })
+    }
+
+    /// The average squared distance of each result from the mean
+    #[must_use]
+    pub fn variance(&self) -> Decimal {
+        let mean = self.mean();
+        self.outcomes
+            .iter()
+            .fold(Decimal::ZERO, |total, (value, probability)| {
+                let distance = value.saturating_sub(mean);
+                total.saturating_add(
+                    distance
+                        .saturating_mul(distance)
+                        .saturating_mul(*probability),
+                )
+            })
+    }
+
+    /// The square root of the [`variance`]
+    ///
+    /// [`variance`]: Self::variance
+    #[must_use]
+    pub fn standard_deviation(&self) -> Decimal {
+        self.variance().sqrt().unwrap_or(Decimal::ZERO)
+    }
+
+    /// The probability that the result is at least `value`
+    #[must_use]
+    pub fn probability_at_least(&self, value: Decimal) -> Decimal {
+        self.outcomes
+            .iter()
+            .filter(|(result, _)| *result >= value)
+            .fold(Decimal::ZERO, |total, (_, probability)| {
+                total.saturating_add(*probability)
+            })
+            .min(Decimal::ONE)
+    }
+
+    /// Returns the lowest result where the chance of rolling that result or lower is at least
+    /// `percentile`, which is between 0 and 1.
+    ///
+    /// For example, `percentile(0.1)` returns a result that is met or exceeded at least 90% of
+    /// the time.
+    #[must_use]
+    pub fn percentile(&self, percentile: Decimal) -> Decimal {
+        let mut total = Decimal::ZERO;
+
+        for (value, probability) in &self.outcomes {
+            total = total.saturating_add(*probability);
+            if total >= percentile {
+                return *value;
+            }
+        }
+
+        self.max()
+    }
+}
+
+/// Dice Distributions
+impl Breakdowns {
+    /// Evaluates every possible result of a value, rolling each [`Value::Dice`] independently
+    /// instead of using the current [`DiceStrategy`].
+    ///
+    /// Attributes and conditions are evaluated as usual, so any dice within them still use the
+    /// current [`DiceStrategy`].
+    ///
+    /// # Errors
+    /// Returns an [`EvalError`] if any possible result cannot be evaluated, such as when dividing
+    /// by zero. Also returns [`EvalError::Undefined`] if the number or size of dice is not a
+    /// whole number.
+    ///
+    /// [`DiceStrategy`]: super::DiceStrategy
+    pub fn evaluate_distribution(&self, value: &Value) -> Result<Distribution, EvalError> {
+        if !value.has_dice() {
+            return self.try_evaluate_value(value).map(Distribution::constant);
+        }
+
+        let overflow = || EvalError::Overflow(value.clone());
+
+        match value {
+            Value::Const(_) | Value::Attribute(_) => {
+                self.try_evaluate_value(value).map(Distribution::constant)
+            }
+            Value::Min(a, b) => self.combine_distributions(a, b, |a, b| Ok(a.min(b))),
+            Value::Max(a, b) => self.combine_distributions(a, b, |a, b| Ok(a.max(b))),
+            Value::Floor(val) => self.evaluate_distribution(val)?.map(|val| Ok(val.floor())),
+            Value::Ceil(val) => self.evaluate_distribution(val)?.map(|val| Ok(val.ceil())),
+            Value::Round(val) => self.evaluate_distribution(val)?.map(|val| Ok(val.round())),
+            Value::Abs(val) => self.evaluate_distribution(val)?.map(|val| Ok(val.abs())),
+            Value::Sign(val) => self.evaluate_distribution(val)?.map(|val| Ok(sign(val))),
+            Value::Add(a, b) => {
+                self.combine_distributions(a, b, |a, b| a.checked_add(b).ok_or_else(overflow))
+            }
+            Value::Sub(a, b) => {
+                self.combine_distributions(a, b, |a, b| a.checked_sub(b).ok_or_else(overflow))
+            }
+            Value::Mul(a, b) => {
+                self.combine_distributions(a, b, |a, b| a.checked_mul(b).ok_or_else(overflow))
+            }
+            Value::Div(a, b) => self.combine_distributions(a, b, |a, b| divide(a, b, value)),
+            Value::Rem(a, b) => self.combine_distributions(a, b, |a, b| remainder(a, b, value)),
+            Value::Pow(a, b) => self.combine_distributions(a, b, |a, b| power(a, b, value)),
+            Value::Clamp(val, min, max) => {
+                let clamped = self.combine_distributions(val, min, |val, min| Ok(val.max(min)))?;
+                clamped.combine(&self.evaluate_distribution(max)?, |val, max| {
+                    Ok(val.min(max))
+                })
+            }
+            Value::Sum(values) => {
+                values
+                    .iter()
+                    .try_fold(Distribution::constant(Decimal::ZERO), |total, val| {
+                        total.combine(&self.evaluate_distribution(val)?, |a, b| {
+                            a.checked_add(b).ok_or_else(overflow)
+                        })
+                    })
+            }
+            Value::Product(values) => {
+                values
+                    .iter()
+                    .try_fold(Distribution::constant(Decimal::ONE), |total, val| {
+                        total.combine(&self.evaluate_distribution(val)?, |a, b| {
+                            a.checked_mul(b).ok_or_else(overflow)
+                        })
+                    })
+            }
+            Value::If {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                if self.try_evaluate_condition(condition)? {
+                    self.evaluate_distribution(if_true)
+                } else {
+                    self.evaluate_distribution(if_false)
+                }
+            }
+            Value::Dice { count, size } => {
+                let count = self.evaluate_distribution(count)?;
+                let size = self.evaluate_distribution(size)?;
+
+                let mut parts = Vec::new();
+                for (count, p_count) in count.outcomes() {
+                    for (size, p_size) in size.outcomes() {
+                        parts.push((
+                            p_count.saturating_mul(*p_size),
+                            Distribution::dice(*count, *size, value)?,
+                        ));
+                    }
+                }
+
+                Ok(Distribution::mixture(parts))
+            }
+            Value::Table { key, steps } => {
+                let key = self.evaluate_distribution(key)?;
+
+                let mut parts = Vec::new();
+                for (key, probability) in key.outcomes() {
+                    let step = match Value::table_step(steps, *key) {
+                        Some(step) => self.evaluate_distribution(step)?,
+                        None => Distribution::constant(Decimal::ZERO),
+                    };
+                    parts.push((*probability, step));
+                }
+
+                Ok(Distribution::mixture(parts))
+            }
+        }
+    }
+
+    /// Combines the distributions of two values, treating them as independent rolls
+    fn combine_distributions<F>(
+        &self,
+        a: &Value,
+        b: &Value,
+        function: F,
+    ) -> Result<Distribution, EvalError>
+    where
+        F: Fn(Decimal, Decimal) -> Result<Decimal, EvalError>,
+    {
+        let a = self.evaluate_distribution(a)?;
+        let b = self.evaluate_distribution(b)?;
+        a.combine(&b, function)
+    }
+}
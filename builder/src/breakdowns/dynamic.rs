@@ -37,7 +37,7 @@ impl Breakdowns {
                 .into_iter()
                 .map(|(attribute, bonuses): (Attribute, B)| {
                     attributes.insert(attribute.clone());
-                    (attribute, bonuses.into_iter().collect())
+                    (attribute, bonuses.into_iter().collect::<Vec<_>>())
                 });
 
         self.dynamic_bonuses.extend(dynamic_bonuses);
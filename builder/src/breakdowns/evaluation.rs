@@ -1,34 +1,41 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{PoisonError, RwLock, RwLockReadGuard},
+};
 
-use rust_decimal::Decimal;
-use utils::{from_into::FromInto, hashmap::MapGetOr};
+use im::HashMap as ImHashMap;
+use rust_decimal::{Decimal, MathematicalOps};
+use utils::{from_into::FromInto, hashmap::MapGetOrDefault};
 
 use crate::{
     attribute::Attribute,
-    bonus::{Bonus, Condition, Value},
+    bonus::{Bonus, BonusType, Condition, HasDice, StackingPolicy, Value},
 };
 
-use super::{BreakdownCache, Breakdowns, DiceStrategy};
+use super::{
+    breakdown::Evaluator, sampling::DiceRoller, Breakdowns, DiagnosticOrigin, DiceStrategy,
+    EvalDiagnostic, EvalError, EvaluatedCache, Overlay, SampleStatistics, MAX_SAMPLES,
+};
 
-/// Non-Mutating API
+/// Cache API
 impl Breakdowns {
     /// Attempts to return the result of the value from the value cache
     #[must_use]
-    pub fn get_value(&self, value: &Value) -> Option<&Decimal> {
-        self.cache.value.get(value)
+    pub fn get_value(&self, value: &Value) -> Option<Decimal> {
+        self.cache.read().value.get(value).copied()
     }
 
     /// Attempts to return the result of the condition from the condition cache
     #[must_use]
     pub fn get_condition(&self, condition: &Condition) -> Option<bool> {
-        self.cache.condition.get(condition).copied()
+        self.cache.read().condition.get(condition).copied()
     }
 }
 
-/// Mutable API From methods
+/// Evaluation API From methods
 impl Breakdowns {
     /// Evaluates a given condition based on values within the current [`Breakdowns`] object.
-    pub fn evaluate_condition_from<C>(&mut self, condition: C) -> bool
+    pub fn evaluate_condition_from<C>(&self, condition: C) -> bool
     where
         C: Into<Condition>,
     {
@@ -36,7 +43,7 @@ impl Breakdowns {
     }
 
     /// Evaluates a given value based on values within the current [`Breakdowns`] object.
-    pub fn evaluate_value_from<V>(&mut self, value: V) -> Decimal
+    pub fn evaluate_value_from<V>(&self, value: V) -> Decimal
     where
         V: Into<Value>,
     {
@@ -45,7 +52,7 @@ impl Breakdowns {
 
     /// Evaluates the value of the given attribute. Defaults to [`Decimal::ZERO`] if there are no
     /// bonuses to that attribute.
-    pub fn evaluate_attribute_from<A>(&mut self, attribute: A) -> Decimal
+    pub fn evaluate_attribute_from<A>(&self, attribute: A) -> Decimal
     where
         A: Into<Attribute>,
     {
@@ -53,8 +60,8 @@ impl Breakdowns {
     }
 
     /// Calculates the current value of a given [`Attribute`].
-    /// Only takes the highest value of bonuses of the same [`BonusType`], except for
-    /// [`BonusType::Stacking`]
+    /// Bonuses of the same [`BonusType`] are combined based on the type's stacking policy. The
+    /// total is then limited by [`Attribute::maximum`] and [`Attribute::minimum`].
     ///
     /// Returns [`Some`] with the resulting value if there are bonuses for it
     /// Returns [`None`] if there are no bonuses available for that [`Attribute`].
@@ -63,8 +70,7 @@ impl Breakdowns {
     /// [`Breakdowns::evaluate_attribute`]
     ///
     /// [`BonusType`]: crate::bonus::BonusType
-    /// [`BonusType::Stacking`]: crate::bonus::BonusType::Stacking
-    pub fn calculate_attribute_from<A>(&mut self, attribute: A) -> Option<Decimal>
+    pub fn calculate_attribute_from<A>(&self, attribute: A) -> Option<Decimal>
     where
         A: Into<Attribute>,
     {
@@ -72,31 +78,66 @@ impl Breakdowns {
     }
 }
 
-/// Mutable API
+/// Evaluation API
+///
+/// These methods only need a shared reference, so a [`Breakdowns`] can be queried from several
+/// threads at once. Results are cached behind a lock as they are evaluated.
 impl Breakdowns {
     /// Evaluates a given condition based on values within the current [`Breakdowns`] object.
-    pub fn evaluate_condition<'a, C>(&mut self, condition: C) -> bool
+    ///
+    /// Returns `false` if the condition cannot be evaluated. Use
+    /// [`Breakdowns::try_evaluate_condition`] to get the error instead.
+    pub fn evaluate_condition<'a, C>(&self, condition: C) -> bool
     where
         C: Into<Option<&'a Condition>>,
     {
         Option::<&'a Condition>::from_into(condition)
-            .is_none_or(|cond| self.snapshot().evaluate_condition(cond))
+            .is_none_or(|cond| self.try_evaluate_condition(cond).unwrap_or(false))
     }
 
     /// Evaluates a given value based on values within the current [`Breakdowns`] object.
-    pub fn evaluate_value(&mut self, value: &Value) -> Decimal {
-        self.snapshot().evaluate_value(value)
+    ///
+    /// Returns [`Decimal::ZERO`] if the value cannot be evaluated. Use
+    /// [`Breakdowns::try_evaluate_value`] to get the error instead.
+    pub fn evaluate_value(&self, value: &Value) -> Decimal {
+        self.try_evaluate_value(value).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Evaluates a given condition based on values within the current [`Breakdowns`] object.
+    ///
+    /// # Errors
+    /// Returns an [`EvalError`] if any value within the condition cannot be evaluated, such as
+    /// when dividing by zero.
+    pub fn try_evaluate_condition(&self, condition: &Condition) -> Result<bool, EvalError> {
+        if let Some(value) = self.get_condition(condition) {
+            return Ok(value);
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_condition(condition))
+    }
+
+    /// Evaluates a given value based on values within the current [`Breakdowns`] object.
+    ///
+    /// # Errors
+    /// Returns an [`EvalError`] if the value cannot be evaluated, such as when dividing by zero.
+    pub fn try_evaluate_value(&self, value: &Value) -> Result<Decimal, EvalError> {
+        if let Some(value) = self.get_value(value) {
+            return Ok(value);
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_value(value))
     }
 
     /// Evaluates the value of the given attribute. Defaults to [`Decimal::ZERO`] if there are no
     /// bonuses to that attribute.
-    pub fn evaluate_attribute(&mut self, attribute: &Attribute) -> Decimal {
-        self.snapshot().evaluate_attribute(attribute)
+    pub fn evaluate_attribute(&self, attribute: &Attribute) -> Decimal {
+        if let Some(value) = self.cache.read().attribute.get(attribute) {
+            return *value;
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_attribute(attribute))
     }
 
     /// Calculates the current value of a given [`Attribute`].
-    /// Only takes the highest value of bonuses of the same [`BonusType`], except for
-    /// [`BonusType::Stacking`]
+    /// Bonuses of the same [`BonusType`] are combined based on the type's stacking policy. The
+    /// total is then limited by [`Attribute::maximum`] and [`Attribute::minimum`].
     ///
     /// Returns [`Some`] with the resulting value if there are bonuses for it
     /// Returns [`None`] if there are no bonuses available for that [`Attribute`].
@@ -105,56 +146,393 @@ impl Breakdowns {
     /// [`Breakdowns::evaluate_attribute`]
     ///
     /// [`BonusType`]: crate::bonus::BonusType
-    /// [`BonusType::Stacking`]: crate::bonus::BonusType::Stacking
-    pub fn calculate_attribute(&mut self, attribute: &Attribute) -> Option<Decimal> {
-        self.snapshot().calculate_attribute(attribute)
+    pub fn calculate_attribute(&self, attribute: &Attribute) -> Option<Decimal> {
+        self.with_snapshot(|snapshot| snapshot.calculate_attribute(attribute))
+    }
+}
+
+/// Combines the values of bonuses that share a bonus type, based on the type's [`StackingPolicy`].
+///
+/// The `cap` is only used with [`StackingPolicy::SumCapped`], and is ignored if it is [`None`].
+pub(super) fn stack_values<I>(policy: &StackingPolicy, cap: Option<Decimal>, values: I) -> Decimal
+where
+    I: IntoIterator<Item = Decimal>,
+{
+    match policy {
+        StackingPolicy::HighestOnly => highest_only(values),
+        StackingPolicy::Sum => sum(values),
+        StackingPolicy::SumCapped(_) => {
+            let total = sum(values);
+            cap.map_or(total, |cap| total.min(cap))
+        }
+    }
+}
+
+/// Collapses entries that share an effect name, keeping only the entry with the highest value.
+///
+/// Returns the entries that are kept, followed by the entries that were suppressed.
+pub(super) fn collapse_named<T, N, V>(entries: Vec<T>, name: N, value: V) -> (Vec<T>, Vec<T>)
+where
+    N: Fn(&T) -> Option<&str>,
+    V: Fn(&T) -> Decimal,
+{
+    let mut best: HashMap<&str, (usize, Decimal)> = HashMap::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let Some(name) = name(entry) else {
+            continue;
+        };
+        let value = value(entry);
+
+        match best.get_mut(name) {
+            Some((_, best)) if *best >= value => {}
+            Some(best) => *best = (index, value),
+            None => {
+                best.insert(name, (index, value));
+            }
+        }
+    }
+
+    let kept = best
+        .into_values()
+        .map(|(index, _)| index)
+        .collect::<HashSet<_>>();
+
+    let keep = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| name(entry).is_none() || kept.contains(&index))
+        .collect::<Vec<_>>();
+
+    let (kept, suppressed): (Vec<_>, Vec<_>) =
+        entries.into_iter().zip(keep).partition(|(_, keep)| *keep);
+
+    (
+        kept.into_iter().map(|(entry, _)| entry).collect(),
+        suppressed.into_iter().map(|(entry, _)| entry).collect(),
+    )
+}
+
+/// Divides `a` by `b`, where `value` is the expression being evaluated
+pub(super) fn divide(a: Decimal, b: Decimal, value: &Value) -> Result<Decimal, EvalError> {
+    if b.is_zero() {
+        return Err(EvalError::DivisionByZero(value.clone()));
     }
+    a.checked_div(b)
+        .ok_or_else(|| EvalError::Overflow(value.clone()))
+}
+
+/// Returns the remainder of dividing `a` by `b`, where `value` is the expression being evaluated
+pub(super) fn remainder(a: Decimal, b: Decimal, value: &Value) -> Result<Decimal, EvalError> {
+    if b.is_zero() {
+        return Err(EvalError::DivisionByZero(value.clone()));
+    }
+    a.checked_rem(b)
+        .ok_or_else(|| EvalError::Overflow(value.clone()))
+}
+
+/// Raises `base` to the power of `exponent`, where `value` is the expression being evaluated.
+///
+/// Results too small to be represented are returned as zero, and only results too large to be
+/// represented are an overflow.
+pub(super) fn power(base: Decimal, exponent: Decimal, value: &Value) -> Result<Decimal, EvalError> {
+    if base.is_zero() && exponent.is_sign_negative() {
+        return Err(EvalError::DivisionByZero(value.clone()));
+    }
+    if base.is_sign_negative() && !exponent.is_integer() {
+        return Err(EvalError::Undefined(value.clone()));
+    }
+    base.checked_powd(exponent).map_or_else(
+        || {
+            let shrinks = (base.abs() > Decimal::ONE) == exponent.is_sign_negative();
+            if shrinks {
+                Ok(Decimal::ZERO)
+            } else {
+                Err(EvalError::Overflow(value.clone()))
+            }
+        },
+        Ok,
+    )
+}
+
+/// Returns -1, 0, or 1 depending on the sign of the value
+pub(super) const fn sign(value: Decimal) -> Decimal {
+    if value.is_zero() {
+        Decimal::ZERO
+    } else if value.is_sign_negative() {
+        Decimal::NEGATIVE_ONE
+    } else {
+        Decimal::ONE
+    }
+}
+
+/// Limits the value to the maximum and minimum, if they are provided
+pub(super) fn apply_limits(
+    value: Decimal,
+    maximum: Option<Decimal>,
+    minimum: Option<Decimal>,
+) -> Decimal {
+    let value = maximum.map_or(value, |maximum| value.min(maximum));
+    minimum.map_or(value, |minimum| value.max(minimum))
+}
+
+/// Returns the highest bonus plus the lowest penalty
+fn highest_only<I>(values: I) -> Decimal
+where
+    I: IntoIterator<Item = Decimal>,
+{
+    let (highest, lowest) = values.into_iter().fold(
+        (Decimal::ZERO, Decimal::ZERO),
+        |(highest, lowest), value| (highest.max(value), lowest.min(value)),
+    );
+    highest.saturating_add(lowest)
+}
+
+fn sum<I>(values: I) -> Decimal
+where
+    I: IntoIterator<Item = Decimal>,
+{
+    values
+        .into_iter()
+        .fold(Decimal::ZERO, Decimal::saturating_add)
 }
 
-struct Snapshot<'a> {
-    cache: &'a mut BreakdownCache,
-    bonuses: &'a HashMap<Attribute, Vec<Bonus>>,
+pub(super) struct Snapshot<'a> {
+    /// The cache shared between evaluations, which is only locked while reading or merging entries
+    shared: &'a RwLock<EvaluatedCache>,
+    /// Entries evaluated by this snapshot, which are merged into `shared` by [`Snapshot::merge`]
+    cache: EvaluatedCache,
+    /// Attributes that were calculated again, whose diagnostics and samples are replaced on merge
+    calculated: HashSet<Attribute>,
+    bonuses: &'a ImHashMap<Attribute, Vec<Bonus>>,
+    /// Replaces `bonuses` while evaluating an overlay, and evaluates unaffected attributes through
+    /// its base
+    overlay: Option<&'a Overlay<'a>>,
     dice_strategy: DiceStrategy,
+    /// Rolls dice while an attribute is being sampled with [`DiceStrategy::Sampled`]
+    roller: Option<DiceRoller>,
 }
 
 /// Snapshot Conversion
 impl Breakdowns {
-    const fn snapshot(&mut self) -> Snapshot<'_> {
-        Snapshot {
-            cache: &mut self.cache,
-            bonuses: &self.bonuses,
-            dice_strategy: self.dice_strategy,
-        }
+    /// Runs `f` with a snapshot of the breakdowns. The evaluated cache is only locked while
+    /// reading cached entries, and while merging the new entries once `f` returns.
+    fn with_snapshot<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Snapshot<'_>) -> T,
+    {
+        let mut snapshot = Snapshot::new(
+            &self.cache.evaluated,
+            &self.bonuses,
+            None,
+            self.dice_strategy,
+        );
+        let result = f(&mut snapshot);
+        snapshot.merge();
+        result
     }
 }
 
-impl Snapshot<'_> {
-    fn calculate_attribute(&mut self, attribute: &Attribute) -> Option<Decimal> {
-        let mut map = HashMap::new();
-        let mut stacking_bonus = Decimal::ZERO;
+impl<'a> Snapshot<'a> {
+    pub(super) fn new(
+        shared: &'a RwLock<EvaluatedCache>,
+        bonuses: &'a ImHashMap<Attribute, Vec<Bonus>>,
+        overlay: Option<&'a Overlay<'a>>,
+        dice_strategy: DiceStrategy,
+    ) -> Self {
+        Self {
+            shared,
+            cache: EvaluatedCache::default(),
+            calculated: HashSet::new(),
+            bonuses,
+            overlay,
+            dice_strategy,
+            roller: None,
+        }
+    }
+
+    /// Locks the shared cache for reading. Entries are only ever fully evaluated, so the cache is
+    /// still used if another thread panicked while holding the lock.
+    fn shared(&self) -> RwLockReadGuard<'_, EvaluatedCache> {
+        self.shared.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Merges the entries evaluated by this snapshot into the shared cache
+    pub(super) fn merge(self) {
+        let mut shared = self.shared.write().unwrap_or_else(PoisonError::into_inner);
+
+        for attribute in &self.calculated {
+            shared.diagnostics.remove(attribute);
+            shared.samples.remove(attribute);
+        }
+
+        shared.value.extend(self.cache.value);
+        shared.condition.extend(self.cache.condition);
+        shared.attribute.extend(self.cache.attribute);
+        shared.diagnostics.extend(self.cache.diagnostics);
+        shared.samples.extend(self.cache.samples);
+    }
+
+    fn attribute_bonuses(&self, attribute: &Attribute) -> Option<&'a Vec<Bonus>> {
+        self.overlay.map_or_else(
+            || self.bonuses.get(attribute),
+            |overlay| overlay.attribute_bonuses(attribute),
+        )
+    }
+
+    pub(super) fn calculate_attribute(&mut self, attribute: &Attribute) -> Option<Decimal> {
+        let bonuses = self.attribute_bonuses(attribute)?;
+        self.calculated.insert(attribute.clone());
 
-        for bonus in self.bonuses.get(attribute)? {
-            let apply_value = bonus
+        let DiceStrategy::Sampled { seed, samples } = self.dice_strategy else {
+            return Some(self.calculate_total(attribute, bonuses));
+        };
+
+        if !bonuses.iter().any(HasDice::has_dice) {
+            self.cache.samples.remove(attribute);
+            return Some(self.calculate_total(attribute, bonuses));
+        }
+
+        // Any attribute sampled within this one uses its own roller
+        let previous = self.roller.replace(DiceRoller::new(seed, attribute));
+        let results = (0..samples.clamp(1, MAX_SAMPLES))
+            .map(|_| self.calculate_total(attribute, bonuses))
+            .collect::<Vec<_>>();
+        self.roller = previous;
+
+        let statistics = SampleStatistics::new(&results);
+        self.cache.samples.insert(attribute.clone(), statistics);
+        Some(statistics.mean())
+    }
+
+    /// Combines the bonuses to an attribute into its value
+    fn calculate_total(&mut self, attribute: &Attribute, bonuses: &[Bonus]) -> Decimal {
+        let mut applied = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for bonus in bonuses {
+            let result = bonus
                 .condition()
-                .is_none_or(|cond| self.evaluate_condition(cond));
+                .map_or(Ok(true), |cond| self.evaluate_condition(cond))
+                .and_then(|apply| {
+                    apply
+                        .then(|| self.evaluate_value(bonus.value()))
+                        .transpose()
+                });
 
-            if apply_value {
-                let value = self.evaluate_value(bonus.value());
-                if bonus.bonus_type().is_stacking() {
-                    stacking_bonus += value;
-                } else {
-                    let val = map.get_mut_or(bonus.bonus_type(), Decimal::MIN);
-                    *val = value.max(*val);
-                }
+            match result {
+                Ok(Some(value)) => applied.push((bonus, value)),
+                Ok(None) => {}
+                Err(error) => diagnostics.push(EvalDiagnostic::new(bonus, error)),
+            }
+        }
+
+        let (applied, _) = collapse_named(applied, |(bonus, _)| bonus.name(), |(_, value)| *value);
+
+        let mut map: HashMap<BonusType, Vec<Decimal>> = HashMap::new();
+        for (bonus, value) in applied {
+            map.get_mut_or_default(bonus.bonus_type()).push(value);
+        }
+
+        let mut total = Decimal::ZERO;
+
+        for (bonus_type, values) in map {
+            let policy = bonus_type.stacking_policy();
+            let cap = self.evaluate_limit(
+                attribute,
+                policy.cap(),
+                |cap| DiagnosticOrigin::Cap(bonus_type, cap),
+                &mut diagnostics,
+            );
+
+            total = total.saturating_add(stack_values(&policy, cap, values));
+        }
+
+        let maximum = self.evaluate_limit(
+            attribute,
+            attribute.maximum().as_ref(),
+            DiagnosticOrigin::Maximum,
+            &mut diagnostics,
+        );
+        let minimum = self.evaluate_limit(
+            attribute,
+            attribute.minimum().as_ref(),
+            DiagnosticOrigin::Minimum,
+            &mut diagnostics,
+        );
+
+        if diagnostics.is_empty() {
+            self.cache.diagnostics.remove(attribute);
+        } else {
+            self.cache
+                .diagnostics
+                .insert(attribute.clone(), diagnostics);
+        }
+
+        apply_limits(total, maximum, minimum)
+    }
+
+    /// Evaluates a limit of `attribute`, such as a cap. If the limit fails to evaluate, the error
+    /// is recorded against the attribute and the limit, using `origin` to describe which limit
+    /// failed, and the limit is ignored.
+    fn evaluate_limit<F>(
+        &mut self,
+        attribute: &Attribute,
+        limit: Option<&Value>,
+        origin: F,
+        diagnostics: &mut Vec<EvalDiagnostic>,
+    ) -> Option<Decimal>
+    where
+        F: FnOnce(Value) -> DiagnosticOrigin,
+    {
+        let limit = limit?;
+        match self.evaluate_value(limit) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                diagnostics.push(EvalDiagnostic::limit(
+                    attribute.clone(),
+                    origin(limit.clone()),
+                    error,
+                ));
+                None
             }
         }
+    }
 
-        Some(stacking_bonus + map.values().sum::<Decimal>())
+    fn cached_attribute(&self, attribute: &Attribute) -> Option<Decimal> {
+        self.cache
+            .attribute
+            .get(attribute)
+            .copied()
+            .or_else(|| self.shared().attribute.get(attribute).copied())
     }
 
-    fn evaluate_attribute(&mut self, attribute: &Attribute) -> Decimal {
-        if let Some(value) = self.cache.attribute.get(attribute) {
-            return *value;
+    fn cached_condition(&self, condition: &Condition) -> Option<bool> {
+        self.cache
+            .condition
+            .get(condition)
+            .copied()
+            .or_else(|| self.shared().condition.get(condition).copied())
+    }
+
+    fn cached_value(&self, value: &Value) -> Option<Decimal> {
+        self.cache
+            .value
+            .get(value)
+            .copied()
+            .or_else(|| self.shared().value.get(value).copied())
+    }
+
+    pub(super) fn evaluate_attribute(&mut self, attribute: &Attribute) -> Decimal {
+        if let Some(value) = self.cached_attribute(attribute) {
+            return value;
+        }
+
+        if let Some(overlay) = self
+            .overlay
+            .filter(|overlay| !overlay.is_affected(attribute))
+        {
+            return overlay.base().evaluate_attribute(attribute);
         }
 
         let value = self.calculate_attribute(attribute).unwrap_or(Decimal::ZERO);
@@ -164,72 +542,200 @@ impl Snapshot<'_> {
         value
     }
 
-    fn evaluate_condition(&mut self, condition: &Condition) -> bool {
-        if let Some(value) = self.cache.condition.get(condition) {
-            return *value;
+    pub(super) fn evaluate_condition(&mut self, condition: &Condition) -> Result<bool, EvalError> {
+        // Rolled dice are different each time, so they cannot be cached
+        let cached = self.roller.is_none() || !condition.has_dice();
+
+        if let Some(value) = self.cached_condition(condition).filter(|_| cached) {
+            return Ok(value);
         }
 
         let result = match condition {
-            Condition::Constant(value) => return *value,
-            Condition::Not(cond) => !self.evaluate_condition(cond),
-            Condition::GreaterThan(a, b) => self.evaluate_value(a) > self.evaluate_value(b),
-            Condition::GreaterEqualTo(a, b) => self.evaluate_value(a) >= self.evaluate_value(b),
-            Condition::LessThan(a, b) => self.evaluate_value(a) < self.evaluate_value(b),
-            Condition::LessEqualTo(a, b) => self.evaluate_value(a) <= self.evaluate_value(b),
-            Condition::EqualTo(a, b) => self.evaluate_value(a) == self.evaluate_value(b),
-            Condition::And(a, b) => self.evaluate_condition(a) && self.evaluate_condition(b),
-            Condition::Or(a, b) => self.evaluate_condition(a) || self.evaluate_condition(b),
-            Condition::Xor(a, b) => self.evaluate_condition(a) != self.evaluate_condition(b),
+            Condition::Constant(value) => return Ok(*value),
+            Condition::Not(cond) => !self.evaluate_condition(cond)?,
+            Condition::GreaterThan(a, b) => self.evaluate_value(a)? > self.evaluate_value(b)?,
+            Condition::GreaterEqualTo(a, b) => self.evaluate_value(a)? >= self.evaluate_value(b)?,
+            Condition::LessThan(a, b) => self.evaluate_value(a)? < self.evaluate_value(b)?,
+            Condition::LessEqualTo(a, b) => self.evaluate_value(a)? <= self.evaluate_value(b)?,
+            Condition::EqualTo(a, b) => self.evaluate_value(a)? == self.evaluate_value(b)?,
+            Condition::And(a, b) => self.evaluate_condition(a)? && self.evaluate_condition(b)?,
+            Condition::Or(a, b) => self.evaluate_condition(a)? || self.evaluate_condition(b)?,
+            Condition::Xor(a, b) => self.evaluate_condition(a)? != self.evaluate_condition(b)?,
+            Condition::All(conditions) => self.evaluate_at_least(conditions.len(), conditions)?,
+            Condition::Any(conditions) => self.evaluate_at_least(1, conditions)?,
+            Condition::AtLeast(count, conditions) => self.evaluate_at_least(*count, conditions)?,
         };
 
-        self.cache.condition.insert(condition.clone(), result);
-        result
+        if cached {
+            self.cache.condition.insert(condition.clone(), result);
+        }
+        Ok(result)
     }
 
-    fn evaluate_value(&mut self, value: &Value) -> Decimal {
-        if let Some(value) = self.cache.value.get(value) {
-            return *value;
+    /// Returns whether at least `count` of the conditions are true. Stops evaluating conditions
+    /// once the result is known.
+    fn evaluate_at_least(
+        &mut self,
+        count: usize,
+        conditions: &[Condition],
+    ) -> Result<bool, EvalError> {
+        let mut needed = count;
+
+        for (index, condition) in conditions.iter().enumerate() {
+            if needed == 0 {
+                return Ok(true);
+            }
+            if conditions.len() - index < needed {
+                return Ok(false);
+            }
+            if self.evaluate_condition(condition)? {
+                needed -= 1;
+            }
+        }
+
+        Ok(needed == 0)
+    }
+
+    pub(super) fn evaluate_value(&mut self, value: &Value) -> Result<Decimal, EvalError> {
+        // Rolled dice are different each time, so they cannot be cached
+        let cached = self.roller.is_none() || !value.has_dice();
+
+        if let Some(value) = self.cached_value(value).filter(|_| cached) {
+            return Ok(value);
         }
 
+        let overflow = || EvalError::Overflow(value.clone());
+
         let result = match value {
-            Value::Const(val) => return *val,
+            Value::Const(val) => return Ok(*val),
             Value::Attribute(attribute) => self.evaluate_attribute(attribute),
-            Value::Max(a, b) => self.evaluate_value(a).max(self.evaluate_value(b)),
-            Value::Min(a, b) => self.evaluate_value(a).min(self.evaluate_value(b)),
-            Value::Floor(val) => self.evaluate_value(val).floor(),
-            Value::Abs(val) => self.evaluate_value(val).abs(),
-            Value::Ceil(val) => self.evaluate_value(val).ceil(),
-            Value::Round(val) => self.evaluate_value(val).round(),
+            Value::Max(a, b) => self.evaluate_value(a)?.max(self.evaluate_value(b)?),
+            Value::Min(a, b) => self.evaluate_value(a)?.min(self.evaluate_value(b)?),
+            Value::Floor(val) => self.evaluate_value(val)?.floor(),
+            Value::Abs(val) => self.evaluate_value(val)?.abs(),
+            Value::Ceil(val) => self.evaluate_value(val)?.ceil(),
+            Value::Round(val) => self.evaluate_value(val)?.round(),
             Value::If {
                 condition,
                 if_true,
                 if_false,
             } => {
-                if self.evaluate_condition(condition) {
-                    self.evaluate_value(if_true)
+                if self.evaluate_condition(condition)? {
+                    self.evaluate_value(if_true)?
                 } else {
-                    self.evaluate_value(if_false)
+                    self.evaluate_value(if_false)?
+                }
+            }
+            Value::Add(a, b) => self
+                .evaluate_value(a)?
+                .checked_add(self.evaluate_value(b)?)
+                .ok_or_else(overflow)?,
+            Value::Sub(a, b) => self
+                .evaluate_value(a)?
+                .checked_sub(self.evaluate_value(b)?)
+                .ok_or_else(overflow)?,
+            Value::Mul(a, b) => self
+                .evaluate_value(a)?
+                .checked_mul(self.evaluate_value(b)?)
+                .ok_or_else(overflow)?,
+            Value::Div(a, b) => divide(self.evaluate_value(a)?, self.evaluate_value(b)?, value)?,
+            Value::Rem(a, b) => remainder(self.evaluate_value(a)?, self.evaluate_value(b)?, value)?,
+            Value::Table { key, steps } => {
+                let key = self.evaluate_value(key)?;
+                match Value::table_step(steps, key) {
+                    Some(step) => self.evaluate_value(step)?,
+                    None => Decimal::ZERO,
+                }
+            }
+            Value::Pow(base, exponent) => power(
+                self.evaluate_value(base)?,
+                self.evaluate_value(exponent)?,
+                value,
+            )?,
+            Value::Clamp(val, min, max) => self
+                .evaluate_value(val)?
+                .max(self.evaluate_value(min)?)
+                .min(self.evaluate_value(max)?),
+            Value::Sign(val) => sign(self.evaluate_value(val)?),
+            Value::Sum(values) => {
+                let mut total = Decimal::ZERO;
+                for val in values {
+                    total = total
+                        .checked_add(self.evaluate_value(val)?)
+                        .ok_or_else(overflow)?;
+                }
+                total
+            }
+            Value::Product(values) => {
+                let mut total = Decimal::ONE;
+                for val in values {
+                    total = total
+                        .checked_mul(self.evaluate_value(val)?)
+                        .ok_or_else(overflow)?;
+                }
+                total
+            }
+            Value::Dice { count, size } if self.roller.is_some() => {
+                let (count, size) = (self.evaluate_value(count)?, self.evaluate_value(size)?);
+                match &mut self.roller {
+                    Some(roller) => roller.roll(count, size, value)?,
+                    None => Decimal::ZERO,
                 }
             }
-            Value::Add(a, b) => self.evaluate_value(a) + self.evaluate_value(b),
-            Value::Sub(a, b) => self.evaluate_value(a) - self.evaluate_value(b),
-            Value::Mul(a, b) => self.evaluate_value(a) * self.evaluate_value(b),
-            Value::Div(a, b) => self.evaluate_value(a) / self.evaluate_value(b),
-            Value::Rem(a, b) => self.evaluate_value(a) % self.evaluate_value(b),
             Value::Dice { count, size } => {
-                self.evaluate_value(count)
-                    * match self.dice_strategy {
-                        DiceStrategy::Minimum => Decimal::ONE,
-                        DiceStrategy::Average => {
-                            (self.evaluate_value(size) + Decimal::ONE) / Decimal::TWO
-                        }
-                        DiceStrategy::Maximum => self.evaluate_value(size),
+                let roll = match self.dice_strategy {
+                    DiceStrategy::Minimum => Decimal::ONE,
+                    // Dice outside of an attribute's bonuses are not sampled
+                    DiceStrategy::Average | DiceStrategy::Sampled { .. } => {
+                        self.evaluate_value(size)?
+                            .checked_add(Decimal::ONE)
+                            .ok_or_else(overflow)?
+                            / Decimal::TWO
                     }
+                    DiceStrategy::Maximum => self.evaluate_value(size)?,
+                };
+
+                self.evaluate_value(count)?
+                    .checked_mul(roll)
+                    .ok_or_else(overflow)?
             }
         };
 
-        self.cache.value.insert(value.clone(), result);
+        if cached {
+            self.cache.value.insert(value.clone(), result);
+        }
 
-        result
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_limit_is_reported_against_attribute() {
+        let cache = RwLock::default();
+        let bonuses = ImHashMap::new();
+        let mut snapshot = Snapshot::new(&cache, &bonuses, None, DiceStrategy::Average);
+
+        let limit = Value::ONE / Value::ZERO;
+        let mut diagnostics = Vec::new();
+        let result = snapshot.evaluate_limit(
+            &Attribute::Debug(0),
+            Some(&limit),
+            DiagnosticOrigin::Maximum,
+            &mut diagnostics,
+        );
+
+        assert_eq!(result, None);
+        assert_eq!(
+            diagnostics,
+            [EvalDiagnostic::limit(
+                Attribute::Debug(0),
+                DiagnosticOrigin::Maximum(limit.clone()),
+                EvalError::DivisionByZero(limit)
+            )]
+        );
     }
 }
@@ -0,0 +1,469 @@
+use core::fmt::{self, Display};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use rust_decimal::Decimal;
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Bonus, Condition, Value},
+};
+
+use super::{breakdown::build_breakdown, AttributeBreakdown, Breakdowns, DiceStrategy};
+
+/// Describes how the value of an attribute was derived.
+///
+/// This includes the breakdown of the attribute, as well as an explanation of how the value and
+/// condition of each bonus to the attribute were calculated. Any attribute referenced within those
+/// values or conditions is explained recursively.
+///
+/// When displayed, an attribute that is referenced more than once is only written in full the
+/// first time, and later references point back to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    attribute: Attribute,
+    breakdown: AttributeBreakdown,
+    bonuses: Vec<BonusExplanation>,
+}
+
+impl Explanation {
+    /// The attribute being explained
+    #[must_use]
+    pub const fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    /// The final value of the attribute
+    #[must_use]
+    pub const fn value(&self) -> &Decimal {
+        self.breakdown.value()
+    }
+
+    /// The breakdown of the attribute, showing which bonuses were applied
+    #[must_use]
+    pub const fn breakdown(&self) -> &AttributeBreakdown {
+        &self.breakdown
+    }
+
+    /// Explanations of each bonus to the attribute
+    #[must_use]
+    pub const fn bonuses(&self) -> &Vec<BonusExplanation> {
+        &self.bonuses
+    }
+}
+
+/// Describes how the value and condition of a single bonus were calculated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BonusExplanation {
+    bonus: Bonus,
+    value: ValueExplanation,
+    condition: Option<ConditionExplanation>,
+}
+
+impl BonusExplanation {
+    /// The bonus being explained
+    #[must_use]
+    pub const fn bonus(&self) -> &Bonus {
+        &self.bonus
+    }
+
+    /// How the value of the bonus was calculated
+    #[must_use]
+    pub const fn value(&self) -> &ValueExplanation {
+        &self.value
+    }
+
+    /// How the condition of the bonus was calculated, if the bonus has a condition
+    #[must_use]
+    pub const fn condition(&self) -> Option<&ConditionExplanation> {
+        self.condition.as_ref()
+    }
+}
+
+/// Describes how a [`Value`] was calculated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueExplanation {
+    value: Value,
+    result: Decimal,
+    detail: ValueDetail,
+}
+
+impl ValueExplanation {
+    /// The value being explained
+    #[must_use]
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// What the value evaluated to
+    #[must_use]
+    pub const fn result(&self) -> &Decimal {
+        &self.result
+    }
+
+    /// How the value was calculated
+    #[must_use]
+    pub const fn detail(&self) -> &ValueDetail {
+        &self.detail
+    }
+}
+
+/// Details on how a [`Value`] was calculated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueDetail {
+    /// The value is a constant
+    Constant,
+    /// The value is copied from an attribute
+    Attribute(Arc<Explanation>),
+    /// The value is calculated from the inner values
+    Operands(Vec<ValueExplanation>),
+    /// The value is chosen based on a condition
+    If {
+        /// The condition that was checked
+        condition: Box<ConditionExplanation>,
+        /// The branch that was chosen
+        branch: Box<ValueExplanation>,
+    },
+    /// The value is a dice roll
+    Dice {
+        /// The number of dice rolled
+        count: Box<ValueExplanation>,
+        /// The size of each dice
+        size: Box<ValueExplanation>,
+        /// The strategy used to turn the dice into a single number
+        strategy: DiceStrategy,
+    },
+    /// The value is looked up from a table
+    Table {
+        /// The value used to pick the step
+        key: Box<ValueExplanation>,
+        /// The step that was picked, if the key reached any threshold
+        step: Option<Box<ValueExplanation>>,
+    },
+}
+
+/// Describes how a [`Condition`] was calculated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionExplanation {
+    condition: Condition,
+    result: bool,
+    detail: ConditionDetail,
+}
+
+impl ConditionExplanation {
+    /// The condition being explained
+    #[must_use]
+    pub const fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    /// What the condition evaluated to
+    #[must_use]
+    pub const fn result(&self) -> bool {
+        self.result
+    }
+
+    /// How the condition was calculated
+    #[must_use]
+    pub const fn detail(&self) -> &ConditionDetail {
+        &self.detail
+    }
+}
+
+/// Details on how a [`Condition`] was calculated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionDetail {
+    /// The condition is a constant
+    Constant,
+    /// The condition is calculated from the inner conditions
+    Conditions(Vec<ConditionExplanation>),
+    /// The condition compares the inner values
+    Values(Vec<ValueExplanation>),
+}
+
+impl Breakdowns {
+    /// Explains how the value of an attribute was derived, recursively explaining every attribute
+    /// it depends on.
+    pub fn explain(&self, attribute: &Attribute) -> Explanation {
+        let mut explained = HashMap::new();
+        self.explain_attribute(attribute, &mut explained)
+    }
+
+    fn explain_attribute(
+        &self,
+        attribute: &Attribute,
+        explained: &mut HashMap<Attribute, Arc<Explanation>>,
+    ) -> Explanation {
+        let breakdown = build_breakdown(self, attribute);
+
+        let bonuses = self
+            .bonuses
+            .get(attribute)
+            .cloned()
+            .into_iter()
+            .flatten()
+            .map(|bonus| BonusExplanation {
+                value: self.explain_value(bonus.value(), explained),
+                condition: bonus
+                    .condition()
+                    .map(|condition| self.explain_condition(condition, explained)),
+                bonus,
+            })
+            .collect();
+
+        Explanation {
+            attribute: attribute.clone(),
+            breakdown,
+            bonuses,
+        }
+    }
+
+    /// Explains an attribute referenced within a value, re-using the explanation if the attribute
+    /// has already been explained
+    fn explain_reference(
+        &self,
+        attribute: &Attribute,
+        explained: &mut HashMap<Attribute, Arc<Explanation>>,
+    ) -> Arc<Explanation> {
+        if let Some(explanation) = explained.get(attribute) {
+            return Arc::clone(explanation);
+        }
+
+        let explanation = Arc::new(self.explain_attribute(attribute, explained));
+        explained.insert(attribute.clone(), Arc::clone(&explanation));
+        explanation
+    }
+
+    fn explain_value(
+        &self,
+        value: &Value,
+        explained: &mut HashMap<Attribute, Arc<Explanation>>,
+    ) -> ValueExplanation {
+        let detail = match value {
+            Value::Const(_) => ValueDetail::Constant,
+            Value::Attribute(attribute) => {
+                ValueDetail::Attribute(self.explain_reference(attribute, explained))
+            }
+            Value::Min(a, b)
+            | Value::Max(a, b)
+            | Value::Add(a, b)
+            | Value::Sub(a, b)
+            | Value::Mul(a, b)
+            | Value::Div(a, b)
+            | Value::Rem(a, b)
+            | Value::Pow(a, b) => ValueDetail::Operands(vec![
+                self.explain_value(a, explained),
+                self.explain_value(b, explained),
+            ]),
+            Value::Floor(val)
+            | Value::Ceil(val)
+            | Value::Round(val)
+            | Value::Abs(val)
+            | Value::Sign(val) => ValueDetail::Operands(vec![self.explain_value(val, explained)]),
+            Value::Clamp(val, min, max) => ValueDetail::Operands(vec![
+                self.explain_value(val, explained),
+                self.explain_value(min, explained),
+                self.explain_value(max, explained),
+            ]),
+            Value::Sum(values) | Value::Product(values) => ValueDetail::Operands(
+                values
+                    .iter()
+                    .map(|val| self.explain_value(val, explained))
+                    .collect(),
+            ),
+            Value::If {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                let condition = self.explain_condition(condition, explained);
+                let branch = if condition.result { if_true } else { if_false };
+                ValueDetail::If {
+                    branch: Box::new(self.explain_value(branch, explained)),
+                    condition: Box::new(condition),
+                }
+            }
+            Value::Dice { count, size } => ValueDetail::Dice {
+                count: Box::new(self.explain_value(count, explained)),
+                size: Box::new(self.explain_value(size, explained)),
+                strategy: self.dice_strategy,
+            },
+            Value::Table { key, steps } => {
+                let key = self.explain_value(key, explained);
+                let step = Value::table_step(steps, key.result)
+                    .map(|step| Box::new(self.explain_value(step, explained)));
+                ValueDetail::Table {
+                    key: Box::new(key),
+                    step,
+                }
+            }
+        };
+
+        ValueExplanation {
+            result: self.evaluate_value(value),
+            value: value.clone(),
+            detail,
+        }
+    }
+
+    fn explain_condition(
+        &self,
+        condition: &Condition,
+        explained: &mut HashMap<Attribute, Arc<Explanation>>,
+    ) -> ConditionExplanation {
+        let detail = match condition {
+            Condition::Constant(_) => ConditionDetail::Constant,
+            Condition::Not(cond) => {
+                ConditionDetail::Conditions(vec![self.explain_condition(cond, explained)])
+            }
+            Condition::And(a, b) | Condition::Or(a, b) | Condition::Xor(a, b) => {
+                ConditionDetail::Conditions(vec![
+                    self.explain_condition(a, explained),
+                    self.explain_condition(b, explained),
+                ])
+            }
+            Condition::All(conditions)
+            | Condition::Any(conditions)
+            | Condition::AtLeast(_, conditions) => ConditionDetail::Conditions(
+                conditions
+                    .iter()
+                    .map(|cond| self.explain_condition(cond, explained))
+                    .collect(),
+            ),
+            Condition::GreaterThan(a, b)
+            | Condition::GreaterEqualTo(a, b)
+            | Condition::LessThan(a, b)
+            | Condition::LessEqualTo(a, b)
+            | Condition::EqualTo(a, b) => ConditionDetail::Values(vec![
+                self.explain_value(a, explained),
+                self.explain_value(b, explained),
+            ]),
+        };
+
+        ConditionExplanation {
+            result: self.evaluate_condition(condition),
+            condition: condition.clone(),
+            detail,
+        }
+    }
+}
+
+/// Writes the given number of indentations
+fn indent(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    write!(f, "{}", "  ".repeat(depth))
+}
+
+/// Attributes that have already been written in full
+type Printed = HashSet<Attribute>;
+
+impl Explanation {
+    fn fmt_depth(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        depth: usize,
+        printed: &mut Printed,
+    ) -> fmt::Result {
+        indent(f, depth)?;
+        if !printed.insert(self.attribute.clone()) {
+            return writeln!(f, "{} = {} (see above)", self.attribute, self.value());
+        }
+        writeln!(f, "{} = {}", self.attribute, self.value())?;
+
+        for bonus in &self.bonuses {
+            indent(f, depth + 1)?;
+            writeln!(
+                f,
+                "{} bonus from {}",
+                bonus.bonus.bonus_type(),
+                bonus.bonus.displayed_source()
+            )?;
+            if let Some(condition) = &bonus.condition {
+                condition.fmt_depth(f, depth + 2, printed)?;
+            }
+            bonus.value.fmt_depth(f, depth + 2, printed)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ValueExplanation {
+    fn fmt_depth(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        depth: usize,
+        printed: &mut Printed,
+    ) -> fmt::Result {
+        indent(f, depth)?;
+        writeln!(f, "{} = {}", self.value, self.result)?;
+
+        match &self.detail {
+            ValueDetail::Constant => Ok(()),
+            ValueDetail::Attribute(explanation) => explanation.fmt_depth(f, depth + 1, printed),
+            ValueDetail::Operands(values) => values
+                .iter()
+                .try_for_each(|value| value.fmt_depth(f, depth + 1, printed)),
+            ValueDetail::If { condition, branch } => {
+                condition.fmt_depth(f, depth + 1, printed)?;
+                branch.fmt_depth(f, depth + 1, printed)
+            }
+            ValueDetail::Dice {
+                count,
+                size,
+                strategy,
+            } => {
+                indent(f, depth + 1)?;
+                writeln!(f, "Dice Strategy: {strategy}")?;
+                count.fmt_depth(f, depth + 1, printed)?;
+                size.fmt_depth(f, depth + 1, printed)
+            }
+            ValueDetail::Table { key, step } => {
+                key.fmt_depth(f, depth + 1, printed)?;
+                step.as_ref()
+                    .map_or(Ok(()), |step| step.fmt_depth(f, depth + 1, printed))
+            }
+        }
+    }
+}
+
+impl ConditionExplanation {
+    fn fmt_depth(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        depth: usize,
+        printed: &mut Printed,
+    ) -> fmt::Result {
+        indent(f, depth)?;
+        writeln!(f, "{} is {}", self.condition, self.result)?;
+
+        match &self.detail {
+            ConditionDetail::Constant => Ok(()),
+            ConditionDetail::Conditions(conditions) => conditions
+                .iter()
+                .try_for_each(|condition| condition.fmt_depth(f, depth + 1, printed)),
+            ConditionDetail::Values(values) => values
+                .iter()
+                .try_for_each(|value| value.fmt_depth(f, depth + 1, printed)),
+        }
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_depth(f, 0, &mut Printed::new())
+    }
+}
+
+impl Display for ValueExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_depth(f, 0, &mut Printed::new())
+    }
+}
+
+impl Display for ConditionExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_depth(f, 0, &mut Printed::new())
+    }
+}
@@ -11,7 +11,10 @@ use crate::{
     types::flag::Flag,
 };
 
-use super::{buffer::Buffer, Breakdowns};
+use super::{
+    buffer::{expand_bonus, Buffer},
+    Breakdowns,
+};
 
 impl Breakdowns {
     /// Removes all bonuses with any of the provided [`BonusSources`].
@@ -28,6 +31,10 @@ impl Breakdowns {
 
         buffer.insert_attributes(self.remove_bonuses_by_source(&sources));
 
+        // Removing bonuses may have broken loops, so quarantined bonuses are given another chance
+        let quarantined = self.cycles.drain(..).map(|error| error.bonus().clone());
+        buffer.insert_expanded_bonuses(quarantined);
+
         self.consume_buffer(buffer);
     }
 
@@ -59,7 +66,18 @@ impl Breakdowns {
 
         let mut buffer = Buffer::new();
         buffer.insert_bonuses(bonuses);
-        buffer.insert_attributes(self.remove_bonuses_by_source(&sources));
+
+        let removed = self
+            .remove_bonuses_by_source(&sources)
+            .map(|bonus| bonus.attribute().clone())
+            .collect::<Vec<_>>();
+
+        // Replacing bonuses may have broken loops, so quarantined bonuses are given another chance
+        if !removed.is_empty() {
+            let quarantined = self.cycles.drain(..).map(|error| error.bonus().clone());
+            buffer.insert_expanded_bonuses(quarantined);
+        }
+        buffer.insert_attributes(removed);
 
         for bonus in buffer.get_bonuses() {
             let attribute = bonus.attribute();
@@ -91,8 +109,9 @@ impl Breakdowns {
 
     /// Forces the recalculation of all attributes
     pub fn recalculate_all_attributes(&mut self) {
-        self.cache.condition.clear();
-        self.cache.value.clear();
+        let cache = self.cache.evaluated_mut();
+        cache.condition.clear();
+        cache.value.clear();
 
         let mut buffer = Buffer::new();
         buffer.insert_attributes(self.bonuses.keys().cloned());
@@ -112,20 +131,42 @@ impl Breakdowns {
 
             let initial_value = self
                 .cache
+                .evaluated_mut()
                 .attribute
                 .remove(&attribute)
                 .or_else(|| forced.then_some(Decimal::ZERO))
                 .or_else(|| self.calculate_attribute(&attribute))
                 .unwrap_or(Decimal::ZERO);
 
-            self.bonuses.get_mut_or_default(&attribute).extend(bonuses);
+            let may_create_cycle = self.may_create_cycle(&attribute, &bonuses);
+            for bonus in bonuses {
+                let cycle = may_create_cycle
+                    .then(|| self.find_cycle(&bonus, &[], &HashSet::new()))
+                    .flatten();
+
+                if let Some(error) = cycle {
+                    self.cycles.push(error);
+                } else {
+                    self.insert_dependant(&bonus);
+                    self.bonuses.get_mut_or_default(&attribute).push(bonus);
+                }
+            }
 
             let current_value = self.calculate_attribute(&attribute).unwrap_or_default();
 
             if !forced && initial_value == current_value {
+                if self.is_watched(&attribute) {
+                    // Keeps the value cached, so the value before the next change is known
+                    self.cache
+                        .evaluated_mut()
+                        .attribute
+                        .insert(attribute, current_value);
+                }
                 continue;
             }
 
+            self.record_change(&attribute, initial_value);
+
             self.update_caches(&attribute, &current_value);
 
             let source = BonusSource::Attribute(attribute.clone());
@@ -152,9 +193,11 @@ impl Breakdowns {
 
             let bonuses = chain!(attribute_bonuses, dynamic_bonuses)
                 .flatten()
-                .map(|bonus| {
-                    children.insert(bonus.attribute().clone());
-                    bonus.to_bonus(source.clone())
+                .map(|bonus| bonus.to_bonus(source.clone()))
+                .inspect(|bonus| {
+                    // Cloned bonuses are tracked too, so they are removed along with the bonus
+                    children
+                        .extend(expand_bonus(bonus.clone()).map(|bonus| bonus.attribute().clone()));
                 });
 
             buffer.insert_bonuses(bonuses);
@@ -169,12 +212,35 @@ impl Breakdowns {
         }
     }
 
-    fn get_dependants<'a>(
-        &'a self,
-        attribute: &'a Attribute,
-    ) -> impl Iterator<Item = &'a Bonus> + 'a {
-        let filter = |bonus: &&Bonus| bonus.has_attr_dependency(attribute);
-        self.get_bonuses().filter(filter)
+    /// Returns all bonuses whose value or condition depend on the given attribute
+    fn get_dependants(&self, attribute: &Attribute) -> impl Iterator<Item = &Bonus> {
+        self.dependants.get(attribute).into_iter().flatten()
+    }
+
+    /// Adds the bonus to the dependants of each attribute it depends on
+    fn insert_dependant(&mut self, bonus: &Bonus) {
+        for dependency in bonus.get_attr_dependencies() {
+            self.dependants
+                .get_mut_or_default(&dependency)
+                .push(bonus.clone());
+        }
+    }
+
+    /// Removes the bonus from the dependants of each attribute it depends on
+    fn remove_dependant(&mut self, bonus: &Bonus) {
+        for dependency in bonus.get_attr_dependencies() {
+            let Some(dependants) = self.dependants.get_mut(&dependency) else {
+                continue;
+            };
+
+            if let Some(index) = dependants.iter().position(|item| item.eq(bonus)) {
+                dependants.swap_remove(index);
+            }
+
+            if dependants.is_empty() {
+                self.dependants.remove(&dependency);
+            }
+        }
     }
 
     fn remove_bonuses_by_source<'a, I>(&'a mut self, sources: I) -> impl Iterator<Item = Bonus> + 'a
@@ -182,6 +248,9 @@ impl Breakdowns {
         I: IntoIterator<Item = &'a BonusSource> + 'a,
     {
         let map = |source: &BonusSource| {
+            self.cycles
+                .retain(|error| error.bonus().source().ne(source));
+
             let children = self.children.remove(source)?;
 
             let mut bonuses = Vec::new();
@@ -195,6 +264,11 @@ impl Breakdowns {
                 let items = set.filter_remove(filter);
                 bonuses.extend(items);
             }
+
+            for bonus in &bonuses {
+                self.remove_dependant(bonus);
+            }
+
             Some(bonuses)
         };
 
@@ -202,15 +276,16 @@ impl Breakdowns {
     }
 
     fn update_caches(&mut self, attribute: &Attribute, value: &Decimal) {
-        fn filter_cache<K, V>(attribute: &Attribute) -> impl Fn(&K, &mut V) -> bool + '_
+        fn filter_cache<K, V>(attribute: &Attribute) -> impl Fn(&K, &V) -> bool + '_
         where
             K: AttributeDependencies,
         {
             |key, _| !key.has_attr_dependency(attribute)
         }
 
-        self.cache.value.retain(filter_cache(attribute));
-        self.cache.condition.retain(filter_cache(attribute));
+        let cache = self.cache.evaluated_mut();
+        cache.value.retain(filter_cache(attribute));
+        cache.condition.retain(filter_cache(attribute));
 
         match &attribute {
             Attribute::Flag(Flag::HasToggle(toggle)) => {
@@ -231,3 +306,97 @@ impl Breakdowns {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bonus::{BonusType, Condition, Value},
+        types::ability::Ability,
+    };
+
+    use super::*;
+
+    /// Asserts that the dependants index returns the same bonuses as scanning every bonus
+    fn assert_index_matches_scan(breakdowns: &Breakdowns) {
+        let mut attributes = breakdowns
+            .get_bonuses()
+            .flat_map(AttributeDependencies::get_attr_dependencies)
+            .collect::<HashSet<_>>();
+        attributes.extend(breakdowns.dependants.keys().cloned());
+
+        for attribute in attributes {
+            let mut scanned = breakdowns
+                .get_bonuses()
+                .filter(|bonus| bonus.has_attr_dependency(&attribute))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let mut indexed = breakdowns
+                .get_dependants(&attribute)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            scanned.sort();
+            indexed.sort();
+
+            assert_eq!(
+                scanned, indexed,
+                "Dependants of {attribute} do not match the scan"
+            );
+        }
+    }
+
+    fn dbg_bonus(attribute: usize, value: Value, source: usize) -> Bonus {
+        Bonus::new(
+            Attribute::Debug(attribute),
+            BonusType::Stacking,
+            value,
+            BonusSource::Debug(source),
+        )
+    }
+
+    #[test]
+    fn base_bonuses_match_scan() {
+        assert_index_matches_scan(&Breakdowns::new());
+    }
+
+    #[test]
+    fn inserted_bonuses_match_scan() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, Value::from(Attribute::Debug(1)), 0),
+            dbg_bonus(1, Value::from(Ability::Strength), 1),
+            dbg_bonus(2, 4.into(), 1).with_condition(Condition::has(Attribute::Debug(0))),
+            Bonus::new(
+                Ability::All,
+                BonusType::Stacking,
+                Attribute::Debug(2),
+                BonusSource::Debug(2),
+            ),
+        ]);
+        assert_index_matches_scan(&breakdowns);
+    }
+
+    #[test]
+    fn removed_bonuses_match_scan() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, Value::from(Attribute::Debug(1)), 0),
+            dbg_bonus(1, Value::from(Ability::Strength), 1),
+            dbg_bonus(2, Value::from(Attribute::Debug(1)), 1),
+        ]);
+        breakdowns.remove_source(BonusSource::Debug(1));
+        assert_index_matches_scan(&breakdowns);
+
+        breakdowns.remove_source(BonusSource::Debug(0));
+        assert_index_matches_scan(&breakdowns);
+    }
+
+    #[test]
+    fn replaced_bonuses_match_scan() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(1)), 0));
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(2)), 0));
+        assert_index_matches_scan(&breakdowns);
+        assert_eq!(breakdowns.get_dependants(&Attribute::Debug(1)).count(), 0);
+    }
+}
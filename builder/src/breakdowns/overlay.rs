@@ -0,0 +1,424 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use itertools::chain;
+use rust_decimal::Decimal;
+use utils::from_into::FromInto;
+
+use crate::{
+    attribute::{Attribute, AttributeDependencies},
+    bonus::{Bonus, BonusSource, Condition, Value},
+};
+
+use super::{
+    breakdown::Evaluator, buffer::expand_bonus, cycles::find_cycle, diff::diff_attributes,
+    evaluation::Snapshot, Breakdowns, BreakdownsDiff, CycleError, EvalError, EvaluatedCache,
+};
+
+/// A hypothetical version of a [`Breakdowns`], with some bonuses added or removed.
+///
+/// An overlay is created with [`Breakdowns::with_overlay`]. It borrows the base [`Breakdowns`],
+/// and only stores the bonuses that differ from it along with its own cache of evaluated values.
+/// Attributes that the changes cannot affect are read through to the base, sharing its cached
+/// values. Evaluating through the overlay never changes the base.
+#[derive(Debug)]
+pub struct Overlay<'a> {
+    base: &'a Breakdowns,
+    /// The full list of bonuses for every attribute whose bonuses differ from the base
+    bonuses: HashMap<Attribute, Vec<Bonus>>,
+    /// The attributes that have bonuses from each source, for sources that were changed
+    children: HashMap<BonusSource, Vec<Attribute>>,
+    /// The attributes with bonuses added by the overlay that depend on each attribute
+    dependants: HashMap<Attribute, Vec<Attribute>>,
+    /// Every attribute whose value may differ from the base
+    affected: HashSet<Attribute>,
+    cycles: Vec<CycleError>,
+    cache: RwLock<EvaluatedCache>,
+}
+
+impl<'a> Overlay<'a> {
+    fn new(base: &'a Breakdowns) -> Self {
+        Self {
+            base,
+            bonuses: HashMap::new(),
+            children: HashMap::new(),
+            dependants: HashMap::new(),
+            affected: HashSet::new(),
+            cycles: Vec::new(),
+            cache: RwLock::default(),
+        }
+    }
+
+    /// The [`Breakdowns`] that the overlay is layered on
+    #[must_use]
+    pub const fn base(&self) -> &'a Breakdowns {
+        self.base
+    }
+
+    /// Returns the bonuses that the overlay did not add, because they would have created a
+    /// circular dependency.
+    #[must_use]
+    pub const fn cycle_errors(&self) -> &Vec<CycleError> {
+        &self.cycles
+    }
+
+    /// Returns `true` if the value of the attribute may differ from the base
+    #[must_use]
+    pub fn is_affected(&self, attribute: &Attribute) -> bool {
+        self.affected.contains(attribute)
+    }
+
+    /// Compares the base [`Breakdowns`] (before) with the overlay (after), and returns every
+    /// attribute whose value changed.
+    pub fn diff(&self) -> BreakdownsDiff {
+        diff_attributes(self.base, self, self.affected.iter().cloned())
+    }
+
+    fn get_children(&self, source: &BonusSource) -> Option<&Vec<Attribute>> {
+        self.children
+            .get(source)
+            .or_else(|| self.base.children.get(source))
+    }
+
+    /// Returns `true` if the value or condition depends on any affected attribute
+    fn depends_on_affected<T>(&self, item: &T) -> bool
+    where
+        T: AttributeDependencies,
+    {
+        item.get_attr_dependencies()
+            .iter()
+            .any(|attribute| self.is_affected(attribute))
+    }
+
+    /// Marks the attribute, and every attribute that depends on it, as affected
+    fn mark_affected(&mut self, attribute: Attribute) {
+        let mut queue = vec![attribute];
+
+        while let Some(attribute) = queue.pop() {
+            if self.is_affected(&attribute) {
+                continue;
+            }
+
+            let source = BonusSource::Attribute(attribute.clone());
+
+            queue.extend(chain!(
+                self.base
+                    .dependants
+                    .get(&attribute)
+                    .into_iter()
+                    .flatten()
+                    .map(Bonus::attribute)
+                    .cloned(),
+                self.dependants
+                    .get(&attribute)
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+                self.get_children(&source).into_iter().flatten().cloned(),
+            ));
+
+            self.affected.insert(attribute);
+        }
+    }
+
+    fn remove_sources(&mut self, sources: &HashSet<BonusSource>) {
+        self.cycles
+            .retain(|error| !sources.contains(error.bonus().source()));
+
+        let attributes = sources
+            .iter()
+            .filter_map(|source| self.get_children(source))
+            .flatten()
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        for attribute in attributes {
+            let bonuses = self
+                .attribute_bonuses(&attribute)
+                .into_iter()
+                .flatten()
+                .filter(|bonus| !sources.contains(bonus.source()))
+                .cloned()
+                .collect();
+
+            self.bonuses.insert(attribute.clone(), bonuses);
+            self.mark_affected(attribute);
+        }
+
+        for source in sources {
+            self.children.insert(source.clone(), Vec::new());
+        }
+    }
+
+    /// Inserts a bonus that has already been expanded, unless it would create a circular
+    /// dependency
+    fn insert_bonus(&mut self, bonus: Bonus) {
+        let cycle = find_cycle(
+            |attribute| self.attribute_bonuses(attribute),
+            &bonus,
+            &[],
+            &HashSet::new(),
+        );
+
+        if let Some(error) = cycle {
+            self.cycles.push(error);
+            return;
+        }
+
+        let base = self.base;
+        let attribute = bonus.attribute().clone();
+
+        for dependency in bonus.get_attr_dependencies() {
+            self.dependants
+                .entry(dependency)
+                .or_default()
+                .push(attribute.clone());
+        }
+
+        let children = self
+            .children
+            .entry(bonus.source().clone())
+            .or_insert_with_key(|source| base.children.get(source).cloned().unwrap_or_default());
+        if !children.contains(&attribute) {
+            children.push(attribute.clone());
+        }
+
+        self.bonuses
+            .entry(attribute.clone())
+            .or_insert_with_key(|attribute| {
+                base.bonuses.get(attribute).cloned().unwrap_or_default()
+            })
+            .push(bonus);
+
+        self.mark_affected(attribute);
+    }
+
+    /// Regenerates the bonuses from every affected attribute whose value has changed, until no
+    /// more values change
+    fn update_attribute_bonuses(&mut self) {
+        // The value each attribute's bonuses were last generated from
+        let mut generated = HashMap::new();
+
+        loop {
+            let mut attributes = self.affected.iter().cloned().collect::<Vec<_>>();
+            attributes.sort();
+
+            let mut changed = false;
+
+            for attribute in attributes {
+                let value = self.evaluate_attribute(&attribute);
+                let previous = generated
+                    .get(&attribute)
+                    .copied()
+                    .unwrap_or_else(|| self.base.evaluate_attribute(&attribute));
+
+                if value == previous {
+                    continue;
+                }
+
+                generated.insert(attribute.clone(), value);
+
+                let source = BonusSource::Attribute(attribute.clone());
+
+                let dynamic_bonuses = if value > Decimal::ZERO {
+                    self.base.dynamic_bonuses.get(&attribute).cloned()
+                } else {
+                    None
+                };
+
+                let bonuses = chain!(attribute.get_bonuses(value), dynamic_bonuses)
+                    .flatten()
+                    .map(|bonus| bonus.to_bonus(source.clone()))
+                    .flat_map(expand_bonus)
+                    .collect::<Vec<_>>();
+
+                self.remove_sources(&HashSet::from([source]));
+                for bonus in bonuses {
+                    self.insert_bonus(bonus);
+                }
+
+                // Values cached so far may have used the previous bonuses
+                *self.cache.get_mut().unwrap_or_else(PoisonError::into_inner) =
+                    EvaluatedCache::default();
+                changed = true;
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Locks the evaluated cache for reading
+    fn read(&self) -> RwLockReadGuard<'_, EvaluatedCache> {
+        self.cache.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the evaluated cache for writing
+    fn write(&self) -> RwLockWriteGuard<'_, EvaluatedCache> {
+        self.cache.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` with a snapshot of the overlay. The evaluated cache is only locked while reading
+    /// cached entries, and while merging the new entries once `f` returns.
+    fn with_snapshot<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Snapshot<'_>) -> T,
+    {
+        let mut snapshot = Snapshot::new(
+            &self.cache,
+            &self.base.bonuses,
+            Some(self),
+            self.base.dice_strategy,
+        );
+        let result = f(&mut snapshot);
+        snapshot.merge();
+        result
+    }
+}
+
+/// Evaluation API
+///
+/// These mirror the evaluation methods of [`Breakdowns`]. Anything that does not depend on an
+/// affected attribute is evaluated by the base.
+impl Overlay<'_> {
+    /// Evaluates the value of the given attribute. Defaults to [`Decimal::ZERO`] if there are no
+    /// bonuses to that attribute.
+    pub fn evaluate_attribute_from<A>(&self, attribute: A) -> Decimal
+    where
+        A: Into<Attribute>,
+    {
+        self.evaluate_attribute(&attribute.into())
+    }
+
+    /// Evaluates the value of the given attribute. Defaults to [`Decimal::ZERO`] if there are no
+    /// bonuses to that attribute.
+    pub fn evaluate_attribute(&self, attribute: &Attribute) -> Decimal {
+        if !self.is_affected(attribute) {
+            return self.base.evaluate_attribute(attribute);
+        }
+        if let Some(value) = self.read().attribute.get(attribute) {
+            return *value;
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_attribute(attribute))
+    }
+
+    /// Calculates the value of a given [`Attribute`], returning [`None`] if there are no bonuses
+    /// for it. See [`Breakdowns::calculate_attribute`].
+    pub fn calculate_attribute(&self, attribute: &Attribute) -> Option<Decimal> {
+        if !self.is_affected(attribute) {
+            return self.base.calculate_attribute(attribute);
+        }
+        self.with_snapshot(|snapshot| snapshot.calculate_attribute(attribute))
+    }
+
+    /// Evaluates a given condition. Returns `false` if the condition cannot be evaluated.
+    pub fn evaluate_condition<'a, C>(&self, condition: C) -> bool
+    where
+        C: Into<Option<&'a Condition>>,
+    {
+        Option::<&'a Condition>::from_into(condition)
+            .is_none_or(|cond| self.try_evaluate_condition(cond).unwrap_or(false))
+    }
+
+    /// Evaluates a given value. Returns [`Decimal::ZERO`] if the value cannot be evaluated.
+    pub fn evaluate_value(&self, value: &Value) -> Decimal {
+        self.try_evaluate_value(value).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Evaluates a given condition.
+    ///
+    /// # Errors
+    /// Returns an [`EvalError`] if any value within the condition cannot be evaluated, such as
+    /// when dividing by zero.
+    pub fn try_evaluate_condition(&self, condition: &Condition) -> Result<bool, EvalError> {
+        if !self.depends_on_affected(condition) {
+            return self.base.try_evaluate_condition(condition);
+        }
+        if let Some(value) = self.read().condition.get(condition) {
+            return Ok(*value);
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_condition(condition))
+    }
+
+    /// Evaluates a given value.
+    ///
+    /// # Errors
+    /// Returns an [`EvalError`] if the value cannot be evaluated, such as when dividing by zero.
+    pub fn try_evaluate_value(&self, value: &Value) -> Result<Decimal, EvalError> {
+        if !self.depends_on_affected(value) {
+            return self.base.try_evaluate_value(value);
+        }
+        if let Some(value) = self.read().value.get(value) {
+            return Ok(*value);
+        }
+        self.with_snapshot(|snapshot| snapshot.evaluate_value(value))
+    }
+}
+
+impl Evaluator for Overlay<'_> {
+    fn attribute_bonuses(&self, attribute: &Attribute) -> Option<&Vec<Bonus>> {
+        self.bonuses
+            .get(attribute)
+            .or_else(|| self.base.bonuses.get(attribute))
+    }
+
+    fn evaluate_attribute(&self, attribute: &Attribute) -> Decimal {
+        Self::evaluate_attribute(self, attribute)
+    }
+
+    fn evaluate_value(&self, value: &Value) -> Decimal {
+        Self::evaluate_value(self, value)
+    }
+
+    fn evaluate_condition(&self, condition: Option<&Condition>) -> bool {
+        Self::evaluate_condition(self, condition)
+    }
+}
+
+impl Breakdowns {
+    /// Creates an [`Overlay`] that evaluates attributes as if the provided bonuses were inserted,
+    /// and all bonuses from `removed_sources` were removed.
+    ///
+    /// Sources are removed before the bonuses are inserted. As with [`Self::insert_bonuses`],
+    /// inserting a bonus replaces all bonuses with the same source.
+    ///
+    /// This does not modify or clone the current [`Breakdowns`]. The overlay borrows it, so the
+    /// current [`Breakdowns`] cannot change while the overlay exists.
+    pub fn with_overlay<B, S>(&self, bonuses: B, removed_sources: S) -> Overlay<'_>
+    where
+        B: IntoIterator<Item = Bonus>,
+        S: IntoIterator<Item = BonusSource>,
+    {
+        let mut overlay = Overlay::new(self);
+
+        let bonuses = bonuses.into_iter().collect::<Vec<_>>();
+
+        let mut sources = removed_sources.into_iter().collect::<HashSet<_>>();
+        sources.extend(bonuses.iter().map(Bonus::source).cloned());
+
+        overlay.remove_sources(&sources);
+
+        // Removing bonuses may have broken loops, so quarantined bonuses are given another chance
+        let quarantined = self
+            .cycles
+            .iter()
+            .map(CycleError::bonus)
+            .filter(|bonus| !sources.is_empty() && !sources.contains(bonus.source()))
+            .cloned();
+
+        for bonus in bonuses
+            .into_iter()
+            .flat_map(expand_bonus)
+            .chain(quarantined)
+        {
+            overlay.insert_bonus(bonus);
+        }
+
+        overlay.update_attribute_bonuses();
+
+        overlay
+    }
+}
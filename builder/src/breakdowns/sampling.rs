@@ -0,0 +1,173 @@
+use core::fmt::Display;
+
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+
+use crate::{attribute::Attribute, bonus::Value};
+
+use super::{Breakdowns, EvalError};
+
+/// The most dice that can be rolled at once with [`DiceStrategy::Sampled`]. Rolling more is an
+/// [`EvalError::TooManyDice`].
+///
+/// [`DiceStrategy::Sampled`]: super::DiceStrategy::Sampled
+pub const MAX_DICE_COUNT: u64 = 10_000;
+
+/// The most samples taken for each attribute with [`DiceStrategy::Sampled`]. Larger sample counts
+/// are reduced to this.
+///
+/// [`DiceStrategy::Sampled`]: super::DiceStrategy::Sampled
+pub const MAX_SAMPLES: u32 = 100_000;
+
+/// The results of evaluating an attribute with [`DiceStrategy::Sampled`]
+///
+/// [`DiceStrategy::Sampled`]: super::DiceStrategy::Sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SampleStatistics {
+    samples: usize,
+    mean: Decimal,
+    standard_deviation: Decimal,
+    min: Decimal,
+    max: Decimal,
+}
+
+impl SampleStatistics {
+    /// Calculates the statistics of a list of samples
+    pub(super) fn new(samples: &[Decimal]) -> Self {
+        let count = Decimal::from(samples.len().max(1));
+
+        let mean = samples
+            .iter()
+            .fold(Decimal::ZERO, |total, sample| total.saturating_add(*sample))
+            / count;
+
+        let variance = samples.iter().fold(Decimal::ZERO, |total, sample| {
+            let distance = sample.saturating_sub(mean);
+            total.saturating_add(distance.saturating_mul(distance))
+        }) / count;
+
+        Self {
+            samples: samples.len(),
+            mean,
+            standard_deviation: variance.sqrt().unwrap_or(Decimal::ZERO),
+            min: samples.iter().copied().min().unwrap_or(Decimal::ZERO),
+            max: samples.iter().copied().max().unwrap_or(Decimal::ZERO),
+        }
+    }
+
+    /// The number of samples that were taken
+    #[must_use]
+    pub const fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// The average of every sample. This is also the value of the attribute
+    #[must_use]
+    pub const fn mean(&self) -> Decimal {
+        self.mean
+    }
+
+    /// How far samples typically were from the mean
+    #[must_use]
+    pub const fn standard_deviation(&self) -> Decimal {
+        self.standard_deviation
+    }
+
+    /// The lowest sample
+    #[must_use]
+    pub const fn min(&self) -> Decimal {
+        self.min
+    }
+
+    /// The highest sample
+    #[must_use]
+    pub const fn max(&self) -> Decimal {
+        self.max
+    }
+}
+
+/// Sampling API
+impl Breakdowns {
+    /// Returns the statistics of the samples taken for an attribute.
+    ///
+    /// Returns [`None`] unless the dice strategy is [`DiceStrategy::Sampled`] and the attribute
+    /// has bonuses that contain dice.
+    ///
+    /// [`DiceStrategy::Sampled`]: super::DiceStrategy::Sampled
+    pub fn sample_statistics(&self, attribute: &Attribute) -> Option<SampleStatistics> {
+        self.evaluate_attribute(attribute);
+        self.cache.read().samples.get(attribute).copied()
+    }
+}
+
+/// A deterministic random number generator used to roll dice, using the `SplitMix64` algorithm.
+///
+/// The algorithm is implemented here so that the same seed always produces the same rolls,
+/// regardless of any dependency updates.
+#[derive(Debug, Clone)]
+pub(super) struct DiceRoller {
+    state: u64,
+}
+
+impl DiceRoller {
+    /// Creates a roller from the seed, mixed with the text of `key` so that different attributes
+    /// roll different dice
+    pub(super) fn new<K>(seed: u64, key: &K) -> Self
+    where
+        K: Display,
+    {
+        // FNV-1a, which is stable between versions unlike the standard library hasher
+        let hash = key
+            .to_string()
+            .bytes()
+            .fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+            });
+
+        Self { state: seed ^ hash }
+    }
+
+    const fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Rolls `count` dice with `size` sides, and returns the total. `value` is the dice
+    /// expression being rolled.
+    ///
+    /// # Errors
+    /// Returns [`EvalError::Undefined`] if either the count or size is not a whole number,
+    /// [`EvalError::TooManyDice`] if more than [`MAX_DICE_COUNT`] dice are rolled, and
+    /// [`EvalError::Overflow`] if the size or total is too large.
+    pub(super) fn roll(
+        &mut self,
+        count: Decimal,
+        size: Decimal,
+        value: &Value,
+    ) -> Result<Decimal, EvalError> {
+        if !count.is_integer() || !size.is_integer() {
+            return Err(EvalError::Undefined(value.clone()));
+        }
+        if count <= Decimal::ZERO || size <= Decimal::ZERO {
+            return Ok(Decimal::ZERO);
+        }
+
+        let overflow = || EvalError::Overflow(value.clone());
+        let count = u64::try_from(count)
+            .ok()
+            .filter(|count| *count <= MAX_DICE_COUNT)
+            .ok_or_else(|| EvalError::TooManyDice(value.clone()))?;
+        let size = u64::try_from(size).map_err(|_| overflow())?;
+
+        let mut total = Decimal::ZERO;
+        for _ in 0..count {
+            total = total
+                .checked_add(Decimal::from(self.next() % size + 1))
+                .ok_or_else(overflow)?;
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Bonus, BonusSource, CloneBonus},
+    types::{slider::Slider, toggle::Toggle},
+};
+
+use super::{Breakdowns, CycleError, DiceStrategy};
+
+/// The choices made for a build, without any of the values calculated from them.
+///
+/// Unlike [`Breakdowns`], this only contains what was supplied by the user, so it is small enough
+/// to save to disk. Everything else can be rebuilt with [`Breakdowns::from_state`].
+///
+/// Bonuses are sorted, so the same build always serializes to the same text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildState {
+    #[serde(deserialize_with = "deserialize_version")]
+    version: u32,
+    dice_strategy: DiceStrategy,
+    toggles: Vec<Toggle>,
+    sliders: Vec<(Slider, Decimal)>,
+    bonuses: Vec<Bonus>,
+}
+
+impl BuildState {
+    /// The current version of the format. States written by a newer version fail to deserialize.
+    pub const VERSION: u32 = 1;
+
+    /// The version of the format that this state was written with
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The dice strategy used by the build
+    #[must_use]
+    pub const fn dice_strategy(&self) -> DiceStrategy {
+        self.dice_strategy
+    }
+
+    /// Toggles that are turned on
+    #[must_use]
+    pub fn toggles(&self) -> &[Toggle] {
+        &self.toggles
+    }
+
+    /// Sliders and the values they are set to
+    #[must_use]
+    pub fn sliders(&self) -> &[(Slider, Decimal)] {
+        &self.sliders
+    }
+
+    /// Every bonus that was inserted into the build, other than toggles and sliders
+    #[must_use]
+    pub fn bonuses(&self) -> &[Bonus] {
+        &self.bonuses
+    }
+}
+
+fn deserialize_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = u32::deserialize(deserializer)?;
+
+    if version > BuildState::VERSION {
+        return Err(de::Error::custom(format!(
+            "Unsupported build state version {version}, expected at most {}",
+            BuildState::VERSION
+        )));
+    }
+
+    Ok(version)
+}
+
+/// Build State
+impl Breakdowns {
+    /// Exports the bonuses, toggles, sliders and dice strategy that were supplied to this
+    /// [`Breakdowns`].
+    ///
+    /// Base bonuses, bonuses created by attributes, and bonuses cloned from another bonus (such
+    /// as each ability cloned from [`Ability::All`]) are left out, since they are added again when
+    /// the state is loaded. Dynamic bonuses are also left out, and must be imported again after
+    /// calling [`Self::from_state`].
+    ///
+    /// [`Ability::All`]: crate::types::ability::Ability::All
+    #[must_use]
+    pub fn to_state(&self) -> BuildState {
+        let mut toggles = Vec::new();
+        let mut sliders = Vec::new();
+        let mut bonuses = Vec::new();
+
+        let supplied = self
+            .get_bonuses()
+            .chain(self.cycles.iter().map(CycleError::bonus))
+            .collect::<Vec<_>>();
+
+        // Each clone is skipped once, so a supplied bonus that matches a clone is still kept
+        let mut cloned = HashMap::<Bonus, usize>::new();
+        for clone in supplied
+            .iter()
+            .filter_map(|bonus| bonus.attribute().clone_bonus(bonus))
+            .flatten()
+        {
+            *cloned.entry(clone).or_default() += 1;
+        }
+
+        for bonus in supplied {
+            if let Some(count) = cloned.get_mut(bonus).filter(|count| **count > 0) {
+                *count -= 1;
+                continue;
+            }
+
+            match (bonus.source(), bonus.attribute()) {
+                (BonusSource::Base | BonusSource::Attribute(_), _) => {}
+                (BonusSource::ToggleGroup(_), Attribute::Toggle(toggle)) => {
+                    if self.evaluate_value(bonus.value()) > Decimal::ZERO {
+                        toggles.push(*toggle);
+                    }
+                }
+                (BonusSource::Slider(slider), Attribute::Slider(_)) => {
+                    sliders.push((*slider, self.evaluate_value(bonus.value())));
+                }
+                _ => bonuses.push(bonus.clone()),
+            }
+        }
+
+        toggles.sort_unstable();
+        toggles.dedup();
+        sliders.sort_unstable();
+        sliders.dedup_by_key(|(slider, _)| *slider);
+        bonuses.sort_by_cached_key(|bonus| (bonus.source().clone(), bonus.to_string()));
+
+        BuildState {
+            version: BuildState::VERSION,
+            dice_strategy: self.dice_strategy,
+            toggles,
+            sliders,
+            bonuses,
+        }
+    }
+
+    /// Creates a new [`Breakdowns`] from an exported [`BuildState`]. Loading the same state always
+    /// results in the same values.
+    #[must_use]
+    pub fn from_state(state: BuildState) -> Self {
+        let mut breakdowns = Self::new();
+        breakdowns.set_dice_strategy(state.dice_strategy);
+
+        let toggles = state.toggles.iter().map(|toggle| toggle.toggle_bonus(true));
+        let sliders = state
+            .sliders
+            .iter()
+            .map(|(slider, value)| slider.slider_bonus(*value));
+
+        breakdowns.insert_bonuses(toggles.chain(sliders).chain(state.bonuses));
+
+        breakdowns
+    }
+}
@@ -0,0 +1,176 @@
+use core::fmt::{self, Display};
+
+use rust_decimal::Decimal;
+use utils::from_into::FromInto;
+
+use crate::attribute::Attribute;
+
+use super::Breakdowns;
+
+/// Describes how the value of a watched attribute changed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeChange {
+    attribute: Attribute,
+    before: Decimal,
+    after: Decimal,
+}
+
+impl AttributeChange {
+    /// The attribute that changed
+    #[must_use]
+    pub const fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    /// The value of the attribute before the change
+    #[must_use]
+    pub const fn before(&self) -> &Decimal {
+        &self.before
+    }
+
+    /// The value of the attribute after the change
+    #[must_use]
+    pub const fn after(&self) -> &Decimal {
+        &self.after
+    }
+
+    /// The difference between the value after and the value before the change
+    #[must_use]
+    pub fn delta(&self) -> Decimal {
+        self.after.saturating_sub(self.before)
+    }
+}
+
+impl Display for AttributeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({:+})",
+            self.attribute,
+            self.before,
+            self.after,
+            self.delta()
+        )
+    }
+}
+
+/// Every watched attribute that changed value since the last call to
+/// [`Breakdowns::take_changes`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ChangeSet {
+    changes: Vec<AttributeChange>,
+}
+
+impl ChangeSet {
+    /// Every change, sorted by attribute
+    #[must_use]
+    pub const fn changes(&self) -> &Vec<AttributeChange> {
+        &self.changes
+    }
+
+    /// Returns the change to the given attribute, if it changed
+    #[must_use]
+    pub fn get(&self, attribute: &Attribute) -> Option<&AttributeChange> {
+        self.changes
+            .iter()
+            .find(|change| change.attribute.eq(attribute))
+    }
+
+    /// Returns `true` if the given attribute changed
+    #[must_use]
+    pub fn contains(&self, attribute: &Attribute) -> bool {
+        self.get(attribute).is_some()
+    }
+
+    /// Returns an iterator of the attributes that changed
+    pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.changes.iter().map(AttributeChange::attribute)
+    }
+
+    /// Returns `true` if no watched attributes changed
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Watchers
+///
+/// Watched attributes record their value before they first change. [`Self::take_changes`] then
+/// returns every watched attribute whose value is different, so a UI only needs to update what
+/// changed.
+impl Breakdowns {
+    /// Starts recording changes to the value of an attribute
+    pub fn watch<A>(&mut self, attribute: A)
+    where
+        A: Into<Attribute>,
+    {
+        let attribute = Attribute::from_into(attribute);
+
+        // Caching the value ensures that the value before the next change is known
+        self.evaluate_attribute(&attribute);
+        self.cache.watched.insert(attribute);
+    }
+
+    /// Starts recording changes to the value of each attribute
+    pub fn watch_all<I, A>(&mut self, attributes: I)
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<Attribute>,
+    {
+        for attribute in attributes {
+            self.watch(attribute);
+        }
+    }
+
+    /// Stops recording changes to an attribute, discarding any changes that were not taken
+    pub fn unwatch(&mut self, attribute: &Attribute) {
+        self.cache.watched.remove(attribute);
+        self.cache.changes.remove(attribute);
+    }
+
+    /// Stops recording changes to all attributes
+    pub fn clear_watched(&mut self) {
+        self.cache.watched.clear();
+        self.cache.changes.clear();
+    }
+
+    /// Returns `true` if the attribute is being watched
+    #[must_use]
+    pub fn is_watched(&self, attribute: &Attribute) -> bool {
+        self.cache.watched.contains(attribute)
+    }
+
+    /// Returns every watched attribute that changed value since the last call, and starts
+    /// recording again. Attributes that changed and then changed back are not included.
+    pub fn take_changes(&mut self) -> ChangeSet {
+        let mut changes = self
+            .cache
+            .changes
+            .clone()
+            .into_iter()
+            .filter_map(|(attribute, before)| {
+                let after = self.evaluate_attribute(&attribute);
+
+                (before != after).then_some(AttributeChange {
+                    attribute,
+                    before,
+                    after,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.cache.changes.clear();
+        changes.sort_by(|a, b| a.attribute.cmp(&b.attribute));
+
+        ChangeSet { changes }
+    }
+
+    /// Records the value of a watched attribute before it changed, unless it already changed
+    /// since the last call to [`Self::take_changes`]
+    pub(super) fn record_change(&mut self, attribute: &Attribute, before: Decimal) {
+        if self.cache.watched.contains(attribute) && !self.cache.changes.contains_key(attribute) {
+            self.cache.changes.insert(attribute.clone(), before);
+        }
+    }
+}
@@ -0,0 +1,504 @@
+//! Describes the choices that make up a character, and compiles them into bonuses
+mod feat_slots;
+mod point_buy;
+mod skills;
+
+pub use feat_slots::*;
+pub use point_buy::*;
+pub use skills::*;
+
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utils::enums::StaticValues;
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Bonus, BonusSource, BonusType},
+    feat::{Feat, UnmetRequirement},
+    types::{
+        ability::Ability, alignment::Alignment, flag::Flag, player_class::PlayerClass, race::Race,
+        skill::Skill, toggle::Toggle,
+    },
+};
+
+/// The choices made for a character, such as race, classes, and feats.
+///
+/// A character is compiled into bonuses with [`Character::to_bonuses`], which are inserted into a
+/// [`Breakdowns`] with [`Breakdowns::insert_character`].
+///
+/// [`Breakdowns`]: crate::breakdowns::Breakdowns
+/// [`Breakdowns::insert_character`]: crate::breakdowns::Breakdowns::insert_character
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Character {
+    race: Race,
+    alignment: (Alignment, Alignment),
+    levels: Vec<PlayerClass>,
+    ability_scores: BTreeMap<Ability, u8>,
+    ability_increases: BTreeMap<usize, Ability>,
+    champion_build: bool,
+    skill_points: BTreeMap<usize, BTreeMap<Skill, u8>>,
+    feats: BTreeSet<Feat>,
+    slotted_feats: BTreeMap<FeatSlot, Feat>,
+    toggles: BTreeSet<Toggle>,
+}
+
+impl Character {
+    /// The highest level a character can reach
+    pub const MAX_LEVEL: usize = 34;
+
+    /// The highest level that adds class levels. Epic and legendary levels only add to the total
+    /// character level.
+    pub const HEROIC_LEVELS: usize = 20;
+
+    /// The ability score that each ability starts at
+    pub const BASE_ABILITY_SCORE: u8 = 8;
+
+    /// Creates a new level 0 character of the given race, with a true neutral alignment
+    #[must_use]
+    pub const fn new(race: Race) -> Self {
+        Self {
+            race,
+            alignment: (Alignment::Neutral, Alignment::Neutral),
+            levels: Vec::new(),
+            ability_scores: BTreeMap::new(),
+            ability_increases: BTreeMap::new(),
+            champion_build: false,
+            skill_points: BTreeMap::new(),
+            feats: BTreeSet::new(),
+            slotted_feats: BTreeMap::new(),
+            toggles: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the alignment, made up of the lawful / chaotic axis and the good / evil axis
+    #[must_use]
+    pub const fn with_alignment(mut self, ethics: Alignment, morals: Alignment) -> Self {
+        self.alignment = (ethics, morals);
+        self
+    }
+
+    /// Adds a level in the given class
+    #[must_use]
+    pub fn with_level(mut self, class: PlayerClass) -> Self {
+        self.levels.push(class);
+        self
+    }
+
+    /// Adds a level for each class, in order
+    #[must_use]
+    pub fn with_levels<I>(mut self, classes: I) -> Self
+    where
+        I: IntoIterator<Item = PlayerClass>,
+    {
+        self.levels.extend(classes);
+        self
+    }
+
+    /// Sets the score bought for an ability, before racial adjustments and ability increases
+    #[must_use]
+    pub fn with_ability_score(mut self, ability: Ability, score: u8) -> Self {
+        self.ability_scores.insert(ability, score);
+        self
+    }
+
+    /// Adds a feat that is not chosen in a feat slot, such as a past life
+    #[must_use]
+    pub fn with_feat<F>(mut self, feat: F) -> Self
+    where
+        F: Into<Feat>,
+    {
+        self.feats.insert(feat.into());
+        self
+    }
+
+    /// Adds several feats
+    #[must_use]
+    pub fn with_feats<I, F>(mut self, feats: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<Feat>,
+    {
+        self.feats.extend(feats.into_iter().map(Into::into));
+        self
+    }
+
+    /// Turns on a toggle
+    #[must_use]
+    pub fn with_toggle(mut self, toggle: Toggle) -> Self {
+        self.toggles.insert(toggle);
+        self
+    }
+}
+
+/// Getters and Setters
+impl Character {
+    /// The race of the character
+    #[must_use]
+    pub const fn race(&self) -> Race {
+        self.race
+    }
+
+    /// Changes the race of the character
+    pub const fn set_race(&mut self, race: Race) {
+        self.race = race;
+    }
+
+    /// The alignment of the character, as the lawful / chaotic axis and the good / evil axis
+    #[must_use]
+    pub const fn alignment(&self) -> (Alignment, Alignment) {
+        self.alignment
+    }
+
+    /// Changes the alignment of the character
+    pub const fn set_alignment(&mut self, ethics: Alignment, morals: Alignment) {
+        self.alignment = (ethics, morals);
+    }
+
+    /// The class chosen at each level, starting with level 1
+    #[must_use]
+    pub fn levels(&self) -> &[PlayerClass] {
+        &self.levels
+    }
+
+    /// Mutable access to the class chosen at each level
+    pub const fn levels_mut(&mut self) -> &mut Vec<PlayerClass> {
+        &mut self.levels
+    }
+
+    /// The total character level
+    #[must_use]
+    pub const fn level(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The number of levels in the given class, only counting heroic levels
+    #[must_use]
+    pub fn class_level(&self, class: PlayerClass) -> usize {
+        self.heroic_levels()
+            .iter()
+            .filter(|level| (**level).eq(&class))
+            .count()
+    }
+
+    /// The classes chosen in heroic levels
+    fn heroic_levels(&self) -> &[PlayerClass] {
+        self.levels
+            .get(..Self::HEROIC_LEVELS)
+            .unwrap_or(&self.levels)
+    }
+
+    /// The score bought for an ability, before racial adjustments and ability increases
+    #[must_use]
+    pub fn ability_score(&self, ability: Ability) -> u8 {
+        self.ability_scores
+            .get(&ability)
+            .copied()
+            .unwrap_or(Self::BASE_ABILITY_SCORE)
+    }
+
+    /// Changes the score bought for an ability
+    pub fn set_ability_score(&mut self, ability: Ability, score: u8) {
+        self.ability_scores.insert(ability, score);
+    }
+
+    /// The feats the character has that are not chosen in a feat slot
+    #[must_use]
+    pub const fn feats(&self) -> &BTreeSet<Feat> {
+        &self.feats
+    }
+
+    /// Mutable access to the feats the character has that are not chosen in a feat slot
+    pub const fn feats_mut(&mut self) -> &mut BTreeSet<Feat> {
+        &mut self.feats
+    }
+
+    /// The toggles that are turned on
+    #[must_use]
+    pub const fn toggles(&self) -> &BTreeSet<Toggle> {
+        &self.toggles
+    }
+
+    /// Mutable access to the toggles that are turned on
+    pub const fn toggles_mut(&mut self) -> &mut BTreeSet<Toggle> {
+        &mut self.toggles
+    }
+}
+
+/// Compiling
+impl Character {
+    /// Compiles the character into bonuses. The same character always results in the same bonuses,
+    /// in the same order.
+    #[must_use]
+    pub fn to_bonuses(&self) -> Vec<Bonus> {
+        let mut bonuses = vec![flag(self.race, CharacterSource::Race)];
+
+        let (ethics, morals) = self.alignment;
+        bonuses.push(flag(Flag::Alignment(ethics), CharacterSource::Alignment));
+        if ethics != morals {
+            bonuses.push(flag(Flag::Alignment(morals), CharacterSource::Alignment));
+        }
+
+        bonuses.push(Bonus::new(
+            Attribute::TotalCharacterLevel,
+            BonusType::Stacking,
+            self.level(),
+            CharacterSource::Levels,
+        ));
+
+        let classes = self
+            .heroic_levels()
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        bonuses.extend(classes.into_iter().map(|class| {
+            Bonus::new(
+                class,
+                BonusType::Stacking,
+                self.class_level(class),
+                CharacterSource::Levels,
+            )
+        }));
+
+        bonuses.extend(self.point_buy_bonuses());
+        bonuses.extend(self.skill_bonuses());
+
+        bonuses.extend(
+            self.all_feats()
+                .into_iter()
+                .map(|feat| flag(feat, CharacterSource::Feats)),
+        );
+
+        bonuses.extend(self.toggles.iter().map(|toggle| toggle.toggle_bonus(true)));
+
+        bonuses
+    }
+}
+
+fn flag<A>(attribute: A, source: CharacterSource) -> Bonus
+where
+    A: Into<Attribute>,
+{
+    Bonus::new(attribute.into(), BonusType::Stacking, 1, source)
+}
+
+/// Validation
+impl Character {
+    /// Returns every problem with the character. The character is valid if this is empty.
+    #[must_use]
+    pub fn validate(&self) -> Vec<CharacterError> {
+        let mut errors = Vec::new();
+
+        if self.level() > Self::MAX_LEVEL {
+            errors.push(CharacterError::TooManyLevels(self.level()));
+        }
+
+        let (ethics, morals) = self.alignment;
+        if !matches!(
+            ethics,
+            Alignment::Lawful | Alignment::Neutral | Alignment::Chaotic
+        ) || !matches!(
+            morals,
+            Alignment::Good | Alignment::Neutral | Alignment::Evil
+        ) {
+            errors.push(CharacterError::InvalidAlignment(ethics, morals));
+        }
+
+        self.validate_point_buy(&mut errors);
+        self.validate_skills(&mut errors);
+        self.validate_feat_slots(&mut errors);
+
+        let mut groups = BTreeMap::new();
+        for toggle in &self.toggles {
+            if let Some(other) = groups.insert(toggle.toggl_source(), *toggle) {
+                errors.push(CharacterError::ConflictingToggles(other, *toggle));
+            }
+        }
+
+        errors
+    }
+}
+
+/// The parts of a [`Character`] that bonuses come from
+#[derive(Hash, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CharacterSource {
+    /// The race of the character
+    Race,
+    /// The alignment of the character
+    Alignment,
+    /// Class and character levels
+    Levels,
+    /// Ability scores bought with point buy
+    PointBuy,
+    /// Ability scores increased every fourth level
+    AbilityIncreases,
+    /// Skill points spent at each level
+    Skills,
+    /// Chosen feats
+    Feats,
+}
+
+impl Display for CharacterSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Race => write!(f, "Race"),
+            Self::Alignment => write!(f, "Alignment"),
+            Self::Levels => write!(f, "Levels"),
+            Self::PointBuy => write!(f, "Point Buy"),
+            Self::AbilityIncreases => write!(f, "Ability Increases"),
+            Self::Skills => write!(f, "Skills"),
+            Self::Feats => write!(f, "Feats"),
+        }
+    }
+}
+
+impl StaticValues for CharacterSource {
+    fn values() -> impl Iterator<Item = Self> {
+        [
+            Self::Race,
+            Self::Alignment,
+            Self::Levels,
+            Self::PointBuy,
+            Self::AbilityIncreases,
+            Self::Skills,
+            Self::Feats,
+        ]
+        .into_iter()
+    }
+}
+
+impl From<CharacterSource> for BonusSource {
+    fn from(value: CharacterSource) -> Self {
+        Self::Character(value)
+    }
+}
+
+/// A problem with the choices made for a [`Character`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CharacterError {
+    /// The character has more than [`Character::MAX_LEVEL`] levels
+    TooManyLevels(usize),
+    /// The alignment is not a lawful / neutral / chaotic choice followed by a good / neutral /
+    /// evil choice
+    InvalidAlignment(Alignment, Alignment),
+    /// An ability score was set for an ability that cannot have one
+    InvalidAbility(Ability),
+    /// An ability score cannot be bought with point buy
+    AbilityScoreOutOfRange(Ability, u8),
+    /// More points were spent on ability scores than are available
+    PointBuyOverspent {
+        /// The number of points spent
+        spent: u32,
+        /// The number of points available
+        available: u8,
+    },
+    /// A racial adjustment to an ability is not constant, so the range of scores that can be
+    /// bought is not known
+    UnknownRacialAdjustment(Race, Ability),
+    /// An ability was increased at a level that does not increase abilities, or that the
+    /// character has not reached
+    InvalidAbilityIncrease(usize),
+    /// Skill points were spent at a level that does not gain skill points, or on a skill that
+    /// cannot have ranks
+    InvalidSkillPoints(usize, Skill),
+    /// More skill points were spent at a level than are available
+    SkillPointsOverspent {
+        /// The character level the points were spent at
+        level: usize,
+        /// The number of points spent
+        spent: u32,
+        /// The number of points available
+        available: u32,
+    },
+    /// A skill has more ranks than allowed at a level
+    TooManySkillRanks {
+        /// The skill with too many ranks
+        skill: Skill,
+        /// The character level the ranks were spent at
+        level: usize,
+        /// The ranks in the skill
+        ranks: Decimal,
+        /// The most ranks the skill can have
+        max: Decimal,
+    },
+    /// Two toggles are turned on that cannot be on at the same time
+    ConflictingToggles(Toggle, Toggle),
+    /// A feat was chosen for a feat slot that the character does not have
+    UnavailableFeatSlot(FeatSlot),
+    /// A feat was chosen for a feat slot that does not allow it
+    FeatNotAllowed(FeatSlot, Feat),
+    /// A feat was chosen for a feat slot that the character already has, either from another
+    /// feat slot or from [`Character::feats`]
+    DuplicateFeat(FeatSlot, Feat),
+    /// A feat was chosen before the character met its requirements
+    FeatRequirementsNotMet(Feat, UnmetRequirement),
+}
+
+impl Display for CharacterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyLevels(levels) => write!(
+                f,
+                "Character has {levels} levels, but the maximum is {}",
+                Character::MAX_LEVEL
+            ),
+            Self::InvalidAlignment(ethics, morals) => {
+                write!(f, "Invalid alignment {ethics} {morals}")
+            }
+            Self::InvalidAbility(ability) => write!(f, "{ability} cannot have an ability score"),
+            Self::AbilityScoreOutOfRange(ability, score) => write!(
+                f,
+                "{ability} cannot be bought at {score}, it must be between {MIN_POINT_BUY_SCORE} \
+                 and {MAX_POINT_BUY_SCORE}"
+            ),
+            Self::PointBuyOverspent { spent, available } => {
+                write!(
+                    f,
+                    "Spent {spent} points, but only {available} are available"
+                )
+            }
+            Self::UnknownRacialAdjustment(race, ability) => {
+                write!(f, "The {race} adjustment to {ability} is not constant")
+            }
+            Self::InvalidAbilityIncrease(level) => {
+                write!(f, "Cannot increase an ability at level {level}")
+            }
+            Self::InvalidSkillPoints(level, skill) => {
+                write!(f, "Cannot spend skill points on {skill} at level {level}")
+            }
+            Self::SkillPointsOverspent {
+                level,
+                spent,
+                available,
+            } => write!(
+                f,
+                "Spent {spent} skill points at level {level}, but only {available} are available"
+            ),
+            Self::TooManySkillRanks {
+                skill,
+                level,
+                ranks,
+                max,
+            } => write!(
+                f,
+                "{skill} has {ranks} ranks at level {level}, but the maximum is {max}"
+            ),
+            Self::ConflictingToggles(a, b) => write!(f, "{a} and {b} cannot both be on"),
+            Self::UnavailableFeatSlot(slot) => write!(f, "Character does not have a {slot}"),
+            Self::FeatNotAllowed(slot, feat) => write!(f, "{feat} cannot be chosen as a {slot}"),
+            Self::DuplicateFeat(slot, feat) => {
+                write!(f, "{feat} was chosen as a {slot}, but is already taken")
+            }
+            Self::FeatRequirementsNotMet(feat, unmet) => {
+                write!(f, "Requirements for {feat} are not met: {unmet}")
+            }
+        }
+    }
+}
+
+impl Error for CharacterError {}
@@ -0,0 +1,292 @@
+use core::fmt::{self, Display};
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use utils::enums::StaticValues;
+
+use crate::{
+    breakdowns::Breakdowns,
+    feat::{
+        Feat, GetFeatRequirement, Proficiency, ShieldProficiency, SpellcastingFeat,
+        WeaponProficiencyClass,
+    },
+    types::{player_class::PlayerClass, race::Race},
+};
+
+use super::{Character, CharacterError};
+
+/// How often a character gains a general feat, in character levels
+pub const FEAT_SLOT_INTERVAL: usize = 3;
+
+/// The character level where the legendary feat is gained. No general feats are gained after it.
+pub const LEGENDARY_LEVEL: usize = 30;
+
+/// A feat that is chosen at a character level
+#[derive(Hash, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FeatSlot {
+    level: usize,
+    kind: FeatSlotKind,
+}
+
+impl FeatSlot {
+    /// Creates a feat slot gained at a character level
+    #[must_use]
+    pub const fn new(level: usize, kind: FeatSlotKind) -> Self {
+        Self { level, kind }
+    }
+
+    /// The character level the slot is gained at
+    #[must_use]
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+
+    /// The kind of feat slot
+    #[must_use]
+    pub const fn kind(&self) -> FeatSlotKind {
+        self.kind
+    }
+
+    /// Returns `true` if the feat can be chosen in this slot. This does not check the feat's
+    /// requirements.
+    #[must_use]
+    pub fn allows(&self, feat: &Feat) -> bool {
+        self.kind.allows(feat)
+    }
+
+    /// Every feat that can be chosen in this slot
+    pub fn feats(&self) -> impl Iterator<Item = Feat> {
+        self.kind.feats()
+    }
+}
+
+impl Display for FeatSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Level {} {}", self.level, self.kind)
+    }
+}
+
+/// The different kinds of feat slots, which each allow different feats
+#[derive(Hash, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FeatSlotKind {
+    /// General feat gained at level 1 and every 3 heroic levels
+    #[serde(rename = "h", alias = "Heroic")]
+    Heroic,
+    /// General feat gained every 3 epic levels
+    #[serde(rename = "e", alias = "Epic")]
+    Epic,
+    /// General feat gained at level 30
+    #[serde(rename = "l", alias = "Legendary")]
+    Legendary,
+    /// Bonus feat that humans gain at level 1
+    #[serde(rename = "hb", alias = "HumanBonus")]
+    HumanBonus,
+    /// Bonus feat gained from class levels
+    #[serde(rename = "cb", alias = "ClassBonus")]
+    ClassBonus(PlayerClass),
+}
+
+impl FeatSlotKind {
+    /// Returns `true` if the feat can be chosen in this kind of slot.
+    ///
+    /// Heroic, epic and human bonus slots allow general feats. There are no epic or legendary
+    /// feats yet, so legendary slots do not allow any feat. Class bonus slots only allow the
+    /// class's bonus feats. Feat requirements are checked separately by [`Character::validate`].
+    #[must_use]
+    pub fn allows(&self, feat: &Feat) -> bool {
+        match self {
+            Self::Heroic | Self::Epic | Self::HumanBonus => Self::is_general_feat(*feat),
+            Self::Legendary => false,
+            Self::ClassBonus(class) => Self::is_class_bonus_feat(*class, *feat),
+        }
+    }
+
+    /// Returns `true` if the feat can be chosen as a general feat. Racial feats and past lives
+    /// are granted in other ways, as are proficiencies with single simple or martial weapons.
+    fn is_general_feat(feat: Feat) -> bool {
+        match feat {
+            Feat::Proficiency(proficiency) => match proficiency {
+                Proficiency::WeaponProficiency(weapon) => {
+                    WeaponProficiencyClass::from(weapon) == WeaponProficiencyClass::Exotic
+                }
+                Proficiency::SimpleWeaponProficiency
+                | Proficiency::MartialWeaponProficiency
+                | Proficiency::Shield(ShieldProficiency::Shield | ShieldProficiency::TowerShield) => {
+                    true
+                }
+                Proficiency::Shield(ShieldProficiency::Orb) | Proficiency::RuneArm => false,
+            },
+            Feat::SkillFocus(_) | Feat::Spellcasting(_) => true,
+            Feat::RacialFeat(_) | Feat::PastLife(_) => false,
+        }
+    }
+
+    /// Returns `true` if the feat is one of the class's bonus feats
+    fn is_class_bonus_feat(class: PlayerClass, feat: Feat) -> bool {
+        match (class, feat) {
+            (PlayerClass::Fighter, Feat::Proficiency(Proficiency::WeaponProficiency(weapon))) => {
+                WeaponProficiencyClass::from(weapon) == WeaponProficiencyClass::Exotic
+            }
+            (PlayerClass::Wizard | PlayerClass::Artificer, Feat::Spellcasting(feat)) => {
+                feat != SpellcastingFeat::MagicalTraining
+            }
+            _ => false,
+        }
+    }
+
+    /// Every feat that can be chosen in this kind of slot
+    pub fn feats(self) -> impl Iterator<Item = Feat> {
+        Feat::values().filter(move |feat| self.allows(feat))
+    }
+
+    /// Returns `true` if reaching the class level in the class grants a bonus feat
+    const fn is_class_bonus_level(class: PlayerClass, class_level: usize) -> bool {
+        match class {
+            PlayerClass::Fighter => class_level == 1 || class_level.is_multiple_of(2),
+            PlayerClass::Wizard => class_level == 1 || class_level.is_multiple_of(5),
+            PlayerClass::Artificer => class_level.is_multiple_of(4),
+            _ => false,
+        }
+    }
+}
+
+impl Display for FeatSlotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Heroic => write!(f, "Heroic Feat"),
+            Self::Epic => write!(f, "Epic Feat"),
+            Self::Legendary => write!(f, "Legendary Feat"),
+            Self::HumanBonus => write!(f, "Human Bonus Feat"),
+            Self::ClassBonus(class) => write!(f, "{class} Bonus Feat"),
+        }
+    }
+}
+
+/// Feat Slots
+impl Character {
+    /// Chooses the feat for a feat slot
+    #[must_use]
+    pub fn with_slotted_feat<F>(mut self, slot: FeatSlot, feat: F) -> Self
+    where
+        F: Into<Feat>,
+    {
+        self.slotted_feats.insert(slot, feat.into());
+        self
+    }
+
+    /// Chooses the feat for a feat slot, returning the feat that was previously chosen
+    pub fn set_slotted_feat<F>(&mut self, slot: FeatSlot, feat: F) -> Option<Feat>
+    where
+        F: Into<Feat>,
+    {
+        self.slotted_feats.insert(slot, feat.into())
+    }
+
+    /// Removes the feat chosen for a feat slot
+    pub fn remove_slotted_feat(&mut self, slot: &FeatSlot) -> Option<Feat> {
+        self.slotted_feats.remove(slot)
+    }
+
+    /// The feats chosen for each feat slot
+    #[must_use]
+    pub const fn slotted_feats(&self) -> &BTreeMap<FeatSlot, Feat> {
+        &self.slotted_feats
+    }
+
+    /// Every feat slot the character has, based on its race and levels, sorted by level
+    #[must_use]
+    pub fn feat_slots(&self) -> Vec<FeatSlot> {
+        let mut slots = Vec::new();
+        let mut class_levels = BTreeMap::new();
+
+        if matches!(self.race, Race::Human | Race::PurpleDragonKnight) && self.level() > 0 {
+            slots.push(FeatSlot::new(1, FeatSlotKind::HumanBonus));
+        }
+
+        for (index, class) in self.levels.iter().enumerate() {
+            let level = index + 1;
+
+            if level == LEGENDARY_LEVEL {
+                slots.push(FeatSlot::new(level, FeatSlotKind::Legendary));
+            } else if level < LEGENDARY_LEVEL
+                && (level == 1 || level.is_multiple_of(FEAT_SLOT_INTERVAL))
+            {
+                let kind = if level > Self::HEROIC_LEVELS {
+                    FeatSlotKind::Epic
+                } else {
+                    FeatSlotKind::Heroic
+                };
+                slots.push(FeatSlot::new(level, kind));
+            }
+
+            if level <= Self::HEROIC_LEVELS {
+                let class_level = class_levels.entry(*class).or_insert(0);
+                *class_level += 1;
+
+                if FeatSlotKind::is_class_bonus_level(*class, *class_level) {
+                    slots.push(FeatSlot::new(level, FeatSlotKind::ClassBonus(*class)));
+                }
+            }
+        }
+
+        slots.sort_unstable();
+        slots
+    }
+
+    /// Feat slots that do not have a feat chosen
+    #[must_use]
+    pub fn empty_feat_slots(&self) -> Vec<FeatSlot> {
+        self.feat_slots()
+            .into_iter()
+            .filter(|slot| !self.slotted_feats.contains_key(slot))
+            .collect()
+    }
+
+    /// Every feat the character has, including feats chosen in feat slots
+    pub(super) fn all_feats(&self) -> BTreeSet<Feat> {
+        self.feats
+            .iter()
+            .chain(self.slotted_feats.values())
+            .copied()
+            .collect()
+    }
+
+    /// Adds any problems with slotted feats to `errors`.
+    ///
+    /// Feat requirements are checked against the character at the level the feat was chosen,
+    /// with only the feats chosen at earlier levels. A feat that is chosen more than once is
+    /// reported for every slot after the first.
+    pub(super) fn validate_feat_slots(&self, errors: &mut Vec<CharacterError>) {
+        let slots = self.feat_slots();
+        let mut levels = BTreeMap::new();
+        let mut chosen = BTreeSet::new();
+
+        for (slot, feat) in &self.slotted_feats {
+            let duplicate = self.feats.contains(feat) || !chosen.insert(*feat);
+
+            if !slots.contains(slot) {
+                errors.push(CharacterError::UnavailableFeatSlot(*slot));
+            } else if !slot.allows(feat) {
+                errors.push(CharacterError::FeatNotAllowed(*slot, *feat));
+            } else if duplicate {
+                errors.push(CharacterError::DuplicateFeat(*slot, *feat));
+            } else if feat.get_feat_requirements().is_some() {
+                let breakdowns = levels
+                    .entry(slot.level)
+                    .or_insert_with(|| Breakdowns::from_character(&self.at_level(slot.level)));
+
+                if let Some(unmet) = feat.unmet_feat_requirements(breakdowns) {
+                    errors.push(CharacterError::FeatRequirementsNotMet(*feat, unmet));
+                }
+            }
+        }
+    }
+
+    /// The character as it was when reaching the given level, before choosing that level's feats
+    fn at_level(&self, level: usize) -> Self {
+        let mut character = self.clone();
+        character.levels.truncate(level);
+        character.slotted_feats.retain(|slot, _| slot.level < level);
+        character
+    }
+}
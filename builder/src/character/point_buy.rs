@@ -0,0 +1,259 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    attribute::{Attribute, GetBonuses},
+    bonus::{Bonus, BonusType, Value},
+    feat::{Feat, PastLifeFeat},
+    types::{ability::Ability, race::Race},
+};
+
+use super::{Character, CharacterError, CharacterSource};
+
+/// The lowest score that can be bought for an ability, before racial adjustments
+pub const MIN_POINT_BUY_SCORE: u8 = 8;
+
+/// The highest score that can be bought for an ability, before racial adjustments
+pub const MAX_POINT_BUY_SCORE: u8 = 18;
+
+/// How often a character can increase one ability score by 1
+pub const ABILITY_INCREASE_INTERVAL: usize = 4;
+
+/// Returns the total number of points needed to buy an ability score, or [`None`] if the score
+/// cannot be bought
+#[must_use]
+pub const fn point_buy_cost(score: u8) -> Option<u8> {
+    match score {
+        8 => Some(0),
+        9 => Some(1),
+        10 => Some(2),
+        11 => Some(3),
+        12 => Some(4),
+        13 => Some(5),
+        14 => Some(6),
+        15 => Some(8),
+        16 => Some(10),
+        17 => Some(13),
+        18 => Some(16),
+        _ => None,
+    }
+}
+
+/// Returns the number of points available to spend.
+///
+/// These follow the build tiers offered at character creation: 28 points for a new character
+/// (Adventurer), 32 points once the Champion build has been unlocked on the account, 34 points
+/// with one heroic past life (Hero), and 36 points with two or more (Legend).
+#[must_use]
+pub const fn point_buy_pool(past_lives: usize, champion: bool) -> u8 {
+    match past_lives {
+        0 if champion => 32,
+        0 => 28,
+        1 => 34,
+        _ => 36,
+    }
+}
+
+/// Returns how much a race increases or decreases an ability score, including bonuses to
+/// [`Ability::All`]. Bonuses with a condition, such as those from a toggle, are not part of the
+/// adjustment.
+///
+/// Returns [`None`] if any of those bonuses has a value that is not constant, since the
+/// adjustment would then depend on more than the race.
+#[must_use]
+pub fn racial_adjustment(race: Race, ability: Ability) -> Option<Decimal> {
+    race.get_bonuses(Decimal::ONE)
+        .into_iter()
+        .flatten()
+        .filter(|bonus| {
+            bonus.condition().is_none()
+                && matches!(
+                    bonus.attribute(),
+                    Attribute::Ability(adjusted)
+                        if adjusted.eq(&ability) || adjusted.eq(&Ability::All)
+                )
+        })
+        .try_fold(Decimal::ZERO, |total, bonus| {
+            match bonus.value().clone().simplify() {
+                Value::Const(value) => Some(total.saturating_add(value)),
+                _ => None,
+            }
+        })
+}
+
+/// Point Buy
+impl Character {
+    /// Sets the ability that is increased at a level. Only every fourth level can increase an
+    /// ability.
+    #[must_use]
+    pub fn with_ability_increase(mut self, level: usize, ability: Ability) -> Self {
+        self.ability_increases.insert(level, ability);
+        self
+    }
+
+    /// Sets the ability that is increased at a level
+    pub fn set_ability_increase(&mut self, level: usize, ability: Ability) {
+        self.ability_increases.insert(level, ability);
+    }
+
+    /// Removes the ability increase at a level
+    pub fn remove_ability_increase(&mut self, level: usize) -> Option<Ability> {
+        self.ability_increases.remove(&level)
+    }
+
+    /// Returns the ability increased at each level
+    pub fn ability_increases(&self) -> impl Iterator<Item = (usize, Ability)> + '_ {
+        self.ability_increases
+            .iter()
+            .map(|(level, ability)| (*level, *ability))
+    }
+
+    /// The number of heroic past lives the character has
+    #[must_use]
+    pub fn past_lives(&self) -> usize {
+        self.feats
+            .iter()
+            .filter(|feat| matches!(feat, Feat::PastLife(PastLifeFeat::Heroic(_))))
+            .count()
+    }
+
+    /// Sets whether the 32 point Champion build has been unlocked
+    #[must_use]
+    pub const fn with_champion_build(mut self, unlocked: bool) -> Self {
+        self.champion_build = unlocked;
+        self
+    }
+
+    /// Sets whether the 32 point Champion build has been unlocked
+    pub const fn set_champion_build(&mut self, unlocked: bool) {
+        self.champion_build = unlocked;
+    }
+
+    /// Returns `true` if the 32 point Champion build has been unlocked
+    #[must_use]
+    pub const fn has_champion_build(&self) -> bool {
+        self.champion_build
+    }
+
+    /// The number of points the character can spend on ability scores
+    #[must_use]
+    pub fn point_buy_pool(&self) -> u8 {
+        point_buy_pool(self.past_lives(), self.champion_build)
+    }
+
+    /// The number of points spent on ability scores. Scores that cannot be bought, and any score
+    /// set for [`Ability::All`], are not counted.
+    #[must_use]
+    pub fn points_spent(&self) -> u32 {
+        Ability::VALUES
+            .into_iter()
+            .filter_map(|ability| point_buy_cost(self.ability_score(ability)))
+            .map(u32::from)
+            .sum()
+    }
+
+    /// The range of scores that can be bought for an ability, including racial adjustments.
+    /// Racial adjustments that are not constant are treated as `0`, and reported by
+    /// [`Character::validate`].
+    #[must_use]
+    pub fn ability_score_range(&self, ability: Ability) -> (Decimal, Decimal) {
+        let adjustment = racial_adjustment(self.race, ability).unwrap_or_default();
+        (
+            Decimal::from(MIN_POINT_BUY_SCORE) + adjustment,
+            Decimal::from(MAX_POINT_BUY_SCORE) + adjustment,
+        )
+    }
+
+    /// The ability score at a character level, from point buy, racial adjustments, and ability
+    /// increases up to that level. Bonuses from feats, items, and other sources are not included,
+    /// and racial adjustments that are not constant are treated as `0`.
+    #[must_use]
+    pub fn base_ability_score(&self, ability: Ability, level: usize) -> Decimal {
+        let increases = self
+            .ability_increases
+            .iter()
+            .filter(|(increase_level, increased)| {
+                increased.eq(&&ability)
+                    && **increase_level <= level
+                    && self.is_ability_increase_level(**increase_level)
+            })
+            .count();
+
+        Decimal::from(self.ability_score(ability))
+            + racial_adjustment(self.race, ability).unwrap_or_default()
+            + Decimal::from(increases)
+    }
+
+    /// Bonuses from point buy and ability increases. Racial adjustments are added by the race.
+    /// Scores and increases for [`Ability::All`] are skipped, since they are not valid.
+    pub(super) fn point_buy_bonuses(&self) -> impl Iterator<Item = Bonus> + '_ {
+        let scores = Ability::VALUES.into_iter().filter_map(|ability| {
+            let score = self.ability_score(ability);
+            (score != Self::BASE_ABILITY_SCORE).then(|| {
+                Bonus::new(
+                    ability,
+                    BonusType::Stacking,
+                    i16::from(score) - i16::from(Self::BASE_ABILITY_SCORE),
+                    CharacterSource::PointBuy,
+                )
+            })
+        });
+
+        let increases = Ability::VALUES.into_iter().filter_map(|ability| {
+            let count = self
+                .ability_increases
+                .iter()
+                .filter(|(level, increased)| {
+                    increased.eq(&&ability) && self.is_ability_increase_level(**level)
+                })
+                .count();
+
+            (count > 0).then(|| {
+                Bonus::new(
+                    ability,
+                    BonusType::Stacking,
+                    count,
+                    CharacterSource::AbilityIncreases,
+                )
+            })
+        });
+
+        scores.chain(increases)
+    }
+
+    /// Returns `true` if the character can increase an ability at the given level
+    const fn is_ability_increase_level(&self, level: usize) -> bool {
+        level > 0 && level.is_multiple_of(ABILITY_INCREASE_INTERVAL) && level <= self.level()
+    }
+
+    /// Adds any problems with ability scores to `errors`
+    pub(super) fn validate_point_buy(&self, errors: &mut Vec<CharacterError>) {
+        for (ability, score) in &self.ability_scores {
+            if ability.eq(&Ability::All) {
+                errors.push(CharacterError::InvalidAbility(*ability));
+            } else if point_buy_cost(*score).is_none() {
+                errors.push(CharacterError::AbilityScoreOutOfRange(*ability, *score));
+            }
+        }
+
+        errors.extend(
+            Ability::VALUES
+                .into_iter()
+                .filter(|ability| racial_adjustment(self.race, *ability).is_none())
+                .map(|ability| CharacterError::UnknownRacialAdjustment(self.race, ability)),
+        );
+
+        let spent = self.points_spent();
+        let available = self.point_buy_pool();
+        if spent > u32::from(available) {
+            errors.push(CharacterError::PointBuyOverspent { spent, available });
+        }
+
+        for (level, ability) in &self.ability_increases {
+            if ability.eq(&Ability::All) {
+                errors.push(CharacterError::InvalidAbility(*ability));
+            } else if !self.is_ability_increase_level(*level) {
+                errors.push(CharacterError::InvalidAbilityIncrease(*level));
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use crate::{
+    attribute::Attribute,
+    bonus::{Bonus, BonusType},
+    types::{ability::Ability, race::Race, skill::Skill},
+};
+
+use super::{Character, CharacterError, CharacterSource};
+
+/// How many times more skill points are gained at level 1
+pub const FIRST_LEVEL_SKILL_POINT_MULTIPLIER: u32 = 4;
+
+/// How many ranks above the character level a class skill can reach
+pub const MAX_RANKS_ABOVE_LEVEL: usize = 3;
+
+/// Skills
+///
+/// Skill points are spent at each heroic level. Class skills of the class taken at that level
+/// cost one point per rank, and any other skill costs two points per rank.
+impl Character {
+    /// Spends skill points on a skill at a character level
+    #[must_use]
+    pub fn with_skill_points(mut self, level: usize, skill: Skill, points: u8) -> Self {
+        self.set_skill_points(level, skill, points);
+        self
+    }
+
+    /// Sets the skill points spent on a skill at a character level. Setting the points to `0`
+    /// removes them.
+    pub fn set_skill_points(&mut self, level: usize, skill: Skill, points: u8) {
+        if points == 0 {
+            if let Some(skills) = self.skill_points.get_mut(&level) {
+                skills.remove(&skill);
+                if skills.is_empty() {
+                    self.skill_points.remove(&level);
+                }
+            }
+        } else {
+            self.skill_points
+                .entry(level)
+                .or_default()
+                .insert(skill, points);
+        }
+    }
+
+    /// The skill points spent on each skill at each character level
+    #[must_use]
+    pub const fn skill_points(&self) -> &BTreeMap<usize, BTreeMap<Skill, u8>> {
+        &self.skill_points
+    }
+
+    /// The number of skill points gained at a character level, or [`None`] if the character does
+    /// not gain skill points at that level
+    #[must_use]
+    pub fn skill_point_budget(&self, level: usize) -> Option<u32> {
+        let class = self.heroic_levels().get(level.checked_sub(1)?)?;
+
+        let modifier = ((self.base_ability_score(Ability::Intelligence, level) - Decimal::TEN)
+            / Decimal::TWO)
+            .floor();
+        let racial = i64::from(matches!(self.race, Race::Human | Race::PurpleDragonKnight));
+        let points = (i64::from(class.skill_points_per_level())
+            + i64::try_from(modifier).unwrap_or_default()
+            + racial)
+            .max(1);
+
+        let points = u32::try_from(points).unwrap_or_default();
+        Some(if level == 1 {
+            points * FIRST_LEVEL_SKILL_POINT_MULTIPLIER
+        } else {
+            points
+        })
+    }
+
+    /// The number of skill points spent at a character level
+    #[must_use]
+    pub fn skill_points_spent(&self, level: usize) -> u32 {
+        self.skill_points
+            .get(&level)
+            .into_iter()
+            .flat_map(BTreeMap::values)
+            .copied()
+            .map(u32::from)
+            .sum()
+    }
+
+    /// Returns `true` if the skill costs one point per rank at the character level
+    #[must_use]
+    pub fn is_class_skill(&self, skill: Skill, level: usize) -> bool {
+        level
+            .checked_sub(1)
+            .and_then(|index| self.heroic_levels().get(index))
+            .is_some_and(|class| class.is_class_skill(skill))
+    }
+
+    /// The highest number of ranks a skill can have at a character level. Skills that are a class
+    /// skill for any class taken by that level can reach 3 ranks above the level, and any other
+    /// skill can reach half of that.
+    #[must_use]
+    pub fn max_skill_ranks(&self, skill: Skill, level: usize) -> Decimal {
+        let level = level.min(Self::HEROIC_LEVELS);
+        let max = Decimal::from(level + MAX_RANKS_ABOVE_LEVEL);
+
+        let is_class_skill = self
+            .heroic_levels()
+            .get(..level)
+            .unwrap_or_else(|| self.heroic_levels())
+            .iter()
+            .any(|class| class.is_class_skill(skill));
+
+        if is_class_skill {
+            max
+        } else {
+            max / Decimal::TWO
+        }
+    }
+
+    /// The total ranks in a skill at a character level
+    #[must_use]
+    pub fn skill_ranks(&self, skill: Skill, level: usize) -> Decimal {
+        self.skill_points
+            .range(..=level)
+            .filter_map(|(level, skills)| {
+                let class = self.heroic_levels().get(level.checked_sub(1)?)?;
+                let points = Decimal::from(*skills.get(&skill)?);
+                Some(if class.is_class_skill(skill) {
+                    points
+                } else {
+                    points / Decimal::TWO
+                })
+            })
+            .sum()
+    }
+
+    /// Bonuses from skill ranks, added to both [`Attribute::Skill`] and
+    /// [`Attribute::SkillRanks`]
+    pub(super) fn skill_bonuses(&self) -> impl Iterator<Item = Bonus> + '_ {
+        Skill::SKILLS.into_iter().flat_map(|skill| {
+            let ranks = self.skill_ranks(skill, self.level());
+            (ranks > Decimal::ZERO)
+                .then(|| {
+                    [Attribute::Skill(skill), Attribute::SkillRanks(skill)].map(|attribute| {
+                        Bonus::new(
+                            attribute,
+                            BonusType::Stacking,
+                            ranks,
+                            CharacterSource::Skills,
+                        )
+                    })
+                })
+                .into_iter()
+                .flatten()
+        })
+    }
+
+    /// Adds any problems with spent skill points to `errors`
+    pub(super) fn validate_skills(&self, errors: &mut Vec<CharacterError>) {
+        for (level, skills) in &self.skill_points {
+            let Some(available) = self.skill_point_budget(*level) else {
+                errors.extend(
+                    skills
+                        .keys()
+                        .map(|skill| CharacterError::InvalidSkillPoints(*level, *skill)),
+                );
+                continue;
+            };
+
+            let spent = self.skill_points_spent(*level);
+            if spent > available {
+                errors.push(CharacterError::SkillPointsOverspent {
+                    level: *level,
+                    spent,
+                    available,
+                });
+            }
+
+            for skill in skills.keys() {
+                if skill.eq(&Skill::All) {
+                    errors.push(CharacterError::InvalidSkillPoints(*level, *skill));
+                    continue;
+                }
+
+                let ranks = self.skill_ranks(*skill, *level);
+                let max = self.max_skill_ranks(*skill, *level);
+                if ranks > max {
+                    errors.push(CharacterError::TooManySkillRanks {
+                        skill: *skill,
+                        level: *level,
+                        ranks,
+                        max,
+                    });
+                }
+            }
+        }
+    }
+}
@@ -72,6 +72,29 @@ impl ItemSet {
         &self.bonuses
     }
 
+    /// Simplifies the value and condition of every bonus in this [`ItemSet`].
+    ///
+    /// See [`BonusTemplate::simplify`]
+    #[must_use]
+    pub fn simplify(self) -> Self {
+        Self {
+            bonuses: self
+                .bonuses
+                .into_iter()
+                .map(|(count, bonuses)| {
+                    (
+                        count,
+                        bonuses
+                            .into_iter()
+                            .map(BonusTemplate::simplify)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect(),
+            ..self
+        }
+    }
+
     /// Returns a mutable reference to the bonuses of this [`SetBonus`].
     pub const fn bonuses_mut(&mut self) -> &mut OrdMap<i32, Vec<BonusTemplate>> {
         &mut self.bonuses
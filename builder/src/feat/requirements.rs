@@ -1,20 +1,30 @@
-use serde::{Deserialize, Serialize};
+use core::fmt::{self, Display};
 
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
-use crate::types::{player_class::PlayerClass, skill::Skill, ability::Ability};
+use crate::{
+    attribute::Attribute,
+    breakdowns::Breakdowns,
+    types::{ability::Ability, player_class::PlayerClass, skill::Skill},
+};
 
 use super::Feat;
 
 /// Describes requirements that must be satisfied in order for a feat to be taken
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FeatRequirement {
     /// Requires that a feat is taken
     Feat(Feat),
     /// Requires that the player has a number of levels in a class
     ClassLevel(PlayerClass, u8),
-    /// Requires that the player has spent a number of points into a skill
+    /// Requires that the player has spent a number of points into a skill. This is checked against
+    /// [`Attribute::SkillRanks`], which only counts skill points spent on a [`Character`].
+    ///
+    /// [`Character`]: crate::character::Character
     SkillPoints(Skill, u8),
-    /// Requires the player to have obtained a given class level
+    /// Requires the player to have a minimum base attack bonus
     BaseAttackBonus(u8),
     /// Ability Score
     AbilityScore(Ability, u8),
@@ -38,6 +48,178 @@ impl FeatRequirement {
     pub fn not_all(requirements: Vec<Self>) -> Self {
         Self::Not(Box::new(Self::All(requirements)))
     }
+
+    /// Returns `true` if the build meets this requirement
+    #[must_use]
+    pub fn is_satisfied(&self, breakdowns: &Breakdowns) -> bool {
+        self.unmet_requirements(breakdowns).is_none()
+    }
+
+    /// Returns which parts of this requirement the build does not meet, or [`None`] if the
+    /// requirement is met.
+    #[must_use]
+    pub fn unmet_requirements(&self, breakdowns: &Breakdowns) -> Option<UnmetRequirement> {
+        match self {
+            Self::Feat(feat) => (breakdowns.evaluate_attribute_from(*feat) <= Decimal::ZERO)
+                .then_some(UnmetRequirement::Feat(*feat)),
+            Self::ClassLevel(class, required) => {
+                let current = breakdowns.evaluate_attribute_from(*class);
+                (current < Decimal::from(*required)).then_some(UnmetRequirement::ClassLevel {
+                    class: *class,
+                    required: *required,
+                    current,
+                })
+            }
+            Self::SkillPoints(skill, required) => {
+                let current = breakdowns.evaluate_attribute_from(Attribute::SkillRanks(*skill));
+                (current < Decimal::from(*required)).then_some(UnmetRequirement::SkillPoints {
+                    skill: *skill,
+                    required: *required,
+                    current,
+                })
+            }
+            Self::BaseAttackBonus(required) => {
+                let current = breakdowns.evaluate_attribute_from(Attribute::BaseAttackBonus);
+                (current < Decimal::from(*required)).then_some(
+                    UnmetRequirement::BaseAttackBonus {
+                        required: *required,
+                        current,
+                    },
+                )
+            }
+            Self::AbilityScore(ability, required) => {
+                let current = breakdowns.evaluate_attribute_from(*ability);
+                (current < Decimal::from(*required)).then_some(UnmetRequirement::AbilityScore {
+                    ability: *ability,
+                    required: *required,
+                    current,
+                })
+            }
+            Self::Any(requirements) => {
+                let mut unmet = Vec::new();
+                for requirement in requirements {
+                    unmet.push(requirement.unmet_requirements(breakdowns)?);
+                }
+                Some(UnmetRequirement::Any(unmet))
+            }
+            Self::All(requirements) => {
+                let unmet = requirements
+                    .iter()
+                    .filter_map(|requirement| requirement.unmet_requirements(breakdowns))
+                    .collect::<Vec<_>>();
+                (!unmet.is_empty()).then_some(UnmetRequirement::All(unmet))
+            }
+            Self::Not(requirement) => requirement
+                .is_satisfied(breakdowns)
+                .then(|| UnmetRequirement::Not((**requirement).clone())),
+        }
+    }
+}
+
+impl Display for FeatRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feat(feat) => write!(f, "{feat}"),
+            Self::ClassLevel(class, level) => write!(f, "{class} Level {level}"),
+            Self::SkillPoints(skill, ranks) => write!(f, "{ranks} Ranks in {skill}"),
+            Self::BaseAttackBonus(bab) => write!(f, "Base Attack Bonus {bab}"),
+            Self::AbilityScore(ability, score) => write!(f, "{score} {ability}"),
+            Self::Any(requirements) => write!(f, "Any of ({})", requirements.iter().join(", ")),
+            Self::All(requirements) => write!(f, "All of ({})", requirements.iter().join(", ")),
+            Self::Not(requirement) => write!(f, "Not {requirement}"),
+        }
+    }
+}
+
+/// Describes which parts of a [`FeatRequirement`] a build does not meet, along with the values
+/// that the build currently has.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UnmetRequirement {
+    /// The feat has not been taken
+    Feat(Feat),
+    /// The player does not have enough levels in a class
+    ClassLevel {
+        /// The class that needs levels
+        class: PlayerClass,
+        /// The number of levels needed
+        required: u8,
+        /// The number of levels the player has
+        current: Decimal,
+    },
+    /// The player has not spent enough points into a skill
+    SkillPoints {
+        /// The skill that needs ranks
+        skill: Skill,
+        /// The number of ranks needed
+        required: u8,
+        /// The number of ranks the player has
+        current: Decimal,
+    },
+    /// The player's base attack bonus is too low
+    BaseAttackBonus {
+        /// The base attack bonus needed
+        required: u8,
+        /// The player's base attack bonus
+        current: Decimal,
+    },
+    /// The player's ability score is too low
+    AbilityScore {
+        /// The ability that is too low
+        ability: Ability,
+        /// The score needed
+        required: u8,
+        /// The player's score
+        current: Decimal,
+    },
+    /// None of the options are met. Contains why each option is not met.
+    Any(Vec<Self>),
+    /// Only the requirements that are not met
+    All(Vec<Self>),
+    /// The requirement is met, but must not be
+    Not(FeatRequirement),
+}
+
+impl UnmetRequirement {
+    /// Returns every requirement in the tree that is not met, without the [`Self::Any`] and
+    /// [`Self::All`] nodes that group them.
+    #[must_use]
+    pub fn leaves(&self) -> Vec<&Self> {
+        match self {
+            Self::Any(requirements) | Self::All(requirements) => {
+                requirements.iter().flat_map(Self::leaves).collect()
+            }
+            _ => vec![self],
+        }
+    }
+}
+
+impl Display for UnmetRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feat(feat) => write!(f, "Requires {feat}"),
+            Self::ClassLevel {
+                class,
+                required,
+                current,
+            } => write!(f, "Requires {class} Level {required} (has {current})"),
+            Self::SkillPoints {
+                skill,
+                required,
+                current,
+            } => write!(f, "Requires {required} Ranks in {skill} (has {current})"),
+            Self::BaseAttackBonus { required, current } => {
+                write!(f, "Requires Base Attack Bonus {required} (has {current})")
+            }
+            Self::AbilityScore {
+                ability,
+                required,
+                current,
+            } => write!(f, "Requires {required} {ability} (has {current})"),
+            Self::Any(requirements) => write!(f, "Any of ({})", requirements.iter().join(", ")),
+            Self::All(requirements) => write!(f, "{}", requirements.iter().join(", ")),
+            Self::Not(requirement) => write!(f, "Cannot have {requirement}"),
+        }
+    }
 }
 
 /// Adds the trait that returns the list of requirements that a feat has.
@@ -45,4 +227,10 @@ pub trait GetFeatRequirement {
     /// Returns a list of requirements that a feat has. Returns [`None`] if there are no
     /// requirements
     fn get_feat_requirements(&self) -> Option<FeatRequirement>;
+
+    /// Returns which requirements the build does not meet, or [`None`] if the feat can be taken
+    fn unmet_feat_requirements(&self, breakdowns: &Breakdowns) -> Option<UnmetRequirement> {
+        self.get_feat_requirements()?
+            .unmet_requirements(breakdowns)
+    }
 }
@@ -3,6 +3,7 @@
 pub mod attribute;
 pub mod bonus;
 pub mod breakdowns;
+pub mod character;
 pub mod debug;
 pub mod equipment;
 pub mod feat;
@@ -5,7 +5,12 @@ use core::fmt::{self, Display};
 use serde::{Deserialize, Serialize};
 use utils::enums::StaticValues;
 
-use crate::attribute::{Attribute, ToAttribute};
+use crate::{
+    attribute::{Attribute, ToAttribute},
+    bonus::{ToValue, Value},
+    types::armor_class::ArmorClass,
+    val,
+};
 
 /// Dodge, Dodge Cap, and Total Dodge
 #[derive(Hash, Clone, Eq, PartialEq, Debug, PartialOrd, Ord, Serialize, Deserialize)]
@@ -27,6 +32,13 @@ pub enum Dodge {
 impl Dodge {
     /// All values
     pub const ALL: [Self; 4] = [Self::Bonus, Self::Cap, Self::Total, Self::Uncapped];
+
+    /// The maximum total of dodge bonuses, from bonuses to [`Dodge::Cap`] and the current max dex
+    /// bonus
+    #[must_use]
+    pub fn cap() -> Value {
+        Self::Cap.to_value() + ArmorClass::MaxDexBonus.to_value().min(val!(25))
+    }
 }
 
 impl Display for Dodge {
@@ -8,7 +8,7 @@ use rust_decimal_macros::dec;
 
 use crate::{
     attribute::{Attribute, GetBonuses},
-    bonus::{BonusTemplate, BonusType, Condition, ConditionFold, ToValue, Value},
+    bonus::{BonusTemplate, BonusType, Condition, ConditionFold, Value},
     types::{
         ability::Ability,
         absorption::{Absorption, AbsorptionSource},
@@ -41,18 +41,9 @@ impl GetBonuses for GuildLevel {
             B: Into<Value>,
             C: Into<Value>,
         {
-            Value::condition(
-                Attribute::TotalCharacterLevel
-                    .to_value()
-                    .greater_than(val!(20)),
-                c,
-                Value::condition(
-                    Attribute::TotalCharacterLevel
-                        .to_value()
-                        .greater_than(val!(10)),
-                    b,
-                    a,
-                ),
+            Value::table(
+                Attribute::TotalCharacterLevel,
+                [(0, a.into()), (11, b.into()), (21, c.into())],
             )
         }
 
@@ -1,5 +1,5 @@
 //! Each of the playable player classes and archetypes
-public_modules!(bonuses);
+public_modules!(bonuses, skills);
 
 use core::fmt;
 
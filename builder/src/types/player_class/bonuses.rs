@@ -1,4 +1,5 @@
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 use crate::{
     attribute::{Attribute, GetBonuses},
@@ -9,11 +10,18 @@ use crate::{
 impl GetBonuses for PlayerClass {
     fn get_bonuses(&self, value: Decimal) -> Option<Vec<BonusTemplate>> {
         // FIX: Check Logic for if zero-value bonuses are passed in
-        let mut bonuses = vec![BonusTemplate::new(
-            Attribute::CasterLevel((*self).into()),
-            BonusType::Stacking,
-            value,
-        )];
+        let mut bonuses = vec![
+            BonusTemplate::new(
+                Attribute::CasterLevel((*self).into()),
+                BonusType::Stacking,
+                value,
+            ),
+            BonusTemplate::new(
+                Attribute::BaseAttackBonus,
+                BonusType::Stacking,
+                (value * self.base_attack_bonus_progression()).floor(),
+            ),
+        ];
 
         if let Some(mut dc_bonuses) = self.get_ability_spell_dc_bonuses(value) {
             bonuses.append(&mut dc_bonuses);
@@ -24,6 +32,21 @@ impl GetBonuses for PlayerClass {
 }
 
 impl PlayerClass {
+    /// The base attack bonus gained with each level in this class
+    #[must_use]
+    pub const fn base_attack_bonus_progression(&self) -> Decimal {
+        match self {
+            Self::Barbarian
+            | Self::Fighter
+            | Self::Paladin
+            | Self::SacredFist
+            | Self::Ranger
+            | Self::DarkHunter => Decimal::ONE,
+            Self::Sorcerer | Self::Wizard | Self::WildMage => dec!(0.5),
+            _ => dec!(0.75),
+        }
+    }
+
     fn ability_bonus_to_spell_dc(self, ability: Ability) -> BonusTemplate {
         BonusTemplate::new(
             Attribute::SpellDC(self.into()),
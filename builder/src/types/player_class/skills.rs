@@ -0,0 +1,173 @@
+use crate::types::{player_class::PlayerClass, skill::Skill};
+
+impl PlayerClass {
+    /// The number of skill points gained with each level in this class, before the Intelligence
+    /// modifier is added
+    #[must_use]
+    pub const fn skill_points_per_level(&self) -> u8 {
+        match self {
+            Self::Rogue => 8,
+            Self::Bard | Self::Stormsinger | Self::Ranger | Self::DarkHunter => 6,
+            Self::Alchemist
+            | Self::Artificer
+            | Self::Barbarian
+            | Self::Monk
+            | Self::Druid
+            | Self::BlightCaster => 4,
+            _ => 2,
+        }
+    }
+
+    /// Skills that cost one skill point per rank when taking a level in this class. Archetypes
+    /// share the class skills of their parent class.
+    #[must_use]
+    pub const fn class_skills(&self) -> &'static [Skill] {
+        match self {
+            Self::Alchemist => &[
+                Skill::Concentration,
+                Skill::Heal,
+                Skill::Repair,
+                Skill::Search,
+                Skill::Spellcraft,
+                Skill::Spot,
+                Skill::UseMagicalDevice,
+            ],
+            Self::Artificer => &[
+                Skill::Balance,
+                Skill::Concentration,
+                Skill::DisableDevice,
+                Skill::Haggle,
+                Skill::OpenLock,
+                Skill::Repair,
+                Skill::Search,
+                Skill::Spellcraft,
+                Skill::Spot,
+                Skill::UseMagicalDevice,
+            ],
+            Self::Barbarian => &[
+                Skill::Balance,
+                Skill::Intimidate,
+                Skill::Jump,
+                Skill::Listen,
+                Skill::Swim,
+            ],
+            Self::Fighter => &[
+                Skill::Balance,
+                Skill::Intimidate,
+                Skill::Jump,
+                Skill::Repair,
+                Skill::Swim,
+            ],
+            Self::Monk => &[
+                Skill::Balance,
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Hide,
+                Skill::Jump,
+                Skill::Listen,
+                Skill::MoveSilently,
+                Skill::Spot,
+                Skill::Swim,
+                Skill::Tumble,
+            ],
+            Self::Rogue => &[
+                Skill::Balance,
+                Skill::Bluff,
+                Skill::Diplomacy,
+                Skill::DisableDevice,
+                Skill::Haggle,
+                Skill::Hide,
+                Skill::Intimidate,
+                Skill::Jump,
+                Skill::Listen,
+                Skill::MoveSilently,
+                Skill::OpenLock,
+                Skill::Perform,
+                Skill::Repair,
+                Skill::Search,
+                Skill::Spot,
+                Skill::Swim,
+                Skill::Tumble,
+                Skill::UseMagicalDevice,
+            ],
+            Self::Sorcerer | Self::WildMage => {
+                &[Skill::Bluff, Skill::Concentration, Skill::Spellcraft]
+            }
+            Self::Wizard => &[
+                Skill::Concentration,
+                Skill::Repair,
+                Skill::Search,
+                Skill::Spellcraft,
+            ],
+            Self::FavoredSoul => &[
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Heal,
+                Skill::Jump,
+                Skill::Spellcraft,
+            ],
+            Self::Bard | Self::Stormsinger => &[
+                Skill::Balance,
+                Skill::Bluff,
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Haggle,
+                Skill::Hide,
+                Skill::Jump,
+                Skill::Listen,
+                Skill::MoveSilently,
+                Skill::Perform,
+                Skill::Spellcraft,
+                Skill::Swim,
+                Skill::Tumble,
+                Skill::UseMagicalDevice,
+            ],
+            Self::Cleric | Self::DarkApostate => &[
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Heal,
+                Skill::Spellcraft,
+            ],
+            Self::Druid | Self::BlightCaster => &[
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Heal,
+                Skill::Listen,
+                Skill::Spellcraft,
+                Skill::Spot,
+                Skill::Swim,
+            ],
+            Self::Paladin | Self::SacredFist => &[
+                Skill::Concentration,
+                Skill::Diplomacy,
+                Skill::Heal,
+                Skill::Intimidate,
+            ],
+            Self::Ranger | Self::DarkHunter => &[
+                Skill::Concentration,
+                Skill::Heal,
+                Skill::Hide,
+                Skill::Jump,
+                Skill::Listen,
+                Skill::MoveSilently,
+                Skill::Search,
+                Skill::Spot,
+                Skill::Swim,
+            ],
+            Self::Warlock | Self::AcolyteOfTheSkin => &[
+                Skill::Bluff,
+                Skill::Concentration,
+                Skill::Intimidate,
+                Skill::Jump,
+                Skill::Spellcraft,
+                Skill::Swim,
+            ],
+        }
+    }
+
+    /// Returns `true` if the skill is a class skill for this class
+    #[must_use]
+    pub fn is_class_skill(&self, skill: Skill) -> bool {
+        self.class_skills().contains(&skill)
+    }
+}
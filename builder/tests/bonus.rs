@@ -57,6 +57,36 @@ mod has_dependency {
             assert!(!value.has_attr_dependency(&Attribute::Debug(2)));
         }
 
+        #[test]
+        fn table() {
+            let value = Value::table(
+                Attribute::Debug(0),
+                [(0, Attribute::Debug(1)), (10, Attribute::Debug(2))],
+            );
+            assert!(value.has_attr_dependency(&Attribute::Debug(0)));
+            assert!(value.has_attr_dependency(&Attribute::Debug(1)));
+            assert!(value.has_attr_dependency(&Attribute::Debug(2)));
+            assert!(!value.has_attr_dependency(&Attribute::Debug(3)));
+        }
+
+        #[test]
+        fn clamp() {
+            let value = Value::from(Attribute::Debug(0))
+                .clamp(Attribute::Debug(1).into(), Attribute::Debug(2).into());
+            assert!(value.has_attr_dependency(&Attribute::Debug(0)));
+            assert!(value.has_attr_dependency(&Attribute::Debug(1)));
+            assert!(value.has_attr_dependency(&Attribute::Debug(2)));
+            assert!(!value.has_attr_dependency(&Attribute::Debug(3)));
+        }
+
+        #[test]
+        fn sum() {
+            let value = Value::sum_all([Attribute::Debug(0), Attribute::Debug(1)]);
+            assert!(value.has_attr_dependency(&Attribute::Debug(0)));
+            assert!(value.has_attr_dependency(&Attribute::Debug(1)));
+            assert!(!value.has_attr_dependency(&Attribute::Debug(2)));
+        }
+
         #[test]
         fn attribute() {
             let value = Value::Attribute(Attribute::Debug(0));
@@ -264,6 +294,19 @@ mod has_dependency {
             assert!(condition.has_attr_dependency(&Attribute::Debug(1)));
             assert!(!condition.has_attr_dependency(&Attribute::Debug(2)));
         }
+
+        #[test]
+        fn lists() {
+            for condition in [
+                Condition::all([attr_condition(0), attr_condition(1)]),
+                Condition::any([attr_condition(0), attr_condition(1)]),
+                Condition::at_least(1, [attr_condition(0), attr_condition(1)]),
+            ] {
+                assert!(condition.has_attr_dependency(&Attribute::Debug(0)));
+                assert!(condition.has_attr_dependency(&Attribute::Debug(1)));
+                assert!(!condition.has_attr_dependency(&Attribute::Debug(2)));
+            }
+        }
     }
 }
 
@@ -321,6 +364,40 @@ mod include_dependencies {
             assert!(!deps.contains(&Attribute::Debug(2)));
         }
 
+        #[test]
+        fn table() {
+            let value = Value::table(
+                Attribute::Debug(0),
+                [(0, Attribute::Debug(1)), (10, Attribute::Debug(2))],
+            );
+            let deps = value.get_attr_dependencies();
+
+            assert!(deps.contains(&Attribute::Debug(0)));
+            assert!(deps.contains(&Attribute::Debug(1)));
+            assert!(deps.contains(&Attribute::Debug(2)));
+            assert!(!deps.contains(&Attribute::Debug(3)));
+        }
+
+        #[test]
+        fn product() {
+            let value = Value::product_all([Attribute::Debug(0), Attribute::Debug(1)]);
+            let deps = value.get_attr_dependencies();
+
+            assert!(deps.contains(&Attribute::Debug(0)));
+            assert!(deps.contains(&Attribute::Debug(1)));
+            assert!(!deps.contains(&Attribute::Debug(2)));
+        }
+
+        #[test]
+        fn pow() {
+            let value = Value::from(Attribute::Debug(0)).pow(Attribute::Debug(1).into());
+            let deps = value.get_attr_dependencies();
+
+            assert!(deps.contains(&Attribute::Debug(0)));
+            assert!(deps.contains(&Attribute::Debug(1)));
+            assert!(!deps.contains(&Attribute::Debug(2)));
+        }
+
         #[test]
         fn attribute() {
             let value = Value::Attribute(Attribute::Debug(0));
@@ -553,6 +630,20 @@ mod include_dependencies {
             assert!(deps.contains(&Attribute::Debug(1)));
             assert!(!deps.contains(&Attribute::Debug(2)));
         }
+
+        #[test]
+        fn lists() {
+            for condition in [
+                Condition::all([attr_condition(0), attr_condition(1)]),
+                Condition::any([attr_condition(0), attr_condition(1)]),
+                Condition::at_least(1, [attr_condition(0), attr_condition(1)]),
+            ] {
+                let deps = condition.get_attr_dependencies();
+                assert!(deps.contains(&Attribute::Debug(0)));
+                assert!(deps.contains(&Attribute::Debug(1)));
+                assert!(!deps.contains(&Attribute::Debug(2)));
+            }
+        }
     }
 }
 
@@ -577,6 +668,18 @@ mod has_dice {
             assert!(!Value::Attribute(Attribute::Debug(0)).has_dice());
         }
 
+        #[test]
+        fn sign() {
+            assert!(!Value::from(0).sign().has_dice());
+            assert!(dice().sign().has_dice());
+        }
+
+        #[test]
+        fn sum() {
+            assert!(!Value::sum_all([0, 1]).has_dice());
+            assert!(Value::sum_all([Value::from(0), dice()]).has_dice());
+        }
+
         #[test]
         fn min() {
             assert!(!Value::min(Value::from(0), Value::from(0)).has_dice());
@@ -657,6 +760,13 @@ mod has_dice {
             assert!(Value::condition(false, dice(), 0).has_dice());
             assert!(Value::condition(false, 0, dice()).has_dice());
         }
+
+        #[test]
+        fn table() {
+            assert!(!Value::table(0, [(0, 1), (5, 2)]).has_dice());
+            assert!(Value::table(dice(), [(0, 1), (5, 2)]).has_dice());
+            assert!(Value::table(0, [(0, Value::ONE), (5, dice())]).has_dice());
+        }
     }
 
     mod condition {
@@ -721,6 +831,14 @@ mod has_dice {
             assert!((dice_cond() ^ no_cond()).has_dice());
             assert!((no_cond() ^ dice_cond()).has_dice());
         }
+
+        #[test]
+        fn lists() {
+            assert!(!Condition::all([no_cond(), no_cond()]).has_dice());
+            assert!(Condition::all([no_cond(), dice_cond()]).has_dice());
+            assert!(Condition::any([dice_cond(), no_cond()]).has_dice());
+            assert!(Condition::at_least(1, [no_cond(), dice_cond()]).has_dice());
+        }
     }
 }
 
@@ -755,6 +873,19 @@ mod depth {
         condition
     }
 
+    #[test]
+    fn table_depth() {
+        let value = Value::table(value_depth(2), [(0, value_depth(4)), (1, value_depth(1))]);
+        assert_eq!(value.get_depth(), 5);
+    }
+
+    #[test]
+    fn list_condition_depth() {
+        let condition = Condition::all([condition_depth(3), condition_depth(5), Condition::TRUE]);
+        assert_eq!(condition.get_depth(), 6);
+        assert_eq!(Condition::any([]).get_depth(), 1);
+    }
+
     #[test]
     fn test_condition_depth() {
         assert_eq!(condition_depth(5).get_depth(), 5);
@@ -887,3 +1018,364 @@ mod depth {
         }
     }
 }
+
+mod name {
+    use builder::{bonus::BonusTemplate, debug::DebugValue};
+    use ron::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn name_serializes() {
+        let bonus = Bonus::new(DebugValue(0), BonusType::Insightful, 3, DebugValue(0))
+            .with_name("Insightful Constitution");
+
+        let serialized = to_string(&bonus).expect("Could not serialize bonus");
+        let deserialized: Bonus = from_str(&serialized).expect("Could not deserialize bonus");
+
+        assert_eq!(deserialized.name(), Some("Insightful Constitution"));
+        assert_eq!(bonus, deserialized);
+    }
+
+    #[test]
+    fn missing_name_deserializes() {
+        let bonus = Bonus::new(DebugValue(0), BonusType::Insightful, 3, DebugValue(0));
+
+        let serialized = to_string(&bonus).expect("Could not serialize bonus");
+        let deserialized: Bonus = from_str(&serialized).expect("Could not deserialize bonus");
+
+        assert_eq!(deserialized.name(), None);
+    }
+
+    #[test]
+    fn template_keeps_name() {
+        let bonus = BonusTemplate::new(DebugValue(0), BonusType::Insightful, 3)
+            .with_name("Insightful Constitution")
+            .to_bonus(DebugValue(0));
+
+        assert_eq!(bonus.name(), Some("Insightful Constitution"));
+    }
+}
+
+mod simplify {
+    use builder::{bonus::BonusTemplate, debug::DebugValue};
+
+    use super::*;
+
+    const fn dummy() -> Value {
+        Value::Attribute(Attribute::Debug(0))
+    }
+
+    #[test]
+    fn constants_fold() {
+        assert_eq!((Value::ONE + Value::TWO).simplify(), Value::Const(3.into()));
+        assert_eq!(
+            (Value::from(10) - Value::from(4)).simplify(),
+            Value::Const(6.into())
+        );
+        assert_eq!(
+            (Value::TWO * Value::from(3)).simplify(),
+            Value::Const(6.into())
+        );
+        assert_eq!(
+            Value::from(5)
+                .max(Value::TWO)
+                .min(Value::from(4))
+                .simplify(),
+            Value::Const(4.into())
+        );
+        assert_eq!(
+            (Value::from(-7) / Value::TWO).floor().abs().simplify(),
+            Value::Const(4.into())
+        );
+    }
+
+    #[test]
+    fn nested_constants_fold() {
+        let value = dummy() + (Value::ONE + Value::TWO) * Value::TWO;
+        assert_eq!(value.simplify(), dummy() + Value::Const(6.into()));
+    }
+
+    #[test]
+    fn identities_are_removed() {
+        assert_eq!((Value::ZERO + dummy()).simplify(), dummy());
+        assert_eq!((dummy() + Value::ZERO).simplify(), dummy());
+        assert_eq!((dummy() - Value::ZERO).simplify(), dummy());
+        assert_eq!((Value::ONE * dummy()).simplify(), dummy());
+        assert_eq!((dummy() * Value::ONE).simplify(), dummy());
+        assert_eq!((dummy() / Value::ONE).simplify(), dummy());
+    }
+
+    #[test]
+    fn non_identities_are_kept() {
+        assert_eq!((Value::ZERO - dummy()).simplify(), Value::ZERO - dummy());
+        assert_eq!((Value::ONE / dummy()).simplify(), Value::ONE / dummy());
+    }
+
+    #[test]
+    fn division_by_zero_is_kept() {
+        let value = Value::ONE / Value::ZERO;
+        assert_eq!(value.clone().simplify(), value);
+    }
+
+    #[test]
+    fn constant_if_collapses() {
+        let value = Value::condition(Condition::TRUE, dummy(), Value::ZERO);
+        assert_eq!(value.simplify(), dummy());
+
+        let value = Value::condition(
+            Condition::GreaterThan(Value::ONE, Value::TWO),
+            dummy(),
+            Value::ZERO,
+        );
+        assert_eq!(value.simplify(), Value::ZERO);
+    }
+
+    #[test]
+    fn if_with_same_branches_collapses() {
+        let value = Value::condition(
+            Condition::has(Attribute::Debug(1)),
+            dummy(),
+            dummy() + Value::ZERO,
+        );
+        assert_eq!(value.simplify(), dummy());
+    }
+
+    #[test]
+    fn constant_table_collapses() {
+        let steps = [
+            (0, Value::ONE),
+            (10, dummy()),
+            (20, Value::TWO + Value::ONE),
+        ];
+
+        assert_eq!(Value::table(15, steps.clone()).simplify(), dummy());
+        assert_eq!(
+            Value::table(25, steps.clone()).simplify(),
+            Value::Const(3.into())
+        );
+        assert_eq!(Value::table(-5, steps.clone()).simplify(), Value::ZERO);
+        assert_eq!(
+            Value::table(dummy(), steps).simplify(),
+            Value::table(
+                dummy(),
+                [(0, Value::ONE), (10, dummy()), (20, Value::Const(3.into()))]
+            )
+        );
+    }
+
+    #[test]
+    fn new_operations_fold() {
+        assert_eq!(
+            Value::from(2).pow(Value::from(3)).simplify(),
+            Value::Const(8.into())
+        );
+        assert_eq!(
+            Value::from(12).clamp(Value::ZERO, Value::TEN).simplify(),
+            Value::TEN
+        );
+        assert_eq!(Value::from(-3).sign().simplify(), Value::NEGATIVE_ONE);
+        assert_eq!(dummy().pow(Value::ONE).simplify(), dummy());
+    }
+
+    #[test]
+    fn undefined_pow_is_kept() {
+        let value = Value::ZERO.pow(Value::NEGATIVE_ONE);
+        assert_eq!(value.clone().simplify(), value);
+    }
+
+    #[test]
+    fn sum_and_product_combine_constants() {
+        assert_eq!(
+            Value::sum_all([Value::ONE, dummy(), Value::TWO]).simplify(),
+            Value::sum_all([dummy(), Value::Const(3.into())])
+        );
+        assert_eq!(Value::sum_all([Value::ZERO, dummy()]).simplify(), dummy());
+        assert_eq!(
+            Value::sum_all([Value::ONE, Value::TWO]).simplify(),
+            Value::Const(3.into())
+        );
+        assert_eq!(Value::sum_all([] as [Value; 0]).simplify(), Value::ZERO);
+        assert_eq!(
+            Value::product_all([Value::ONE, dummy()]).simplify(),
+            dummy()
+        );
+        assert_eq!(Value::product_all([] as [Value; 0]).simplify(), Value::ONE);
+    }
+
+    #[test]
+    fn dice_is_not_folded() {
+        let value = Value::dice(Value::ONE + Value::ONE, Value::from(6));
+        assert_eq!(value.simplify(), Value::dice(Value::TWO, Value::from(6)));
+    }
+
+    #[test]
+    fn double_not_collapses() {
+        let condition = Condition::has(Attribute::Debug(0));
+        assert_eq!((!!condition.clone()).simplify(), condition);
+        assert_eq!((!Condition::TRUE).simplify(), Condition::FALSE);
+    }
+
+    #[test]
+    fn logic_with_constants_collapses() {
+        let condition = Condition::has(Attribute::Debug(0));
+
+        assert_eq!(
+            (condition.clone() & Condition::FALSE).simplify(),
+            Condition::FALSE
+        );
+        assert_eq!((condition.clone() & Condition::TRUE).simplify(), condition);
+        assert_eq!(
+            (condition.clone() | Condition::TRUE).simplify(),
+            Condition::TRUE
+        );
+        assert_eq!((condition.clone() | Condition::FALSE).simplify(), condition);
+        assert_eq!((condition.clone() ^ Condition::FALSE).simplify(), condition);
+        assert_eq!((condition.clone() ^ Condition::TRUE).simplify(), !condition);
+    }
+
+    #[test]
+    fn lists_remove_constants() {
+        let a = Condition::has(Attribute::Debug(0));
+        let b = Condition::has(Attribute::Debug(1));
+
+        assert_eq!(
+            Condition::all([a.clone(), Condition::TRUE, b.clone()]).simplify(),
+            Condition::all([a.clone(), b.clone()])
+        );
+        assert_eq!(
+            Condition::all([a.clone(), Condition::FALSE]).simplify(),
+            Condition::FALSE
+        );
+        assert_eq!(
+            Condition::any([a.clone(), Condition::FALSE, b.clone()]).simplify(),
+            Condition::any([a.clone(), b])
+        );
+        assert_eq!(
+            Condition::any([a.clone(), Condition::TRUE]).simplify(),
+            Condition::TRUE
+        );
+        assert_eq!(Condition::all([a.clone()]).simplify(), a);
+        assert_eq!(Condition::all([]).simplify(), Condition::TRUE);
+        assert_eq!(Condition::any([]).simplify(), Condition::FALSE);
+    }
+
+    #[test]
+    fn at_least_simplifies() {
+        let a = Condition::has(Attribute::Debug(0));
+        let b = Condition::has(Attribute::Debug(1));
+        let c = Condition::has(Attribute::Debug(2));
+
+        assert_eq!(
+            Condition::at_least(2, [a.clone(), Condition::TRUE, b.clone()]).simplify(),
+            Condition::any([a.clone(), b.clone()])
+        );
+        assert_eq!(
+            Condition::at_least(2, [a.clone(), Condition::FALSE, b.clone()]).simplify(),
+            Condition::all([a.clone(), b.clone()])
+        );
+        assert_eq!(
+            Condition::at_least(3, [a.clone(), Condition::FALSE, b.clone()]).simplify(),
+            Condition::FALSE
+        );
+        assert_eq!(
+            Condition::at_least(2, [a.clone(), b.clone(), c.clone()]).simplify(),
+            Condition::at_least(2, [a, b, c])
+        );
+    }
+
+    fn failing() -> Condition {
+        (Value::ONE / Value::ZERO).greater_than(Value::ZERO)
+    }
+
+    #[test]
+    fn if_with_failing_condition_is_kept() {
+        let value = Value::condition(failing(), dummy(), dummy());
+        assert_eq!(value.clone().simplify(), value);
+    }
+
+    #[test]
+    fn logic_keeps_failing_conditions() {
+        assert_eq!(
+            (failing() & Condition::FALSE).simplify(),
+            failing() & Condition::FALSE
+        );
+        assert_eq!(
+            (failing() | Condition::TRUE).simplify(),
+            failing() | Condition::TRUE
+        );
+        assert_eq!((Condition::FALSE & failing()).simplify(), Condition::FALSE);
+        assert_eq!((Condition::TRUE | failing()).simplify(), Condition::TRUE);
+    }
+
+    #[test]
+    fn lists_keep_failing_conditions() {
+        let condition = Condition::all([failing(), Condition::FALSE]);
+        assert_eq!(condition.clone().simplify(), condition);
+
+        let condition = Condition::any([Condition::TRUE, failing()]);
+        assert_eq!(condition.clone().simplify(), condition);
+    }
+
+    #[test]
+    fn bonus_removes_true_condition() {
+        let bonus = Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            Value::ZERO + dummy(),
+            DebugValue(1),
+        )
+        .with_condition(Condition::GreaterThan(Value::TWO, Value::ONE));
+
+        let simplified = bonus.simplify();
+        assert_eq!(simplified.value(), &dummy());
+        assert_eq!(simplified.condition(), None);
+    }
+
+    #[test]
+    fn template_simplifies() {
+        let template = BonusTemplate::new(DebugValue(0), BonusType::Stacking, Value::ONE * dummy())
+            .with_condition(!!Condition::has(Attribute::Debug(1)));
+
+        let simplified = template.simplify();
+        assert_eq!(simplified.value(), &dummy());
+        assert_eq!(
+            simplified.condition(),
+            Some(&Condition::has(Attribute::Debug(1)))
+        );
+    }
+}
+
+mod condition_serialization {
+    use ron::{from_str, to_string};
+
+    use super::*;
+
+    #[test]
+    fn binary_conditions_deserialize() {
+        let condition: Condition =
+            from_str("a(b(true),o(b(false),b(true)))").expect("Could not deserialize condition");
+
+        assert_eq!(
+            condition,
+            Condition::TRUE & (Condition::FALSE | Condition::TRUE)
+        );
+    }
+
+    #[test]
+    fn lists_round_trip() {
+        let condition = Condition::at_least(
+            1,
+            [
+                Condition::all([Condition::TRUE, Condition::FALSE]),
+                Condition::any([Condition::FALSE]),
+            ],
+        );
+
+        let serialized = to_string(&condition).expect("Could not serialize condition");
+        let deserialized: Condition =
+            from_str(&serialized).expect("Could not deserialize condition");
+
+        assert_eq!(condition, deserialized);
+    }
+}
@@ -3,7 +3,7 @@
 use builder::{
     attribute::Attribute,
     bonus::{Bonus, BonusSource, BonusType, Condition, Value},
-    breakdowns::{Breakdowns, DiceStrategy},
+    breakdowns::{Breakdowns, DiceStrategy, Distribution},
     debug::DebugValue,
 };
 use core::{ops::Neg, str::FromStr};
@@ -53,6 +53,118 @@ mod value {
         );
     }
 
+    #[test]
+    fn pow() {
+        expect_value([dbg_bonus(0, Value::from(3).pow(Value::from(2)))], 9);
+        expect_value(
+            [dbg_bonus(0, Value::from(2).pow(Value::from(-1)))],
+            Decimal::new(5, 1),
+        );
+        expect_value([dbg_bonus(0, Value::from(-2).pow(Value::from(3)))], -8);
+        expect_value([dbg_bonus(0, Value::from(5).pow(Value::ZERO))], 1);
+    }
+
+    #[test]
+    fn pow_fractional() {
+        let breakdowns = Breakdowns::new();
+        let value = breakdowns.evaluate_value(&Value::from(16).pow(Value::try_from(0.5).unwrap()));
+        assert!(
+            (value - Decimal::from(4)).abs() < Decimal::new(1, 6),
+            "Found {value}"
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let clamp = |value: i32| Value::from(value).clamp(Value::from(-2), Value::from(5));
+
+        expect_value([dbg_bonus(0, clamp(-10))], -2);
+        expect_value([dbg_bonus(0, clamp(3))], 3);
+        expect_value([dbg_bonus(0, clamp(10))], 5);
+    }
+
+    #[test]
+    fn clamp_with_min_above_max_returns_max() {
+        expect_value(
+            [dbg_bonus(
+                0,
+                Value::from(3).clamp(Value::from(10), Value::from(5)),
+            )],
+            5,
+        );
+    }
+
+    #[test]
+    fn sign() {
+        expect_value([dbg_bonus(0, Value::from(-7).sign())], -1);
+        expect_value([dbg_bonus(0, Value::ZERO.sign())], 0);
+        expect_value([dbg_bonus(0, Value::try_from(0.25).unwrap().sign())], 1);
+    }
+
+    #[test]
+    fn sum() {
+        expect_value([dbg_bonus(0, Value::sum_all([1, 2, 3, 4]))], 10);
+        expect_value([dbg_bonus(0, Value::sum_all([] as [Value; 0]))], 0);
+    }
+
+    #[test]
+    fn product() {
+        expect_value([dbg_bonus(0, Value::product_all([2, 3, 4]))], 24);
+        expect_value([dbg_bonus(0, Value::product_all([] as [Value; 0]))], 1);
+    }
+
+    #[test]
+    fn multiplicative_stacking() {
+        let absorb = |percent: i64| Value::ONE - Value::Const(Decimal::new(percent, 2));
+        expect_value(
+            [dbg_bonus(
+                0,
+                Value::ONE - Value::product_all([absorb(50), absorb(20)]),
+            )],
+            Decimal::new(60, 2),
+        );
+    }
+
+    #[test]
+    fn table() {
+        let table = |key: i32| {
+            Value::table(
+                Value::from(key),
+                [
+                    (20, Value::from(3)),
+                    (1, Value::from(1)),
+                    (10, Value::from(2)),
+                ],
+            )
+        };
+
+        expect_value([dbg_bonus(0, table(0))], 0);
+        expect_value([dbg_bonus(0, table(1))], 1);
+        expect_value([dbg_bonus(0, table(9))], 1);
+        expect_value([dbg_bonus(0, table(10))], 2);
+        expect_value([dbg_bonus(0, table(25))], 3);
+    }
+
+    #[test]
+    fn table_uses_later_step_for_same_threshold() {
+        expect_value([dbg_bonus(0, Value::table(5, [(1, 1), (1, 2)]))], 2);
+    }
+
+    #[test]
+    fn table_only_evaluates_chosen_step() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(
+            0,
+            Value::table(5, [(0, Value::ONE), (10, Value::ONE / Value::ZERO)]),
+        ));
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Attribute::Debug(0)),
+            Decimal::ONE
+        );
+        assert_eq!(breakdowns.diagnostics().count(), 0);
+    }
+
     #[test]
     fn add() {
         expect_value([dbg_bonus(0, Value::from(1) + Value::from(2))], 3);
@@ -295,6 +407,58 @@ mod condition {
         test_condition(Condition::Constant(false), false);
         test_condition(Condition::Constant(true), true);
     }
+
+    #[test]
+    fn all() {
+        test_condition(Condition::all([]), true);
+        test_condition(Condition::all([Condition::TRUE, Condition::TRUE]), true);
+        test_condition(Condition::all([Condition::TRUE, Condition::FALSE]), false);
+        test_condition(Condition::all([Condition::FALSE, Condition::FALSE]), false);
+    }
+
+    #[test]
+    fn any() {
+        test_condition(Condition::any([]), false);
+        test_condition(Condition::any([Condition::FALSE, Condition::FALSE]), false);
+        test_condition(Condition::any([Condition::FALSE, Condition::TRUE]), true);
+        test_condition(Condition::any([Condition::TRUE, Condition::TRUE]), true);
+    }
+
+    #[test]
+    fn at_least() {
+        let conditions = [Condition::TRUE, Condition::FALSE, Condition::TRUE];
+
+        test_condition(Condition::at_least(0, []), true);
+        test_condition(Condition::at_least(1, []), false);
+        test_condition(Condition::at_least(1, conditions.clone()), true);
+        test_condition(Condition::at_least(2, conditions.clone()), true);
+        test_condition(Condition::at_least(3, conditions), false);
+    }
+
+    #[test]
+    fn lists_short_circuit() {
+        let failing = || (Value::ONE / Value::ZERO).greater_than(Value::ZERO);
+
+        for condition in [
+            Condition::all([Condition::FALSE, failing()]),
+            Condition::any([Condition::TRUE, failing()]),
+            Condition::at_least(1, [Condition::TRUE, failing()]),
+            Condition::at_least(2, [Condition::FALSE, Condition::FALSE, failing()]),
+        ] {
+            let mut breakdowns = Breakdowns::new();
+            breakdowns.insert_bonus(
+                Bonus::new(DebugValue(0), DebugValue(0), 10, DebugValue(0))
+                    .with_condition(condition.clone()),
+            );
+            breakdowns.evaluate_attribute_from(Attribute::from(DebugValue(0)));
+
+            assert_eq!(
+                breakdowns.diagnostics().count(),
+                0,
+                "Condition {condition} did not short circuit"
+            );
+        }
+    }
 }
 
 mod dynamic {
@@ -433,6 +597,8 @@ mod sources {
 }
 
 mod stacking {
+    use builder::{bonus::StackingPolicy, breakdowns::BonusEntry, types::dodge::Dodge};
+
     use super::*;
 
     #[test]
@@ -478,6 +644,320 @@ mod stacking {
             8,
         );
     }
+
+    #[test]
+    fn same_type_penalties_take_lowest() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), DebugValue(0), -1, DebugValue(0)),
+                Bonus::new(DebugValue(0), DebugValue(0), -3, DebugValue(0)),
+            ],
+            -3,
+        );
+    }
+
+    #[test]
+    fn same_type_bonus_and_penalty_both_apply() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), DebugValue(0), 5, DebugValue(0)),
+                Bonus::new(DebugValue(0), DebugValue(0), 2, DebugValue(0)),
+                Bonus::new(DebugValue(0), DebugValue(0), -1, DebugValue(0)),
+                Bonus::new(DebugValue(0), DebugValue(0), -3, DebugValue(0)),
+            ],
+            2,
+        );
+    }
+
+    #[test]
+    fn breakdown_includes_applied_penalty() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), DebugValue(0), 5, DebugValue(0)),
+            Bonus::new(DebugValue(0), DebugValue(0), -1, DebugValue(1)),
+            Bonus::new(DebugValue(0), DebugValue(0), -3, DebugValue(2)),
+        ]);
+        breakdowns.add_breakdown(DebugValue(0));
+
+        let breakdown = breakdowns
+            .breakdowns()
+            .get(&DebugValue(0).into())
+            .expect("Expected breakdown to be tracked");
+
+        assert_eq!(breakdown.value(), &Decimal::from(2));
+
+        let [entry] = &breakdown.bonuses()[..] else {
+            panic!("Expected one bonus type");
+        };
+
+        assert_eq!(
+            entry.applied().as_ref().map(BonusEntry::value),
+            Some(&Decimal::from(5))
+        );
+        assert_eq!(
+            entry.applied_penalty().as_ref().map(BonusEntry::value),
+            Some(&Decimal::from(-3))
+        );
+
+        let [overwritten] = &entry.overwritten()[..] else {
+            panic!("Expected one overwritten bonus");
+        };
+        assert_eq!(overwritten.value(), &Decimal::from(-1));
+    }
+
+    #[test]
+    fn dodge_stacks() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), BonusType::Dodge, 1, DebugValue(0)),
+                Bonus::new(DebugValue(0), BonusType::Dodge, 2, DebugValue(1)),
+                Bonus::new(DebugValue(0), BonusType::Dodge, 3, DebugValue(2)),
+            ],
+            6,
+        );
+    }
+
+    #[test]
+    fn dodge_is_capped_by_stacking_policy() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Dodge, 1, DebugValue(0)),
+            Bonus::new(DebugValue(0), BonusType::Dodge, 100, DebugValue(1)),
+            Bonus::new(DebugValue(0), BonusType::Stacking, 100, DebugValue(2)),
+        ]);
+
+        let cap = breakdowns.evaluate_value(&Dodge::cap());
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(DebugValue(0)),
+            cap + Decimal::from(100)
+        );
+    }
+
+    #[test]
+    fn dodge_is_capped_by_attribute() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(Dodge::Bonus, BonusType::Dodge, 1, DebugValue(0)),
+            Bonus::new(Dodge::Bonus, BonusType::Dodge, 2, DebugValue(1)),
+        ]);
+        assert_eq!(breakdowns.evaluate_attribute_from(Dodge::Bonus), 3.into());
+
+        breakdowns.insert_bonus(Bonus::new(
+            Dodge::Bonus,
+            BonusType::Dodge,
+            100,
+            DebugValue(2),
+        ));
+        let cap = breakdowns.evaluate_value(&Dodge::cap());
+        assert_eq!(breakdowns.evaluate_attribute_from(Dodge::Bonus), cap);
+
+        breakdowns.insert_bonus(Bonus::new(
+            Dodge::Cap,
+            BonusType::Stacking,
+            5,
+            DebugValue(3),
+        ));
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Dodge::Bonus),
+            cap + Decimal::from(5)
+        );
+    }
+
+    #[test]
+    fn breakdown_includes_stacked_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Dodge, 2, DebugValue(0)),
+            Bonus::new(DebugValue(0), BonusType::Dodge, 3, DebugValue(0)),
+            Bonus::new(DebugValue(0), BonusType::Dodge, 1, DebugValue(1)),
+        ]);
+        breakdowns.add_breakdown(DebugValue(0));
+
+        let breakdown = breakdowns
+            .breakdowns()
+            .get(&DebugValue(0).into())
+            .expect("Expected breakdown to be tracked");
+
+        let [entry] = &breakdown.bonuses()[..] else {
+            panic!("Expected one bonus type");
+        };
+
+        assert_eq!(
+            entry.stacking_policy(),
+            &StackingPolicy::SumCapped(Dodge::cap())
+        );
+        assert_eq!(entry.value(), &Decimal::from(6));
+        assert_eq!(entry.stacked().len(), 3);
+        assert!(entry.overwritten().is_empty());
+    }
+}
+
+mod named_effects {
+    use super::*;
+
+    #[test]
+    fn same_name_does_not_stack() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), BonusType::Insightful, 3, DebugValue(0))
+                    .with_name("Effect"),
+                Bonus::new(DebugValue(0), BonusType::Quality, 2, DebugValue(1)).with_name("Effect"),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn same_name_does_not_stack_with_stacking() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), BonusType::Stacking, 3, DebugValue(0))
+                    .with_name("Effect"),
+                Bonus::new(DebugValue(0), BonusType::Stacking, 3, DebugValue(1))
+                    .with_name("Effect"),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn different_names_stack() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), BonusType::Insightful, 3, DebugValue(0))
+                    .with_name("Effect"),
+                Bonus::new(DebugValue(0), BonusType::Quality, 2, DebugValue(1))
+                    .with_name("Other Effect"),
+            ],
+            5,
+        );
+    }
+
+    #[test]
+    fn disabled_bonus_does_not_suppress() {
+        expect_value(
+            [
+                Bonus::new(DebugValue(0), BonusType::Insightful, 5, DebugValue(0))
+                    .with_name("Effect")
+                    .with_condition(Condition::FALSE),
+                Bonus::new(DebugValue(0), BonusType::Quality, 2, DebugValue(1)).with_name("Effect"),
+            ],
+            2,
+        );
+    }
+
+    #[test]
+    fn breakdown_includes_suppressed() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Insightful, 3, DebugValue(0)).with_name("Effect"),
+            Bonus::new(DebugValue(0), BonusType::Quality, 2, DebugValue(1)).with_name("Effect"),
+        ]);
+        breakdowns.add_breakdown(DebugValue(0));
+
+        let breakdown = breakdowns
+            .breakdowns()
+            .get(&DebugValue(0).into())
+            .expect("Expected breakdown to be tracked");
+
+        assert_eq!(breakdown.value(), &Decimal::from(3));
+
+        let [suppressed] = &breakdown.suppressed()[..] else {
+            panic!("Expected one suppressed bonus");
+        };
+        assert_eq!(suppressed.bonus().source(), &BonusSource::Debug(1));
+    }
+}
+
+mod limits {
+    use builder::types::{
+        absorption::{Absorption, AbsorptionSource},
+        damage_type::DamageType,
+        sheltering::Sheltering,
+    };
+
+    use super::*;
+
+    #[test]
+    fn maximum_caps_value() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            Sheltering::Magical,
+            BonusType::Stacking,
+            1000,
+            DebugValue(0),
+        ));
+
+        let cap = breakdowns.evaluate_attribute_from(Sheltering::MagicalCap);
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Sheltering::MagicalTotal),
+            cap
+        );
+    }
+
+    #[test]
+    fn maximum_updates_with_dependency() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            Sheltering::Magical,
+            BonusType::Stacking,
+            1000,
+            DebugValue(0),
+        ));
+        let cap = breakdowns.evaluate_attribute_from(Sheltering::MagicalCap);
+
+        breakdowns.insert_bonus(Bonus::new(
+            Sheltering::MagicalCap,
+            BonusType::Stacking,
+            10,
+            DebugValue(1),
+        ));
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Sheltering::MagicalTotal),
+            cap + Decimal::from(10)
+        );
+    }
+
+    #[test]
+    fn minimum_floors_value() {
+        let absorption = Absorption::Bonus(DamageType::Fire, AbsorptionSource::Guild);
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            absorption,
+            BonusType::Stacking,
+            -1,
+            DebugValue(0),
+        ));
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Absorption::Total(DamageType::Fire)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn breakdown_includes_uncapped_value() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            Sheltering::Magical,
+            BonusType::Stacking,
+            1000,
+            DebugValue(0),
+        ));
+        let cap = breakdowns.evaluate_attribute_from(Sheltering::MagicalCap);
+        breakdowns.add_breakdown(Sheltering::MagicalTotal);
+
+        let breakdown = breakdowns
+            .breakdowns()
+            .get(&Sheltering::MagicalTotal.into())
+            .expect("Expected breakdown to be tracked");
+
+        assert_eq!(breakdown.value(), &cap);
+        assert_eq!(breakdown.uncapped_value(), &Decimal::from(1000));
+        assert_eq!(breakdown.maximum(), Some(&cap));
+        assert_eq!(breakdown.lost_to_limits(), Decimal::from(1000) - cap);
+    }
 }
 
 mod breakdowns {
@@ -588,6 +1068,10 @@ mod breakdowns {
 }
 
 mod dice_strategy {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use builder::breakdowns::{EvalError, MAX_SAMPLES};
 
     use super::*;
 
@@ -623,4 +1107,1486 @@ mod dice_strategy {
             Decimal::from_str("6").unwrap()
         );
     }
+
+    fn sampled(seed: u64, value: Value) -> Breakdowns {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.set_dice_strategy(DiceStrategy::Sampled {
+            seed,
+            samples: 2000,
+        });
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            DebugValue(0),
+            value,
+            DebugValue(0),
+        ));
+        breakdowns
+    }
+
+    #[test]
+    fn sampled_is_reproducible() {
+        let a = sampled(42, Value::dice(2, 8));
+        let b = sampled(42, Value::dice(2, 8));
+
+        assert_eq!(
+            a.evaluate_attribute_from(DebugValue(0)),
+            b.evaluate_attribute_from(DebugValue(0))
+        );
+        assert_eq!(
+            a.sample_statistics(&DebugValue(0).into()),
+            b.sample_statistics(&DebugValue(0).into())
+        );
+    }
+
+    #[test]
+    fn sampled_seeds_roll_differently() {
+        let a = sampled(1, Value::dice(2, 8));
+        let b = sampled(2, Value::dice(2, 8));
+
+        assert_ne!(
+            a.sample_statistics(&DebugValue(0).into()),
+            b.sample_statistics(&DebugValue(0).into())
+        );
+    }
+
+    #[test]
+    fn sampled_mean_is_close_to_average() {
+        let breakdowns = sampled(7, Value::dice(1, 6));
+        let value = breakdowns.evaluate_attribute_from(DebugValue(0));
+
+        assert!(
+            (value - Decimal::from_str("3.5").unwrap()).abs() < Decimal::from_str("0.2").unwrap(),
+            "Found {value}"
+        );
+
+        let statistics = breakdowns.sample_statistics(&DebugValue(0).into()).unwrap();
+        assert_eq!(statistics.samples(), 2000);
+        assert_eq!(statistics.mean(), value);
+        assert_eq!(statistics.min(), Decimal::ONE);
+        assert_eq!(statistics.max(), Decimal::from(6));
+        assert!(statistics.standard_deviation() > Decimal::ONE);
+    }
+
+    #[test]
+    fn sampled_too_many_dice_is_error() {
+        let value = Value::dice(Value::Const(Decimal::from(1_000_000_000_000_u64)), 6);
+        let start = Instant::now();
+        let breakdowns = sampled(7, value.clone());
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(DebugValue(0)),
+            Decimal::ZERO
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(breakdowns
+            .diagnostics()
+            .any(|diagnostic| diagnostic.error() == &EvalError::TooManyDice(value.clone())));
+    }
+
+    #[test]
+    fn sampled_samples_are_limited() {
+        let mut breakdowns = sampled(7, Value::dice(1, 6));
+        breakdowns.set_dice_strategy(DiceStrategy::Sampled {
+            seed: 7,
+            samples: u32::MAX,
+        });
+        breakdowns.evaluate_attribute_from(DebugValue(0));
+
+        let statistics = breakdowns.sample_statistics(&DebugValue(0).into()).unwrap();
+        assert_eq!(statistics.samples(), MAX_SAMPLES as usize);
+    }
+
+    #[test]
+    fn sampled_rolls_dice_before_nonlinear_operations() {
+        let value = (Value::dice(1, 6) / Value::TWO).floor();
+        let mut breakdowns = sampled(7, value);
+        let sampled = breakdowns.evaluate_attribute_from(DebugValue(0));
+
+        assert!(
+            (sampled - Decimal::from_str("1.5").unwrap()).abs() < Decimal::from_str("0.2").unwrap(),
+            "Found {sampled}"
+        );
+
+        breakdowns.set_dice_strategy(DiceStrategy::Average);
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(DebugValue(0)),
+            Decimal::ONE
+        );
+        assert_eq!(breakdowns.sample_statistics(&DebugValue(0).into()), None);
+    }
+
+    #[test]
+    fn attributes_without_dice_are_not_sampled() {
+        let breakdowns = sampled(7, Value::from(5));
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(DebugValue(0)),
+            Decimal::from(5)
+        );
+        assert_eq!(breakdowns.sample_statistics(&DebugValue(0).into()), None);
+    }
+}
+
+mod distribution {
+    use builder::breakdowns::EvalError;
+
+    use super::*;
+
+    /// Asserts that two values are equal, ignoring rounding in the last few decimal places
+    fn assert_close(value: Decimal, expected: Decimal) {
+        assert!(
+            (value - expected).abs() < Decimal::new(1, 10),
+            "Expected {expected}, found {value}"
+        );
+    }
+
+    fn distribution(value: &Value) -> Distribution {
+        Breakdowns::new().evaluate_distribution(value).unwrap()
+    }
+
+    #[test]
+    fn constant_value() {
+        let distribution = distribution(&(Value::from(3) + Value::TWO));
+        assert_eq!(distribution.outcomes(), &[(Decimal::from(5), Decimal::ONE)]);
+        assert_eq!(distribution.variance(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn single_die() {
+        let distribution = distribution(&Value::dice(1, 6));
+
+        assert_eq!(distribution.outcomes().len(), 6);
+        assert_eq!(distribution.min(), Decimal::ONE);
+        assert_eq!(distribution.max(), Decimal::from(6));
+        assert_close(distribution.mean(), Decimal::new(35, 1));
+        assert_close(
+            distribution.variance(),
+            Decimal::from(35) / Decimal::from(12),
+        );
+    }
+
+    #[test]
+    fn multiple_dice() {
+        let distribution = distribution(&Value::dice(2, 6));
+
+        assert_eq!(distribution.outcomes().len(), 11);
+        assert_close(distribution.mean(), Decimal::from(7));
+        assert_close(
+            distribution.probability_at_least(Decimal::from(12)),
+            Decimal::ONE / Decimal::from(36),
+        );
+        assert_close(
+            distribution.probability_at_least(Decimal::from(7)),
+            Decimal::from(21) / Decimal::from(36),
+        );
+    }
+
+    #[test]
+    fn percentiles() {
+        let distribution = distribution(&Value::dice(1, 20));
+
+        assert_eq!(distribution.percentile(Decimal::new(1, 1)), Decimal::TWO);
+        assert_eq!(distribution.percentile(Decimal::new(5, 1)), Decimal::TEN);
+        assert_eq!(distribution.percentile(Decimal::ONE), Decimal::from(20));
+    }
+
+    #[test]
+    fn dice_pass_through_operations() {
+        let distribution = distribution(&(Value::dice(1, 4) * Value::TWO + Value::dice(1, 4)));
+
+        assert_eq!(distribution.min(), Decimal::from(3));
+        assert_eq!(distribution.max(), Decimal::from(12));
+        assert_close(distribution.mean(), Decimal::new(75, 1));
+    }
+
+    #[test]
+    fn nonlinear_operations_use_every_roll() {
+        let value = (Value::dice(1, 6) / Value::TWO).floor();
+        let breakdowns = Breakdowns::new();
+
+        assert_close(
+            breakdowns.evaluate_distribution(&value).unwrap().mean(),
+            Decimal::new(15, 1),
+        );
+        assert_eq!(breakdowns.evaluate_value(&value), Decimal::ONE);
+    }
+
+    #[test]
+    fn table_with_dice_key() {
+        let value = Value::table(Value::dice(1, 4), [(1, 10), (3, 20)]);
+        let distribution = distribution(&value);
+
+        assert_eq!(
+            distribution.outcomes(),
+            &[
+                (Decimal::TEN, Decimal::new(5, 1)),
+                (Decimal::from(20), Decimal::new(5, 1))
+            ]
+        );
+    }
+
+    #[test]
+    fn large_distributions_are_compressed() {
+        let distribution = distribution(&Value::dice(100, 20));
+
+        assert!(distribution.outcomes().len() <= 256);
+        assert_close(distribution.mean(), Decimal::from(1050));
+        assert!(distribution.min() >= Decimal::from(100));
+        assert!(distribution.max() <= Decimal::from(2000));
+    }
+
+    #[test]
+    fn large_dice_are_compressed() {
+        let distribution = distribution(&Value::dice(1, 10000));
+
+        assert!(distribution.outcomes().len() <= 256);
+        assert_close(distribution.mean(), Decimal::new(50005, 1));
+    }
+
+    #[test]
+    fn division_by_possible_zero() {
+        let value = Value::ONE / (Value::dice(1, 6) - Value::ONE);
+        let denominator = Value::dice(1, 6) - Value::ONE;
+
+        assert_eq!(
+            Breakdowns::new().evaluate_distribution(&value),
+            Err(EvalError::DivisionByZero(Value::ONE / denominator))
+        );
+    }
+
+    #[test]
+    fn fractional_dice_count_is_undefined() {
+        let value = Value::dice(Value::try_from(1.5).unwrap(), Value::from(6));
+
+        assert_eq!(
+            Breakdowns::new().evaluate_distribution(&value),
+            Err(EvalError::Undefined(value.clone()))
+        );
+    }
+
+    #[test]
+    fn largest_die_does_not_overflow() {
+        let distribution = distribution(&Value::dice(1, Decimal::MAX));
+
+        assert!(distribution.outcomes().len() <= 256);
+        assert!(distribution.min() >= Decimal::ONE);
+        assert!(distribution.max() <= Decimal::MAX);
+    }
+
+    #[test]
+    fn adding_largest_dice_is_overflow() {
+        let value = Value::dice(2, Decimal::MAX);
+
+        assert_eq!(
+            Breakdowns::new().evaluate_distribution(&value),
+            Err(EvalError::Overflow(value.clone()))
+        );
+    }
+}
+
+mod cycles {
+    use super::*;
+
+    fn dbg_bonus(attribute: usize, value: Value, source: usize) -> Bonus {
+        Bonus::new(
+            Attribute::Debug(attribute),
+            BonusType::Stacking,
+            value,
+            BonusSource::Debug(source),
+        )
+    }
+
+    #[test]
+    fn base_bonuses_have_no_cycles() {
+        let breakdowns = Breakdowns::new();
+        assert!(
+            breakdowns.cycle_errors().is_empty(),
+            "Found cycles: {:?}",
+            breakdowns.cycle_errors()
+        );
+    }
+
+    #[test]
+    fn self_dependency_is_quarantined() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(0)) + 1.into(), 0));
+
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(0)), 0.into());
+
+        let [error] = breakdowns.cycle_errors().as_slice() else {
+            panic!("Expected a single cycle error");
+        };
+        assert_eq!(error.attributes(), &vec![Attribute::Debug(0)]);
+        assert_eq!(error.sources(), &vec![BonusSource::Debug(0)]);
+    }
+
+    #[test]
+    fn loop_is_quarantined() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, Value::from(Attribute::Debug(1)) + 1.into(), 0),
+            dbg_bonus(1, Value::from(Attribute::Debug(0)) + 1.into(), 1),
+        ]);
+
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(0)), 1.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(1)), 0.into());
+
+        let [error] = breakdowns.cycle_errors().as_slice() else {
+            panic!("Expected a single cycle error");
+        };
+        assert_eq!(
+            error.attributes(),
+            &vec![Attribute::Debug(1), Attribute::Debug(0)]
+        );
+        assert_eq!(
+            error.sources(),
+            &vec![BonusSource::Debug(1), BonusSource::Debug(0)]
+        );
+    }
+
+    #[test]
+    fn loop_through_conditions_is_quarantined() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, 1.into(), 0).with_condition(Condition::has(Attribute::Debug(1))),
+            dbg_bonus(1, 1.into(), 1).with_condition(Condition::has(Attribute::Debug(2))),
+            dbg_bonus(2, 1.into(), 2).with_condition(!Condition::has(Attribute::Debug(0))),
+        ]);
+
+        assert_eq!(breakdowns.cycle_errors().len(), 1, "Expected a cycle error");
+    }
+
+    #[test]
+    fn removing_source_clears_error() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(0)), 0));
+        assert_eq!(breakdowns.cycle_errors().len(), 1, "Expected a cycle error");
+
+        breakdowns.remove_source(BonusSource::Debug(0));
+        assert!(breakdowns.cycle_errors().is_empty(), "Expected no errors");
+    }
+
+    #[test]
+    fn quarantined_bonus_applies_when_loop_is_broken() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, Value::from(Attribute::Debug(1)) + 1.into(), 0),
+            dbg_bonus(1, Value::from(Attribute::Debug(0)) + 1.into(), 1),
+        ]);
+
+        breakdowns.remove_source(BonusSource::Debug(0));
+
+        assert!(breakdowns.cycle_errors().is_empty(), "Expected no errors");
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(0)), 0.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(1)), 1.into());
+    }
+
+    #[test]
+    fn quarantined_bonus_applies_when_source_is_replaced() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            dbg_bonus(0, Value::from(Attribute::Debug(1)) + 1.into(), 0),
+            dbg_bonus(1, Value::from(Attribute::Debug(0)) + 1.into(), 1),
+        ]);
+        assert_eq!(breakdowns.cycle_errors().len(), 1, "Expected a cycle error");
+
+        breakdowns.insert_bonus(dbg_bonus(0, 5.into(), 0));
+
+        assert!(breakdowns.cycle_errors().is_empty(), "Expected no errors");
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(0)), 5.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(1)), 6.into());
+    }
+
+    #[test]
+    fn try_insert_refuses_loop() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(1)), 0));
+
+        let result = breakdowns.try_insert_bonuses([
+            dbg_bonus(2, 5.into(), 1),
+            dbg_bonus(1, Value::from(Attribute::Debug(0)), 1),
+        ]);
+
+        let error = result.expect_err("Expected a cycle error");
+        assert_eq!(
+            error.attributes(),
+            &vec![Attribute::Debug(1), Attribute::Debug(0)]
+        );
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(2)), 0.into());
+        assert!(breakdowns.cycle_errors().is_empty(), "Expected no errors");
+    }
+
+    #[test]
+    fn try_insert_ignores_replaced_sources() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(0, Value::from(Attribute::Debug(1)), 0));
+
+        breakdowns
+            .try_insert_bonuses([dbg_bonus(1, Value::from(Attribute::Debug(0)), 0)])
+            .expect("Expected bonuses to be inserted");
+
+        assert!(breakdowns.cycle_errors().is_empty(), "Expected no errors");
+    }
+}
+
+mod diagnostics {
+    use builder::breakdowns::{DiagnosticOrigin, EvalError};
+
+    use super::*;
+
+    fn dbg_bonus(value: Value, source: usize) -> Bonus {
+        Bonus::new(
+            Attribute::Debug(0),
+            BonusType::Stacking,
+            value,
+            BonusSource::Debug(source),
+        )
+    }
+
+    #[test]
+    fn division_by_zero_falls_back_to_zero() {
+        expect_value(
+            [
+                dbg_bonus(Value::from(1) / Value::ZERO, 0),
+                dbg_bonus(Value::from(3), 1),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn remainder_by_zero_falls_back_to_zero() {
+        expect_value(
+            [
+                dbg_bonus(Value::from(1) % Value::ZERO, 0),
+                dbg_bonus(Value::from(3), 1),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn overflow_falls_back_to_zero() {
+        expect_value(
+            [
+                dbg_bonus(Value::MAX * Value::TWO, 0),
+                dbg_bonus(Value::from(3), 1),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn failing_condition_disables_bonus() {
+        expect_value(
+            [
+                dbg_bonus(Value::from(5), 0)
+                    .with_condition((Value::ONE / Value::ZERO).greater_than(Value::ZERO)),
+                dbg_bonus(Value::from(3), 1),
+            ],
+            3,
+        );
+    }
+
+    #[test]
+    fn diagnostic_records_bonus() {
+        let mut breakdowns = Breakdowns::new();
+        let value = Value::from(1) / Value::ZERO;
+        breakdowns.insert_bonus(dbg_bonus(value.clone(), 0));
+
+        let diagnostics = breakdowns.diagnostics().collect::<Vec<_>>();
+        let [diagnostic] = diagnostics.as_slice() else {
+            panic!("Expected a single diagnostic, found {diagnostics:?}");
+        };
+
+        assert_eq!(diagnostic.attribute(), &Attribute::Debug(0));
+        assert_eq!(diagnostic.source(), Some(&BonusSource::Debug(0)));
+        assert_eq!(
+            diagnostic.origin(),
+            &DiagnosticOrigin::Bonus(BonusSource::Debug(0))
+        );
+        assert_eq!(diagnostic.error(), &EvalError::DivisionByZero(value));
+    }
+
+    #[test]
+    fn pow_of_zero_to_negative_is_division_by_zero() {
+        let breakdowns = Breakdowns::new();
+        let value = Value::ZERO.pow(Value::NEGATIVE_ONE);
+
+        assert_eq!(
+            breakdowns.try_evaluate_value(&value),
+            Err(EvalError::DivisionByZero(value.clone()))
+        );
+    }
+
+    #[test]
+    fn fractional_pow_of_negative_is_undefined() {
+        let breakdowns = Breakdowns::new();
+        let value = Value::from(-4).pow(Value::try_from(0.5).unwrap());
+
+        assert_eq!(
+            breakdowns.try_evaluate_value(&value),
+            Err(EvalError::Undefined(value.clone()))
+        );
+    }
+
+    #[test]
+    fn pow_underflow_is_zero() {
+        let breakdowns = Breakdowns::new();
+
+        let value = Value::from(10).pow(Value::from(-30));
+        assert_eq!(breakdowns.try_evaluate_value(&value), Ok(Decimal::ZERO));
+
+        let value = Value::try_from(0.1).unwrap().pow(Value::from(30));
+        assert_eq!(breakdowns.try_evaluate_value(&value), Ok(Decimal::ZERO));
+    }
+
+    #[test]
+    fn pow_overflow() {
+        let breakdowns = Breakdowns::new();
+        let value = Value::from(10).pow(Value::from(30));
+
+        assert_eq!(
+            breakdowns.try_evaluate_value(&value),
+            Err(EvalError::Overflow(value.clone()))
+        );
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let breakdowns = Breakdowns::new();
+        let value = Value::sum_all([Value::MAX, Value::ONE]);
+
+        assert_eq!(
+            breakdowns.try_evaluate_value(&value),
+            Err(EvalError::Overflow(value.clone()))
+        );
+    }
+
+    #[test]
+    fn removing_bonus_clears_diagnostic() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(dbg_bonus(Value::from(1) / Value::ZERO, 0));
+        breakdowns.remove_source(BonusSource::Debug(0));
+
+        assert_eq!(breakdowns.diagnostics().count(), 0);
+    }
+
+    #[test]
+    fn base_bonuses_have_no_diagnostics() {
+        let breakdowns = Breakdowns::new();
+        assert_eq!(breakdowns.diagnostics().count(), 0);
+    }
+
+    #[test]
+    fn try_evaluate_value_returns_error() {
+        let breakdowns = Breakdowns::new();
+        let value = Value::MAX + Value::ONE;
+
+        assert_eq!(
+            breakdowns.try_evaluate_value(&value),
+            Err(EvalError::Overflow(value.clone()))
+        );
+        assert_eq!(breakdowns.evaluate_value(&value), Decimal::ZERO);
+    }
+}
+
+mod explain {
+    use builder::{
+        breakdowns::{ConditionDetail, ConditionExplanation, ValueDetail, ValueExplanation},
+        types::{ability::Ability, saving_throw::SavingThrow},
+    };
+
+    use super::*;
+
+    #[test]
+    fn explains_value() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+            Bonus::new(DebugValue(0), DebugValue(0), 3, DebugValue(0)),
+        ]);
+
+        let explanation = breakdowns.explain(&Attribute::Debug(0));
+
+        assert_eq!(explanation.attribute(), &Attribute::Debug(0));
+        assert_eq!(explanation.value(), &Decimal::from(8));
+        assert_eq!(explanation.bonuses().len(), 2);
+    }
+
+    #[test]
+    fn explains_referenced_attributes() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                Value::from(Attribute::Debug(1)) + Value::ONE,
+                DebugValue(0),
+            ),
+            Bonus::new(DebugValue(1), BonusType::Stacking, 4, DebugValue(0)),
+        ]);
+
+        let explanation = breakdowns.explain(&Attribute::Debug(0));
+        let [bonus] = explanation.bonuses().as_slice() else {
+            panic!("Expected a single bonus");
+        };
+
+        assert_eq!(bonus.value().result(), &Decimal::from(5));
+
+        let ValueDetail::Operands(operands) = bonus.value().detail() else {
+            panic!("Expected operands");
+        };
+        let Some(ValueDetail::Attribute(inner)) = operands.first().map(ValueExplanation::detail)
+        else {
+            panic!("Expected an attribute");
+        };
+
+        assert_eq!(inner.attribute(), &Attribute::Debug(1));
+        assert_eq!(inner.value(), &Decimal::from(4));
+    }
+
+    #[test]
+    fn explains_conditions() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0))
+                .with_condition(Condition::has(Attribute::Debug(1)) | Condition::TRUE),
+        );
+
+        let explanation = breakdowns.explain(&Attribute::Debug(0));
+        let [bonus] = explanation.bonuses().as_slice() else {
+            panic!("Expected a single bonus");
+        };
+        let condition = bonus.condition().expect("Expected a condition");
+
+        assert!(condition.result(), "Expected condition to be true");
+
+        let ConditionDetail::Conditions(conditions) = condition.detail() else {
+            panic!("Expected inner conditions");
+        };
+        assert_eq!(
+            conditions
+                .iter()
+                .map(ConditionExplanation::result)
+                .collect::<Vec<_>>(),
+            vec![false, true]
+        );
+    }
+
+    #[test]
+    fn explains_dice_strategy() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.set_dice_strategy(DiceStrategy::Maximum);
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            Value::dice(2, 6),
+            DebugValue(0),
+        ));
+
+        let explanation = breakdowns.explain(&Attribute::Debug(0));
+        let [bonus] = explanation.bonuses().as_slice() else {
+            panic!("Expected a single bonus");
+        };
+
+        assert_eq!(bonus.value().result(), &Decimal::from(12));
+        assert!(
+            matches!(
+                bonus.value().detail(),
+                ValueDetail::Dice {
+                    strategy: DiceStrategy::Maximum,
+                    ..
+                }
+            ),
+            "Expected dice with the maximum strategy"
+        );
+    }
+
+    #[test]
+    fn shared_attributes_are_written_once() {
+        let mut breakdowns = Breakdowns::new();
+        let bonus = |attribute: usize, value: Value, source: usize| {
+            Bonus::new(
+                Attribute::Debug(attribute),
+                BonusType::Stacking,
+                value,
+                BonusSource::Debug(source),
+            )
+        };
+        breakdowns.insert_bonuses([
+            bonus(
+                0,
+                Value::from(Attribute::Debug(1)) + Value::from(Attribute::Debug(2)),
+                0,
+            ),
+            bonus(1, Value::from(Attribute::Debug(3)), 1),
+            bonus(2, Value::from(Attribute::Debug(3)), 2),
+            bonus(3, Value::from(5), 3),
+        ]);
+
+        let display = breakdowns.explain(&Attribute::Debug(0)).to_string();
+        let shared = format!("bonus from {}", BonusSource::Debug(3));
+
+        assert_eq!(display.matches(&shared).count(), 1, "{display}");
+        assert_eq!(display.matches("(see above)").count(), 1, "{display}");
+    }
+
+    #[test]
+    fn explains_base_bonuses() {
+        let breakdowns = Breakdowns::new();
+        let explanation = breakdowns.explain(&Attribute::SavingThrow(SavingThrow::Reflex));
+        let display = explanation.to_string();
+
+        assert!(
+            display.contains(&Attribute::Ability(Ability::Dexterity).to_string()),
+            "Expected explanation to include Dexterity:\n{display}"
+        );
+    }
+}
+
+mod overlay {
+    use builder::{bonus::BonusTemplate, types::ability::Ability};
+
+    use super::*;
+
+    #[test]
+    fn overlay_includes_bonuses() {
+        let breakdowns = Breakdowns::new();
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                5,
+                DebugValue(0),
+            )],
+            [],
+        );
+
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(0)), 5.into());
+    }
+
+    #[test]
+    fn overlay_removes_sources() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+            Bonus::new(DebugValue(0), BonusType::Stacking, 3, DebugValue(1)),
+        ]);
+
+        let overlay = breakdowns.with_overlay([], [BonusSource::Debug(0)]);
+
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(0)), 3.into());
+    }
+
+    #[test]
+    fn overlay_does_not_modify_base() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            5,
+            DebugValue(0),
+        ));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                2,
+                DebugValue(1),
+            )],
+            [BonusSource::Debug(0)],
+        );
+
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(0)), 2.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(0)), 5.into());
+    }
+
+    #[test]
+    fn overlay_updates_dependants() {
+        let breakdowns = Breakdowns::new();
+        let before =
+            breakdowns.evaluate_attribute_from(Attribute::AbilityModifier(Ability::Strength));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                Ability::Strength,
+                BonusType::Stacking,
+                4,
+                DebugValue(0),
+            )],
+            [],
+        );
+
+        let after = overlay.evaluate_attribute_from(Attribute::AbilityModifier(Ability::Strength));
+
+        assert_eq!(after - before, 2.into());
+    }
+
+    #[test]
+    fn overlay_applies_dynamic_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.import_dynamic_bonus(
+            Attribute::Debug(0),
+            vec![BonusTemplate::new(DebugValue(1), DebugValue(1), 10)],
+        );
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(DebugValue(0), DebugValue(0), 1, DebugValue(0))],
+            [],
+        );
+
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(1)), 10.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(1)), 0.into());
+    }
+
+    #[test]
+    fn overlay_removes_dynamic_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.import_dynamic_bonus(
+            Attribute::Debug(0),
+            vec![BonusTemplate::new(DebugValue(1), DebugValue(1), 10)],
+        );
+        breakdowns.insert_bonus(Bonus::new(DebugValue(0), DebugValue(0), 1, DebugValue(0)));
+
+        let overlay = breakdowns.with_overlay([], [BonusSource::Debug(0)]);
+
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(1)), 0.into());
+        assert_eq!(breakdowns.evaluate_attribute_from(DebugValue(1)), 10.into());
+    }
+
+    #[test]
+    fn overlay_reads_unaffected_attributes_from_base() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+            Bonus::new(DebugValue(1), BonusType::Stacking, 3, DebugValue(1)),
+        ]);
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                2,
+                DebugValue(2),
+            )],
+            [],
+        );
+
+        assert!(overlay.is_affected(&Attribute::Debug(0)));
+        assert!(!overlay.is_affected(&Attribute::Debug(1)));
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(1)), 3.into());
+    }
+
+    #[test]
+    fn overlay_rejects_cycles() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            Value::Attribute(Attribute::Debug(1)),
+            DebugValue(0),
+        ));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(1),
+                BonusType::Stacking,
+                Value::Attribute(Attribute::Debug(0)),
+                DebugValue(1),
+            )],
+            [],
+        );
+
+        assert_eq!(overlay.cycle_errors().len(), 1);
+        assert_eq!(overlay.evaluate_attribute_from(DebugValue(0)), 0.into());
+    }
+}
+
+mod diff {
+    use builder::breakdowns::{BonusState, BonusStatus};
+
+    use super::*;
+
+    #[test]
+    fn unchanged_breakdowns_have_empty_diff() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            5,
+            DebugValue(0),
+        ));
+        let other = breakdowns.clone();
+
+        assert!(
+            breakdowns.diff(&other).is_empty(),
+            "Expected no attributes to change"
+        );
+    }
+
+    #[test]
+    fn diff_includes_value_and_delta() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            5,
+            DebugValue(0),
+        ));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                3,
+                DebugValue(1),
+            )],
+            [],
+        );
+
+        let diff = overlay.diff();
+        let attribute = diff
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+
+        assert_eq!(attribute.before(), &Decimal::from(5));
+        assert_eq!(attribute.after(), &Decimal::from(8));
+        assert_eq!(attribute.delta(), Decimal::from(3));
+    }
+
+    #[test]
+    fn diff_lists_added_and_removed_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            5,
+            DebugValue(0),
+        ));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                3,
+                DebugValue(1),
+            )],
+            [BonusSource::Debug(0)],
+        );
+
+        let diff = overlay.diff();
+        let attribute = diff
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+
+        let [added] = attribute.added().collect::<Vec<_>>()[..] else {
+            panic!("Expected one added bonus");
+        };
+        assert_eq!(added.bonus().source(), &BonusSource::Debug(1));
+
+        let [removed] = attribute.removed().collect::<Vec<_>>()[..] else {
+            panic!("Expected one removed bonus");
+        };
+        assert_eq!(removed.bonus().source(), &BonusSource::Debug(0));
+
+        assert_eq!(attribute.changed().count(), 0);
+    }
+
+    #[test]
+    fn diff_lists_overwritten_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(0),
+            BonusType::Enhancement,
+            5,
+            DebugValue(0),
+        ));
+
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Enhancement,
+                7,
+                DebugValue(1),
+            )],
+            [],
+        );
+
+        let diff = overlay.diff();
+        let attribute = diff
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+
+        let [changed] = attribute.changed().collect::<Vec<_>>()[..] else {
+            panic!("Expected one changed bonus");
+        };
+        assert_eq!(changed.bonus().source(), &BonusSource::Debug(0));
+        assert_eq!(
+            changed.before().map(BonusState::status),
+            Some(BonusStatus::Applied)
+        );
+        assert_eq!(
+            changed.after().map(BonusState::status),
+            Some(BonusStatus::Overwritten)
+        );
+    }
+
+    #[test]
+    fn diff_lists_disabled_bonuses() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)).with_condition(
+                Condition::GreaterThan(Value::Attribute(Attribute::Debug(1)), Value::ZERO),
+            ),
+            Bonus::new(DebugValue(1), BonusType::Stacking, 1, DebugValue(1)),
+        ]);
+
+        let overlay = breakdowns.with_overlay([], [BonusSource::Debug(1)]);
+
+        let diff = overlay.diff();
+        let attribute = diff
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+
+        let [changed] = attribute.changed().collect::<Vec<_>>()[..] else {
+            panic!("Expected one changed bonus");
+        };
+        assert_eq!(
+            changed.after().map(BonusState::status),
+            Some(BonusStatus::Disabled)
+        );
+        assert!(
+            diff.get(&DebugValue(1).into()).is_some(),
+            "Expected the removed attribute to change"
+        );
+    }
+}
+
+mod shared {
+    use std::thread;
+
+    use super::*;
+
+    const fn assert_sync<T: Send + Sync>() {}
+
+    fn breakdowns() -> Breakdowns {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+            Bonus::new(
+                DebugValue(1),
+                BonusType::Stacking,
+                Value::Attribute(Attribute::Debug(0)) * Value::TWO,
+                DebugValue(0),
+            ),
+        ]);
+        breakdowns
+    }
+
+    #[test]
+    fn breakdowns_are_sync() {
+        assert_sync::<Breakdowns>();
+    }
+
+    #[test]
+    fn evaluates_through_shared_reference() {
+        let breakdowns = breakdowns();
+        let shared = &breakdowns;
+
+        assert_eq!(shared.evaluate_attribute_from(DebugValue(1)), 10.into());
+        assert_eq!(
+            shared.get_value(&Value::Attribute(Attribute::Debug(0))),
+            Some(5.into())
+        );
+    }
+
+    #[test]
+    fn evaluates_from_multiple_threads() {
+        let breakdowns = breakdowns();
+
+        let results = thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (
+                            breakdowns.evaluate_attribute_from(DebugValue(1)),
+                            breakdowns.evaluate_attribute_from(DebugValue(0)),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Expected thread to finish"))
+                .collect::<Vec<_>>()
+        });
+
+        for result in results {
+            assert_eq!(result, (10.into(), 5.into()));
+        }
+    }
+
+    /// A chain of attributes, where each attribute is one more than the previous attribute
+    fn chain(length: usize) -> Breakdowns {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonuses((1..length).map(|index| {
+            Bonus::new(
+                DebugValue(index),
+                BonusType::Stacking,
+                Value::Attribute(Attribute::Debug(index - 1)) + Value::ONE,
+                DebugValue(0),
+            )
+        }));
+        breakdowns
+    }
+
+    #[test]
+    fn evaluates_overlapping_attributes_from_multiple_threads() {
+        let breakdowns = chain(64);
+
+        thread::scope(|scope| {
+            for thread in 0..8 {
+                let breakdowns = &breakdowns;
+                scope.spawn(move || {
+                    // Each thread walks the chain in a different order, so evaluations overlap
+                    for step in 0..64 {
+                        let index = (step * 7 + thread * 13) % 64;
+                        assert_eq!(
+                            breakdowns.evaluate_attribute_from(DebugValue(index)),
+                            Decimal::from(index)
+                        );
+                    }
+                });
+            }
+        });
+
+        for index in 0..64 {
+            assert_eq!(
+                breakdowns.evaluate_attribute_from(DebugValue(index)),
+                Decimal::from(index)
+            );
+        }
+    }
+
+    #[test]
+    fn overlay_evaluates_from_multiple_threads() {
+        let breakdowns = chain(32);
+        let overlay = breakdowns.with_overlay(
+            [Bonus::new(
+                DebugValue(0),
+                BonusType::Stacking,
+                10,
+                DebugValue(1),
+            )],
+            [],
+        );
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for index in (0..32).rev() {
+                        assert_eq!(
+                            overlay.evaluate_attribute_from(DebugValue(index)),
+                            Decimal::from(index + 10)
+                        );
+                    }
+                });
+            }
+        });
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(DebugValue(31)),
+            Decimal::from(31)
+        );
+    }
+
+    #[test]
+    fn clone_keeps_evaluated_cache() {
+        let breakdowns = breakdowns();
+        breakdowns.evaluate_attribute_from(DebugValue(1));
+
+        let clone = breakdowns.clone();
+
+        assert_eq!(
+            clone.get_value(&Value::Attribute(Attribute::Debug(0))),
+            Some(5.into())
+        );
+        assert!(
+            ron::to_string(&clone).is_ok(),
+            "Expected breakdowns to serialize"
+        );
+        assert_eq!(
+            ron::to_string(&clone).ok(),
+            ron::to_string(&breakdowns).ok()
+        );
+    }
+}
+
+mod state {
+    use builder::{
+        breakdowns::BuildState,
+        types::{ability::Ability, slider::Slider, toggle::Toggle},
+    };
+
+    use super::*;
+
+    fn breakdowns() -> Breakdowns {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.set_dice_strategy(DiceStrategy::Maximum);
+        breakdowns.insert_bonuses([
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+            Bonus::new(
+                DebugValue(1),
+                BonusType::Stacking,
+                Value::dice(1, 6),
+                DebugValue(1),
+            ),
+            Toggle::Blocking.toggle_bonus(true),
+            Slider::Optimism.slider_bonus(3),
+        ]);
+        breakdowns
+    }
+
+    #[test]
+    fn only_includes_supplied_bonuses() {
+        let state = breakdowns().to_state();
+
+        assert_eq!(state.version(), BuildState::VERSION);
+        assert_eq!(state.dice_strategy(), DiceStrategy::Maximum);
+        assert_eq!(state.toggles(), [Toggle::Blocking]);
+        assert_eq!(state.sliders(), [(Slider::Optimism, 3.into())]);
+        assert_eq!(state.bonuses().len(), 2);
+    }
+
+    #[test]
+    fn disabled_toggles_are_not_included() {
+        let mut breakdowns = breakdowns();
+        breakdowns.insert_bonus(Toggle::Blocking.toggle_bonus(false));
+
+        assert!(breakdowns.to_state().toggles().is_empty());
+    }
+
+    #[test]
+    fn from_state_rebuilds_values() {
+        let breakdowns = breakdowns();
+        let serialized =
+            ron::to_string(&breakdowns.to_state()).expect("Expected state to serialize");
+        let state: BuildState = ron::from_str(&serialized).expect("Expected state to deserialize");
+        let rebuilt = Breakdowns::from_state(state);
+
+        for attribute in [
+            Attribute::Debug(0),
+            Attribute::Debug(1),
+            Attribute::Toggle(Toggle::Blocking),
+            Attribute::Slider(Slider::Optimism),
+        ] {
+            assert_eq!(
+                rebuilt.evaluate_attribute(&attribute),
+                breakdowns.evaluate_attribute(&attribute),
+                "Expected {attribute} to match"
+            );
+        }
+        assert_eq!(rebuilt.dice_strategy(), DiceStrategy::Maximum);
+        assert_eq!(rebuilt.to_state(), breakdowns.to_state());
+    }
+
+    #[test]
+    fn cloned_bonuses_are_not_applied_twice() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            Ability::All,
+            BonusType::Stacking,
+            2,
+            DebugValue(0),
+        ));
+        let state = breakdowns.to_state();
+
+        assert_eq!(state.bonuses().len(), 1);
+
+        let rebuilt = Breakdowns::from_state(state);
+        let base = Breakdowns::new();
+        for ability in Ability::VALUES {
+            let attribute = Attribute::Ability(ability);
+            assert_eq!(
+                rebuilt.evaluate_attribute(&attribute),
+                base.evaluate_attribute(&attribute) + Decimal::TWO,
+                "Expected {ability} to only be raised once"
+            );
+            assert_eq!(
+                rebuilt.evaluate_attribute(&attribute),
+                breakdowns.evaluate_attribute(&attribute)
+            );
+        }
+        assert_eq!(rebuilt.to_state(), breakdowns.to_state());
+    }
+
+    #[test]
+    fn serialization_is_deterministic() {
+        let mut reversed = Breakdowns::new();
+        reversed.set_dice_strategy(DiceStrategy::Maximum);
+        reversed.insert_bonuses([
+            Slider::Optimism.slider_bonus(3),
+            Toggle::Blocking.toggle_bonus(true),
+            Bonus::new(
+                DebugValue(1),
+                BonusType::Stacking,
+                Value::dice(1, 6),
+                DebugValue(1),
+            ),
+            Bonus::new(DebugValue(0), BonusType::Stacking, 5, DebugValue(0)),
+        ]);
+
+        assert_eq!(
+            ron::to_string(&reversed.to_state()).ok(),
+            ron::to_string(&breakdowns().to_state()).ok()
+        );
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let serialized = ron::to_string(&breakdowns().to_state())
+            .expect("Expected state to serialize")
+            .replacen(
+                &format!("version:{}", BuildState::VERSION),
+                &format!("version:{}", BuildState::VERSION + 1),
+                1,
+            );
+
+        assert!(
+            serialized.contains(&format!("version:{}", BuildState::VERSION + 1)),
+            "Expected the version to be replaced"
+        );
+        let error = ron::from_str::<BuildState>(&serialized)
+            .expect_err("Expected newer version to be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("Unsupported build state version"),
+            "Unexpected error: {error}"
+        );
+    }
+}
+
+mod watch {
+    use super::*;
+
+    fn debug_bonus(value: i32, source: usize) -> Bonus {
+        Bonus::new(
+            DebugValue(0),
+            BonusType::Stacking,
+            value,
+            DebugValue(source),
+        )
+    }
+
+    #[test]
+    fn records_changes_to_watched_attributes() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.watch(DebugValue(0));
+        breakdowns.insert_bonus(debug_bonus(5, 0));
+
+        let changes = breakdowns.take_changes();
+        let change = changes
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+
+        assert_eq!(change.before(), &Decimal::ZERO);
+        assert_eq!(change.after(), &5.into());
+        assert_eq!(change.delta(), 5.into());
+    }
+
+    #[test]
+    fn ignores_unwatched_attributes() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.watch(DebugValue(1));
+        breakdowns.insert_bonus(debug_bonus(5, 0));
+
+        assert!(breakdowns.take_changes().is_empty());
+    }
+
+    #[test]
+    fn includes_dependant_attributes() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(Bonus::new(
+            DebugValue(1),
+            BonusType::Stacking,
+            Value::Attribute(Attribute::Debug(0)) * Value::TWO,
+            DebugValue(1),
+        ));
+        breakdowns.watch_all([DebugValue(0), DebugValue(1)]);
+        breakdowns.insert_bonus(debug_bonus(3, 0));
+
+        let changes = breakdowns.take_changes();
+        assert_eq!(
+            changes.attributes().cloned().collect::<Vec<_>>(),
+            [Attribute::Debug(0), Attribute::Debug(1)]
+        );
+        assert_eq!(
+            changes
+                .get(&DebugValue(1).into())
+                .map(|change| *change.after()),
+            Some(6.into())
+        );
+    }
+
+    #[test]
+    fn keeps_first_value_until_taken() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(debug_bonus(2, 0));
+        breakdowns.watch(DebugValue(0));
+        breakdowns.insert_bonus(debug_bonus(5, 1));
+        breakdowns.insert_bonus(debug_bonus(7, 2));
+
+        let changes = breakdowns.take_changes();
+        let change = changes
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+        assert_eq!(change.before(), &2.into());
+        assert_eq!(change.after(), &14.into());
+
+        assert!(
+            breakdowns.take_changes().is_empty(),
+            "Expected changes to be cleared"
+        );
+    }
+
+    #[test]
+    fn reverted_changes_are_not_included() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.watch(DebugValue(0));
+        breakdowns.insert_bonus(debug_bonus(5, 0));
+        breakdowns.remove_source(DebugValue(0));
+
+        assert!(breakdowns.take_changes().is_empty());
+    }
+
+    #[test]
+    fn unchanged_updates_keep_previous_value() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.insert_bonus(debug_bonus(5, 0));
+        breakdowns.watch(DebugValue(0));
+        breakdowns.insert_bonus(debug_bonus(0, 1));
+        breakdowns.remove_source(DebugValue(0));
+
+        let changes = breakdowns.take_changes();
+        let change = changes
+            .get(&DebugValue(0).into())
+            .expect("Expected attribute to change");
+        assert_eq!(change.before(), &5.into());
+        assert_eq!(change.after(), &Decimal::ZERO);
+    }
+
+    #[test]
+    fn unwatch_discards_changes() {
+        let mut breakdowns = Breakdowns::new();
+        breakdowns.watch(DebugValue(0));
+        breakdowns.insert_bonus(debug_bonus(5, 0));
+        breakdowns.unwatch(&DebugValue(0).into());
+
+        assert!(!breakdowns.is_watched(&DebugValue(0).into()));
+        assert!(breakdowns.take_changes().is_empty());
+    }
 }
@@ -0,0 +1,869 @@
+//! Tests compiling characters into bonuses
+
+use builder::{
+    attribute::Attribute,
+    bonus::{BonusSource, BonusType},
+    breakdowns::Breakdowns,
+    character::{Character, CharacterError, CharacterSource},
+    feat::{IconicPastLife, RacialFeat},
+    types::{
+        ability::Ability, alignment::Alignment, flag::Flag, player_class::PlayerClass, race::Race,
+        toggle::Toggle,
+    },
+};
+use rust_decimal::Decimal;
+use utils::enums::StaticValues;
+
+fn fighter_wizard() -> Character {
+    Character::new(Race::Elf)
+        .with_alignment(Alignment::Lawful, Alignment::Good)
+        .with_levels([
+            PlayerClass::Fighter,
+            PlayerClass::Wizard,
+            PlayerClass::Fighter,
+        ])
+        .with_ability_score(Ability::Strength, 16)
+}
+
+mod compiling {
+    use super::*;
+
+    #[test]
+    fn race_is_flagged() {
+        let breakdowns = Breakdowns::from_character(&fighter_wizard());
+        assert!(breakdowns.evaluate_attribute_from(Flag::Race(Race::Elf)) > Decimal::ZERO);
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Flag::Race(Race::Human)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn racial_bonuses_apply() {
+        let breakdowns = Breakdowns::from_character(&fighter_wizard());
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Dexterity),
+            10.into()
+        );
+        assert!(
+            breakdowns.evaluate_attribute_from(RacialFeat::ImmunityToSleep) > Decimal::ZERO,
+            "Expected elves to have immunity to sleep"
+        );
+    }
+
+    #[test]
+    fn alignment_is_flagged() {
+        let breakdowns = Breakdowns::from_character(&fighter_wizard());
+        assert!(
+            breakdowns.evaluate_attribute_from(Flag::Alignment(Alignment::Lawful)) > Decimal::ZERO
+        );
+        assert!(
+            breakdowns.evaluate_attribute_from(Flag::Alignment(Alignment::Good)) > Decimal::ZERO
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Flag::Alignment(Alignment::Evil)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn class_levels() {
+        let breakdowns = Breakdowns::from_character(&fighter_wizard());
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(PlayerClass::Fighter),
+            2.into()
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(PlayerClass::Wizard),
+            1.into()
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Attribute::TotalCharacterLevel),
+            3.into()
+        );
+    }
+
+    #[test]
+    fn epic_levels_do_not_add_class_levels() {
+        let character = Character::new(Race::Human).with_levels([PlayerClass::Fighter; 25]);
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert_eq!(character.class_level(PlayerClass::Fighter), 20);
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(PlayerClass::Fighter),
+            20.into()
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Attribute::TotalCharacterLevel),
+            25.into()
+        );
+    }
+
+    #[test]
+    fn ability_scores() {
+        let breakdowns = Breakdowns::from_character(&fighter_wizard());
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Strength),
+            16.into()
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Charisma),
+            8.into()
+        );
+    }
+
+    #[test]
+    fn feats_and_toggles() {
+        let character = fighter_wizard()
+            .with_feat(IconicPastLife(Race::Razorclaw))
+            .with_toggle(Toggle::Blocking);
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert!(
+            breakdowns.evaluate_attribute_from(IconicPastLife(Race::Razorclaw)) > Decimal::ZERO
+        );
+        assert!(breakdowns.evaluate_attribute_from(Toggle::Blocking) > Decimal::ZERO);
+    }
+
+    #[test]
+    fn bonuses_have_character_sources() {
+        let bonuses = fighter_wizard().to_bonuses();
+
+        assert!(bonuses
+            .iter()
+            .all(|bonus| matches!(bonus.source(), BonusSource::Character(_))));
+        assert!(bonuses.iter().any(|bonus| {
+            bonus
+                .source()
+                .eq(&BonusSource::Character(CharacterSource::Levels))
+                && bonus.bonus_type().eq(&BonusType::Stacking)
+        }));
+    }
+
+    #[test]
+    fn compiling_is_deterministic() {
+        let a = fighter_wizard()
+            .with_feat(IconicPastLife(Race::Razorclaw))
+            .with_feat(RacialFeat::ImmunityToSleep);
+        let b = fighter_wizard()
+            .with_feat(RacialFeat::ImmunityToSleep)
+            .with_feat(IconicPastLife(Race::Razorclaw));
+
+        assert_eq!(a.to_bonuses(), b.to_bonuses());
+    }
+
+    #[test]
+    fn inserting_replaces_previous_character() {
+        let mut breakdowns = Breakdowns::from_character(
+            &fighter_wizard()
+                .with_feat(IconicPastLife(Race::Razorclaw))
+                .with_toggle(Toggle::Blocking),
+        );
+        breakdowns.insert_character(&Character::new(Race::Dwarf).with_level(PlayerClass::Rogue));
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Flag::Race(Race::Elf)),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(PlayerClass::Fighter),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(IconicPastLife(Race::Razorclaw)),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Toggle::Blocking),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(PlayerClass::Rogue),
+            1.into()
+        );
+    }
+}
+
+mod validation {
+    use super::*;
+
+    #[test]
+    fn valid_character() {
+        assert!(fighter_wizard().validate().is_empty());
+    }
+
+    #[test]
+    fn too_many_levels() {
+        let character = Character::new(Race::Human).with_levels([PlayerClass::Fighter; 35]);
+        assert_eq!(character.validate(), [CharacterError::TooManyLevels(35)]);
+    }
+
+    #[test]
+    fn invalid_alignment() {
+        let character =
+            Character::new(Race::Human).with_alignment(Alignment::Good, Alignment::Lawful);
+        assert_eq!(
+            character.validate(),
+            [CharacterError::InvalidAlignment(
+                Alignment::Good,
+                Alignment::Lawful
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_ability() {
+        let character = Character::new(Race::Human).with_ability_score(Ability::All, 10);
+        assert_eq!(
+            character.validate(),
+            [CharacterError::InvalidAbility(Ability::All)]
+        );
+    }
+
+    #[test]
+    fn conflicting_toggles() {
+        let character = Character::new(Race::Human)
+            .with_toggle(Toggle::IconicPastLife(IconicPastLife(Race::Razorclaw)))
+            .with_toggle(Toggle::IconicPastLife(IconicPastLife(Race::Scourge)));
+        assert_eq!(character.validate().len(), 1);
+    }
+}
+
+mod point_buy {
+    use builder::{
+        character::{point_buy_cost, point_buy_pool, racial_adjustment},
+        feat::HeroicPastLife,
+    };
+
+    use super::*;
+
+    #[test]
+    fn costs() {
+        assert_eq!(point_buy_cost(8), Some(0));
+        assert_eq!(point_buy_cost(14), Some(6));
+        assert_eq!(point_buy_cost(18), Some(16));
+        assert_eq!(point_buy_cost(7), None);
+        assert_eq!(point_buy_cost(19), None);
+    }
+
+    #[test]
+    fn pool_grows_with_past_lives() {
+        assert_eq!(point_buy_pool(0, false), 28);
+        assert_eq!(point_buy_pool(0, true), 32);
+        assert_eq!(point_buy_pool(1, false), 34);
+        assert_eq!(point_buy_pool(1, true), 34);
+        assert_eq!(point_buy_pool(2, false), 36);
+        assert_eq!(point_buy_pool(10, true), 36);
+
+        let character = Character::new(Race::Human).with_feat(HeroicPastLife(PlayerClass::Fighter));
+        assert_eq!(character.past_lives(), 1);
+        assert_eq!(character.point_buy_pool(), 34);
+
+        let character = character.with_feat(HeroicPastLife(PlayerClass::Wizard));
+        assert_eq!(character.past_lives(), 2);
+        assert_eq!(character.point_buy_pool(), 36);
+    }
+
+    #[test]
+    fn champion_build_allows_more_points() {
+        let character = Character::new(Race::Human)
+            .with_ability_score(Ability::Strength, 18)
+            .with_ability_score(Ability::Dexterity, 16)
+            .with_ability_score(Ability::Constitution, 14);
+        assert!(!character.has_champion_build());
+        assert_eq!(character.point_buy_pool(), 28);
+
+        let character = character.with_champion_build(true);
+        assert_eq!(character.point_buy_pool(), 32);
+        assert!(character.validate().is_empty());
+    }
+
+    #[test]
+    fn racial_adjustments() {
+        assert_eq!(
+            racial_adjustment(Race::Elf, Ability::Dexterity),
+            Some(2.into())
+        );
+        assert_eq!(
+            racial_adjustment(Race::Elf, Ability::Constitution),
+            Some((-2).into())
+        );
+        assert_eq!(
+            racial_adjustment(Race::Elf, Ability::Strength),
+            Some(Decimal::ZERO)
+        );
+
+        let character = Character::new(Race::Elf);
+        assert_eq!(
+            character.ability_score_range(Ability::Dexterity),
+            (10.into(), 20.into())
+        );
+        assert_eq!(
+            character.ability_score_range(Ability::Constitution),
+            (6.into(), 16.into())
+        );
+    }
+
+    #[test]
+    fn racial_adjustments_are_constant() {
+        for race in Race::values() {
+            for ability in Ability::VALUES {
+                assert!(
+                    racial_adjustment(race, ability).is_some(),
+                    "{race} adjustment to {ability} is not constant"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn all_ability_is_not_bought() {
+        let character = Character::new(Race::Human).with_ability_score(Ability::All, 18);
+
+        assert_eq!(character.points_spent(), 0);
+        assert!(!character.to_bonuses().iter().any(|bonus| bonus
+            .attribute()
+            .eq(&Attribute::Ability(Ability::All))
+            && bonus
+                .source()
+                .eq(&BonusSource::Character(CharacterSource::PointBuy))));
+        assert_eq!(
+            character.validate(),
+            [CharacterError::InvalidAbility(Ability::All)]
+        );
+    }
+
+    #[test]
+    fn points_spent() {
+        let character = Character::new(Race::Human)
+            .with_ability_score(Ability::Strength, 18)
+            .with_ability_score(Ability::Constitution, 14);
+        assert_eq!(character.points_spent(), 22);
+        assert!(character.validate().is_empty());
+    }
+
+    #[test]
+    fn overspending() {
+        let character = Character::new(Race::Human)
+            .with_ability_score(Ability::Strength, 18)
+            .with_ability_score(Ability::Dexterity, 16)
+            .with_ability_score(Ability::Constitution, 14);
+        assert_eq!(
+            character.validate(),
+            [CharacterError::PointBuyOverspent {
+                spent: 32,
+                available: 28
+            }]
+        );
+    }
+
+    #[test]
+    fn past_lives_allow_more_points() {
+        let character = Character::new(Race::Human)
+            .with_ability_score(Ability::Strength, 18)
+            .with_ability_score(Ability::Dexterity, 16)
+            .with_ability_score(Ability::Constitution, 14)
+            .with_feat(HeroicPastLife(PlayerClass::Fighter));
+        assert!(character.validate().is_empty());
+    }
+
+    #[test]
+    fn out_of_range_scores() {
+        let character = Character::new(Race::Human)
+            .with_ability_score(Ability::Strength, 19)
+            .with_ability_score(Ability::Dexterity, 7);
+        assert_eq!(
+            character.validate(),
+            [
+                CharacterError::AbilityScoreOutOfRange(Ability::Strength, 19),
+                CharacterError::AbilityScoreOutOfRange(Ability::Dexterity, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn scores_include_racial_adjustments() {
+        let character = Character::new(Race::Elf).with_ability_score(Ability::Dexterity, 18);
+        let breakdowns = Breakdowns::from_character(&character);
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Dexterity),
+            20.into()
+        );
+    }
+
+    #[test]
+    fn ability_increases() {
+        let character = Character::new(Race::Human)
+            .with_levels([PlayerClass::Fighter; 8])
+            .with_ability_score(Ability::Strength, 16)
+            .with_ability_increase(4, Ability::Strength)
+            .with_ability_increase(8, Ability::Strength);
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert!(character.validate().is_empty());
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Strength),
+            18.into()
+        );
+        assert!(character.to_bonuses().iter().any(|bonus| bonus
+            .source()
+            .eq(&BonusSource::Character(CharacterSource::AbilityIncreases))));
+    }
+
+    #[test]
+    fn invalid_ability_increases() {
+        let character = Character::new(Race::Human)
+            .with_levels([PlayerClass::Fighter; 4])
+            .with_ability_increase(3, Ability::Strength)
+            .with_ability_increase(8, Ability::Strength);
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert_eq!(
+            character.validate(),
+            [
+                CharacterError::InvalidAbilityIncrease(3),
+                CharacterError::InvalidAbilityIncrease(8),
+            ]
+        );
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Ability::Strength),
+            8.into()
+        );
+    }
+}
+
+mod feat_slots {
+    use builder::{
+        character::{FeatSlot, FeatSlotKind},
+        feat::{Proficiency, RacialFeat, SkillFocus, SpellFocusFeat, SpellcastingFeat},
+        types::{item_type::WeaponType, skill::Skill, spell_school::SpellSchool},
+    };
+
+    use super::*;
+
+    const SPELL_FOCUS: SpellFocusFeat = SpellFocusFeat::SpellFocus(SpellSchool::Evocation);
+    const GREATER_SPELL_FOCUS: SpellFocusFeat =
+        SpellFocusFeat::GreaterSpellFocus(SpellSchool::Evocation);
+
+    #[test]
+    fn human_fighter_bonus_feats() {
+        let character = Character::new(Race::Human).with_level(PlayerClass::Fighter);
+        assert_eq!(
+            character.feat_slots(),
+            [
+                FeatSlot::new(1, FeatSlotKind::Heroic),
+                FeatSlot::new(1, FeatSlotKind::HumanBonus),
+                FeatSlot::new(1, FeatSlotKind::ClassBonus(PlayerClass::Fighter)),
+            ]
+        );
+    }
+
+    #[test]
+    fn general_feats_every_three_levels() {
+        let character = Character::new(Race::Elf).with_levels([PlayerClass::Rogue; 34]);
+        let slots = character
+            .feat_slots()
+            .into_iter()
+            .map(|slot| (slot.level(), slot.kind()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            slots,
+            [
+                (1, FeatSlotKind::Heroic),
+                (3, FeatSlotKind::Heroic),
+                (6, FeatSlotKind::Heroic),
+                (9, FeatSlotKind::Heroic),
+                (12, FeatSlotKind::Heroic),
+                (15, FeatSlotKind::Heroic),
+                (18, FeatSlotKind::Heroic),
+                (21, FeatSlotKind::Epic),
+                (24, FeatSlotKind::Epic),
+                (27, FeatSlotKind::Epic),
+                (30, FeatSlotKind::Legendary),
+            ]
+        );
+    }
+
+    #[test]
+    fn one_legendary_feat() {
+        let character = Character::new(Race::Elf).with_levels([PlayerClass::Rogue; 33]);
+        let legendary = character
+            .feat_slots()
+            .into_iter()
+            .filter(|slot| slot.kind() == FeatSlotKind::Legendary)
+            .collect::<Vec<_>>();
+
+        assert_eq!(legendary, [FeatSlot::new(30, FeatSlotKind::Legendary)]);
+        assert!(character.feat_slots().iter().all(|slot| slot.level() <= 30));
+
+        let slot = FeatSlot::new(33, FeatSlotKind::Legendary);
+        assert_eq!(
+            character
+                .with_slotted_feat(slot, SkillFocus::Alertness)
+                .validate(),
+            [CharacterError::UnavailableFeatSlot(slot)]
+        );
+    }
+
+    #[test]
+    fn class_bonus_feats_follow_class_level() {
+        let character = Character::new(Race::Elf)
+            .with_level(PlayerClass::Fighter)
+            .with_levels([PlayerClass::Wizard; 5])
+            .with_levels([PlayerClass::Artificer; 4]);
+        let bonus = character
+            .feat_slots()
+            .into_iter()
+            .filter(|slot| matches!(slot.kind(), FeatSlotKind::ClassBonus(_)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            bonus,
+            [
+                FeatSlot::new(1, FeatSlotKind::ClassBonus(PlayerClass::Fighter)),
+                FeatSlot::new(2, FeatSlotKind::ClassBonus(PlayerClass::Wizard)),
+                FeatSlot::new(6, FeatSlotKind::ClassBonus(PlayerClass::Wizard)),
+                FeatSlot::new(10, FeatSlotKind::ClassBonus(PlayerClass::Artificer)),
+            ]
+        );
+    }
+
+    #[test]
+    fn general_slots_allow_general_feats() {
+        for kind in [
+            FeatSlotKind::Heroic,
+            FeatSlotKind::Epic,
+            FeatSlotKind::HumanBonus,
+        ] {
+            assert!(kind.allows(&SkillFocus::Alertness.into()));
+            assert!(kind.allows(&SPELL_FOCUS.into()));
+            assert!(kind.allows(&Proficiency::MartialWeaponProficiency.into()));
+            assert!(kind.allows(&Proficiency::from(WeaponType::BastardSword).into()));
+            assert!(!kind.allows(&Proficiency::from(WeaponType::LongSword).into()));
+            assert!(!kind.allows(&RacialFeat::ImmunityToSleep.into()));
+        }
+
+        assert_eq!(FeatSlotKind::Legendary.feats().count(), 0);
+    }
+
+    #[test]
+    fn class_bonus_slots_allow_class_feats() {
+        let fighter = FeatSlotKind::ClassBonus(PlayerClass::Fighter);
+        assert!(fighter.allows(&Proficiency::from(WeaponType::BastardSword).into()));
+        assert!(!fighter.allows(&SkillFocus::Alertness.into()));
+        assert!(!fighter.allows(&SPELL_FOCUS.into()));
+
+        let wizard = FeatSlotKind::ClassBonus(PlayerClass::Wizard);
+        assert!(wizard.allows(&SPELL_FOCUS.into()));
+        assert!(!wizard.allows(&SpellcastingFeat::MagicalTraining.into()));
+        assert!(!wizard.allows(&SkillFocus::Alertness.into()));
+
+        let rogue = FeatSlotKind::ClassBonus(PlayerClass::Rogue);
+        assert_eq!(rogue.feats().count(), 0);
+    }
+
+    #[test]
+    fn slotted_feats_compile() {
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_slotted_feat(
+                FeatSlot::new(1, FeatSlotKind::Heroic),
+                SkillFocus::Focus(Skill::Jump),
+            );
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert!(character.validate().is_empty());
+        assert_eq!(
+            character.empty_feat_slots(),
+            [
+                FeatSlot::new(1, FeatSlotKind::HumanBonus),
+                FeatSlot::new(1, FeatSlotKind::ClassBonus(PlayerClass::Fighter)),
+            ]
+        );
+        assert!(breakdowns.evaluate_attribute_from(SkillFocus::Focus(Skill::Jump)) > Decimal::ZERO);
+    }
+
+    #[test]
+    fn unavailable_slots() {
+        let slot = FeatSlot::new(3, FeatSlotKind::Heroic);
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_slotted_feat(slot, SkillFocus::Alertness);
+
+        assert_eq!(
+            character.validate(),
+            [CharacterError::UnavailableFeatSlot(slot)]
+        );
+    }
+
+    #[test]
+    fn disallowed_feats_are_rejected() {
+        let class_bonus = FeatSlot::new(1, FeatSlotKind::ClassBonus(PlayerClass::Fighter));
+        let human_bonus = FeatSlot::new(1, FeatSlotKind::HumanBonus);
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_slotted_feat(class_bonus, SkillFocus::Alertness)
+            .with_slotted_feat(human_bonus, RacialFeat::ImmunityToSleep);
+
+        assert_eq!(
+            character.validate(),
+            [
+                CharacterError::FeatNotAllowed(human_bonus, RacialFeat::ImmunityToSleep.into()),
+                CharacterError::FeatNotAllowed(class_bonus, SkillFocus::Alertness.into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn legendary_slot_rejects_general_feats() {
+        let slot = FeatSlot::new(30, FeatSlotKind::Legendary);
+        let character = Character::new(Race::Elf)
+            .with_levels([PlayerClass::Rogue; 30])
+            .with_slotted_feat(slot, SkillFocus::Alertness);
+
+        assert_eq!(
+            character.validate(),
+            [CharacterError::FeatNotAllowed(
+                slot,
+                SkillFocus::Alertness.into()
+            )]
+        );
+    }
+
+    #[test]
+    fn feats_in_two_slots_are_duplicates() {
+        let heroic = FeatSlot::new(1, FeatSlotKind::Heroic);
+        let human_bonus = FeatSlot::new(1, FeatSlotKind::HumanBonus);
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_slotted_feat(heroic, SkillFocus::Alertness)
+            .with_slotted_feat(human_bonus, SkillFocus::Alertness);
+
+        assert_eq!(
+            character.validate(),
+            [CharacterError::DuplicateFeat(
+                human_bonus,
+                SkillFocus::Alertness.into()
+            )]
+        );
+    }
+
+    #[test]
+    fn slotted_feats_already_taken_are_duplicates() {
+        let slot = FeatSlot::new(1, FeatSlotKind::Heroic);
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_feat(SkillFocus::Alertness)
+            .with_slotted_feat(slot, SkillFocus::Alertness);
+
+        assert_eq!(
+            character.validate(),
+            [CharacterError::DuplicateFeat(
+                slot,
+                SkillFocus::Alertness.into()
+            )]
+        );
+    }
+
+    #[test]
+    fn requirements_are_checked() {
+        let character = Character::new(Race::Human)
+            .with_level(PlayerClass::Fighter)
+            .with_slotted_feat(FeatSlot::new(1, FeatSlotKind::Heroic), SPELL_FOCUS);
+
+        let errors = character.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors.first(),
+            Some(CharacterError::FeatRequirementsNotMet(feat, _)) if feat.eq(&SPELL_FOCUS.into())
+        ));
+    }
+
+    #[test]
+    fn requirements_use_earlier_levels() {
+        let wizard = Character::new(Race::Elf)
+            .with_levels([PlayerClass::Wizard; 3])
+            .with_slotted_feat(FeatSlot::new(1, FeatSlotKind::Heroic), SPELL_FOCUS);
+
+        let valid = wizard
+            .clone()
+            .with_slotted_feat(FeatSlot::new(3, FeatSlotKind::Heroic), GREATER_SPELL_FOCUS);
+        assert!(valid.validate().is_empty());
+
+        let same_level = wizard.with_slotted_feat(
+            FeatSlot::new(1, FeatSlotKind::ClassBonus(PlayerClass::Wizard)),
+            GREATER_SPELL_FOCUS,
+        );
+        assert_eq!(same_level.validate().len(), 1);
+    }
+}
+
+mod skills {
+    use builder::{feat::FeatRequirement, types::skill::Skill};
+
+    use super::*;
+
+    fn human_fighter() -> Character {
+        Character::new(Race::Human)
+            .with_levels([PlayerClass::Fighter; 2])
+            .with_ability_score(Ability::Intelligence, 14)
+    }
+
+    #[test]
+    fn budgets() {
+        let character = human_fighter();
+        assert_eq!(character.skill_point_budget(1), Some(20));
+        assert_eq!(character.skill_point_budget(2), Some(5));
+        assert_eq!(character.skill_point_budget(0), None);
+        assert_eq!(character.skill_point_budget(3), None);
+
+        let wizard = Character::new(Race::Elf).with_level(PlayerClass::Wizard);
+        assert_eq!(wizard.skill_point_budget(1), Some(4));
+    }
+
+    #[test]
+    fn at_least_one_point_per_level() {
+        let character = Character::new(Race::HalfOrc).with_levels([PlayerClass::Fighter; 2]);
+
+        assert_eq!(character.skill_point_budget(1), Some(4));
+        assert_eq!(character.skill_point_budget(2), Some(1));
+    }
+
+    #[test]
+    fn epic_levels_gain_no_skill_points() {
+        let character = Character::new(Race::Human).with_levels([PlayerClass::Rogue; 21]);
+        assert!(character.skill_point_budget(20).is_some());
+        assert_eq!(character.skill_point_budget(21), None);
+    }
+
+    #[test]
+    fn cross_class_skills_cost_double() {
+        let character = human_fighter()
+            .with_skill_points(1, Skill::Jump, 4)
+            .with_skill_points(1, Skill::Spot, 2);
+
+        assert!(character.is_class_skill(Skill::Jump, 1));
+        assert!(!character.is_class_skill(Skill::Spot, 1));
+        assert_eq!(character.skill_ranks(Skill::Jump, 1), 4.into());
+        assert_eq!(character.skill_ranks(Skill::Spot, 1), 1.into());
+        assert!(character.validate().is_empty());
+    }
+
+    #[test]
+    fn class_skills_follow_the_level_taken() {
+        let character = Character::new(Race::Elf)
+            .with_ability_score(Ability::Intelligence, 16)
+            .with_level(PlayerClass::Wizard)
+            .with_level(PlayerClass::Rogue)
+            .with_skill_points(1, Skill::Spot, 4)
+            .with_skill_points(2, Skill::Spot, 3);
+
+        assert_eq!(character.skill_ranks(Skill::Spot, 1), 2.into());
+        assert_eq!(character.skill_ranks(Skill::Spot, 2), 5.into());
+        assert_eq!(character.max_skill_ranks(Skill::Spot, 1), 2.into());
+        assert_eq!(character.max_skill_ranks(Skill::Spot, 2), 5.into());
+        assert!(character.validate().is_empty());
+    }
+
+    #[test]
+    fn max_ranks() {
+        let character = human_fighter()
+            .with_skill_points(1, Skill::Jump, 5)
+            .with_skill_points(1, Skill::Spot, 6);
+
+        assert_eq!(
+            character.validate(),
+            [
+                CharacterError::TooManySkillRanks {
+                    skill: Skill::Jump,
+                    level: 1,
+                    ranks: 5.into(),
+                    max: 4.into(),
+                },
+                CharacterError::TooManySkillRanks {
+                    skill: Skill::Spot,
+                    level: 1,
+                    ranks: 3.into(),
+                    max: 2.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn overspending() {
+        let character = human_fighter()
+            .with_skill_points(2, Skill::Jump, 3)
+            .with_skill_points(2, Skill::Swim, 3);
+        assert_eq!(
+            character.validate(),
+            [CharacterError::SkillPointsOverspent {
+                level: 2,
+                spent: 6,
+                available: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn lowering_intelligence_overspends() {
+        let mut character = human_fighter()
+            .with_skill_points(1, Skill::Jump, 4)
+            .with_skill_points(1, Skill::Swim, 4)
+            .with_skill_points(1, Skill::Balance, 4)
+            .with_skill_points(1, Skill::Intimidate, 4)
+            .with_skill_points(1, Skill::Repair, 4);
+        assert!(character.validate().is_empty());
+
+        character.set_ability_score(Ability::Intelligence, 8);
+        assert_eq!(
+            character.validate(),
+            [CharacterError::SkillPointsOverspent {
+                level: 1,
+                spent: 20,
+                available: 8
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_skill_points() {
+        let character = human_fighter()
+            .with_skill_points(3, Skill::Jump, 1)
+            .with_skill_points(1, Skill::All, 1);
+
+        assert_eq!(
+            character.validate(),
+            [
+                CharacterError::InvalidSkillPoints(1, Skill::All),
+                CharacterError::InvalidSkillPoints(3, Skill::Jump),
+            ]
+        );
+        assert_eq!(character.skill_ranks(Skill::Jump, 3), Decimal::ZERO);
+    }
+
+    #[test]
+    fn removing_skill_points() {
+        let mut character = human_fighter().with_skill_points(1, Skill::Jump, 4);
+        character.set_skill_points(1, Skill::Jump, 0);
+        assert!(character.skill_points().is_empty());
+    }
+
+    #[test]
+    fn ranks_compile() {
+        let character = human_fighter()
+            .with_ability_score(Ability::Strength, 14)
+            .with_skill_points(1, Skill::Jump, 4)
+            .with_skill_points(2, Skill::Concentration, 2);
+        let breakdowns = Breakdowns::from_character(&character);
+
+        assert_eq!(
+            breakdowns.evaluate_attribute_from(Attribute::SkillRanks(Skill::Jump)),
+            4.into()
+        );
+        assert_eq!(breakdowns.evaluate_attribute_from(Skill::Jump), 6.into());
+        assert!(FeatRequirement::SkillPoints(Skill::Jump, 4).is_satisfied(&breakdowns));
+        assert!(!FeatRequirement::SkillPoints(Skill::Concentration, 2).is_satisfied(&breakdowns));
+    }
+}
@@ -0,0 +1,511 @@
+//! Tests the expression language for values and conditions
+
+use builder::{
+    attribute::Attribute,
+    bonus::{Condition, ExpressionErrorKind, Value, MAX_EXPRESSION_DEPTH},
+    types::{ability::Ability, flag::Flag, race::Race, toggle::Toggle},
+    val,
+};
+
+const fn strength() -> Value {
+    Value::Attribute(Attribute::Ability(Ability::Strength))
+}
+
+fn parse_value(expression: &str) -> Value {
+    expression.parse().expect("Could not parse value")
+}
+
+fn parse_condition(expression: &str) -> Condition {
+    expression.parse().expect("Could not parse condition")
+}
+
+mod values {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_value("5"), Value::from(5));
+        assert_eq!(parse_value("2.5"), val!(2.5));
+        assert_eq!(parse_value("-3"), Value::from(-3));
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(parse_value("Ability(Strength)"), strength());
+        assert_eq!(
+            parse_value("GuildLevel"),
+            Value::Attribute(Attribute::GuildLevel)
+        );
+        assert_eq!(parse_value("`a(s)`"), strength());
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            parse_value("1 + 2 * 3"),
+            Value::ONE + Value::TWO * Value::from(3)
+        );
+        assert_eq!(
+            parse_value("(1 + 2) * 3"),
+            (Value::ONE + Value::TWO) * Value::from(3)
+        );
+    }
+
+    #[test]
+    fn left_associative() {
+        assert_eq!(
+            parse_value("10 - 2 - 1"),
+            (Value::TEN - Value::TWO) - Value::ONE
+        );
+        assert_eq!(
+            parse_value("10 / 2 % 3"),
+            (Value::TEN / Value::TWO) % Value::from(3)
+        );
+    }
+
+    #[test]
+    fn negation() {
+        assert_eq!(parse_value("-Ability(Strength)"), -strength());
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(
+            parse_value("floor((Ability(Strength) - 10) / 2)"),
+            ((strength() - Value::TEN) / Value::TWO).floor()
+        );
+        assert_eq!(parse_value("min(1, 2)"), Value::ONE.min(Value::TWO));
+        assert_eq!(parse_value("max(1, 2)"), Value::ONE.max(Value::TWO));
+        assert_eq!(parse_value("ceil(1)"), Value::ONE.ceil());
+        assert_eq!(parse_value("round(1)"), Value::ONE.round());
+        assert_eq!(parse_value("abs(1)"), Value::ONE.abs());
+        assert_eq!(
+            parse_value("dice(2, 6)"),
+            Value::dice(Value::TWO, Value::from(6))
+        );
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            parse_value("table(Ability(Strength), -5 => 0, 10 => 1 + 1)"),
+            Value::table(
+                strength(),
+                [(-5, Value::ZERO), (10, Value::ONE + Value::ONE)]
+            )
+        );
+        assert_eq!(
+            parse_value("table(Ability(Strength))"),
+            Value::table(strength(), [] as [(i32, Value); 0])
+        );
+    }
+
+    #[test]
+    fn math_functions() {
+        assert_eq!(
+            parse_value("pow(Ability(Strength), 2)"),
+            strength().pow(Value::TWO)
+        );
+        assert_eq!(
+            parse_value("clamp(Ability(Strength), 0, 10)"),
+            strength().clamp(Value::ZERO, Value::TEN)
+        );
+        assert_eq!(
+            parse_value("sign(-Ability(Strength))"),
+            (-strength()).sign()
+        );
+    }
+
+    #[test]
+    fn sum_and_product() {
+        assert_eq!(
+            parse_value("sum(1, Ability(Strength), 2)"),
+            Value::sum_all([Value::ONE, strength(), Value::TWO])
+        );
+        assert_eq!(
+            parse_value("product()"),
+            Value::product_all([] as [Value; 0])
+        );
+    }
+
+    #[test]
+    fn conditional() {
+        assert_eq!(
+            parse_value("if Toggle(Blocking) then 10 else 0"),
+            Value::condition(
+                Condition::toggled(Toggle::Blocking),
+                Value::TEN,
+                Value::ZERO
+            )
+        );
+    }
+}
+
+mod conditions {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(parse_condition("true"), Condition::TRUE);
+        assert_eq!(parse_condition("false"), Condition::FALSE);
+    }
+
+    #[test]
+    fn comparisons() {
+        assert_eq!(
+            parse_condition("Ability(Strength) > 10"),
+            strength().greater_than(Value::TEN)
+        );
+        assert_eq!(
+            parse_condition("Ability(Strength) >= 10"),
+            strength().greater_or_equal_to(Value::TEN)
+        );
+        assert_eq!(
+            parse_condition("Ability(Strength) < 10"),
+            strength().less_than(Value::TEN)
+        );
+        assert_eq!(
+            parse_condition("Ability(Strength) <= 10"),
+            strength().less_or_equal_to(Value::TEN)
+        );
+        assert_eq!(
+            parse_condition("Ability(Strength) == 10"),
+            strength().equal_to(Value::TEN)
+        );
+    }
+
+    #[test]
+    fn has() {
+        let elf = Condition::has(Attribute::Flag(Flag::Race(Race::Elf)));
+
+        assert_eq!(parse_condition("has(Flag(Race(Elf)))"), elf);
+        assert_eq!(parse_condition("Flag(Race(Elf))"), elf);
+    }
+
+    #[test]
+    fn logic() {
+        let elf = Condition::has(Attribute::Flag(Flag::Race(Race::Elf)));
+        let blocking = Condition::toggled(Toggle::Blocking);
+
+        assert_eq!(
+            parse_condition("has(Flag(Race(Elf))) and Toggle(Blocking)"),
+            elf.clone() & blocking.clone()
+        );
+        assert_eq!(
+            parse_condition("not Flag(Race(Elf)) or Toggle(Blocking)"),
+            !elf.clone() | blocking.clone()
+        );
+        assert_eq!(
+            parse_condition("not (Flag(Race(Elf)) xor Toggle(Blocking))"),
+            !(elf ^ blocking)
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        let a = Condition::has(Attribute::Debug(0));
+        let b = Condition::has(Attribute::Debug(1));
+        let c = Condition::has(Attribute::Debug(2));
+
+        assert_eq!(
+            parse_condition("Debug(0) or Debug(1) and Debug(2)"),
+            a.clone() | (b.clone() & c.clone())
+        );
+        assert_eq!(
+            parse_condition("(Debug(0) or Debug(1)) and Debug(2)"),
+            (a | b) & c
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let a = Condition::has(Attribute::Debug(0));
+        let b = Condition::has(Attribute::Debug(1));
+
+        assert_eq!(
+            parse_condition("all(Debug(0), Debug(1))"),
+            Condition::all([a.clone(), b.clone()])
+        );
+        assert_eq!(
+            parse_condition("any(Debug(0) and Debug(1), Debug(1))"),
+            Condition::any([a.clone() & b.clone(), b.clone()])
+        );
+        assert_eq!(
+            parse_condition("at_least(1, Debug(0), Debug(1))"),
+            Condition::at_least(1, [a, b])
+        );
+        assert_eq!(parse_condition("all()"), Condition::all([]));
+        assert_eq!(parse_condition("at_least(0)"), Condition::at_least(0, []));
+    }
+
+    #[test]
+    fn grouped_values() {
+        assert_eq!(
+            parse_condition("(Ability(Strength) + 2) > 10"),
+            (strength() + Value::TWO).greater_than(Value::TEN)
+        );
+        assert_eq!(
+            parse_condition("(Ability(Strength) > 10)"),
+            strength().greater_than(Value::TEN)
+        );
+    }
+}
+
+mod errors {
+    use super::*;
+
+    fn value_error(expression: &str) -> (ExpressionErrorKind, core::ops::Range<usize>) {
+        let error = expression
+            .parse::<Value>()
+            .expect_err("Expression should not parse");
+        (error.kind().clone(), error.span().clone())
+    }
+
+    #[test]
+    fn unexpected_character() {
+        assert_eq!(
+            value_error("1 + $"),
+            (ExpressionErrorKind::UnexpectedCharacter('$'), 4..5)
+        );
+    }
+
+    #[test]
+    fn unexpected_token() {
+        assert_eq!(
+            value_error("1 + * 2"),
+            (
+                ExpressionErrorKind::UnexpectedToken {
+                    found: "'*'".to_owned(),
+                    expected: "a value"
+                },
+                4..5
+            )
+        );
+    }
+
+    #[test]
+    fn unexpected_end() {
+        assert_eq!(
+            value_error("(1 + 2"),
+            (ExpressionErrorKind::UnexpectedEnd { expected: "')'" }, 6..6)
+        );
+    }
+
+    #[test]
+    fn trailing_tokens() {
+        let (kind, span) = value_error("1 2");
+        assert!(matches!(
+            kind,
+            ExpressionErrorKind::UnexpectedToken {
+                expected: "end of expression",
+                ..
+            }
+        ));
+        assert_eq!(span, 2..3);
+    }
+
+    #[test]
+    fn invalid_attribute() {
+        let (kind, span) = value_error("2 * Ability(Muscles)");
+        assert!(matches!(kind, ExpressionErrorKind::InvalidAttribute(_)));
+        assert_eq!(span, 4..20);
+    }
+
+    #[test]
+    fn unterminated_attribute() {
+        assert_eq!(
+            value_error("Ability(Strength"),
+            (ExpressionErrorKind::UnterminatedAttribute, 0..16)
+        );
+    }
+
+    #[test]
+    fn unknown_function() {
+        assert_eq!(
+            value_error("sqrt(4)"),
+            (
+                ExpressionErrorKind::UnknownFunction("sqrt".to_owned()),
+                0..4
+            )
+        );
+    }
+
+    #[test]
+    fn argument_count() {
+        assert_eq!(
+            value_error("floor(1, 2)"),
+            (
+                ExpressionErrorKind::ArgumentCount {
+                    function: "floor".to_owned(),
+                    expected: 1,
+                    found: 2
+                },
+                0..11
+            )
+        );
+    }
+
+    #[test]
+    fn condition_reports_furthest_error() {
+        let error = "(Toggle(Blocking) and 5)"
+            .parse::<Condition>()
+            .expect_err("Expression should not parse");
+
+        assert_eq!(
+            error.kind(),
+            &ExpressionErrorKind::UnexpectedToken {
+                found: "')'".to_owned(),
+                expected: "a comparison"
+            }
+        );
+        assert_eq!(error.span(), &(23..24));
+    }
+
+    #[test]
+    fn deeply_nested_values_are_rejected() {
+        let parentheses = "(".repeat(100_000);
+        assert_eq!(value_error(&parentheses).0, ExpressionErrorKind::TooDeep);
+
+        let negations = format!("{}1", "- ".repeat(100_000));
+        assert_eq!(value_error(&negations).0, ExpressionErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn deeply_nested_conditions_are_rejected() {
+        for expression in [
+            "(".repeat(100_000),
+            format!("{}true", "not ".repeat(100_000)),
+        ] {
+            let error = expression
+                .parse::<Condition>()
+                .expect_err("Expression should not parse");
+            assert_eq!(error.kind(), &ExpressionErrorKind::TooDeep);
+        }
+    }
+
+    #[test]
+    fn nesting_up_to_limit_parses() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+
+        // The outermost value is the first level
+        assert_eq!(parse_value(&nested(MAX_EXPRESSION_DEPTH - 1)), Value::ONE);
+        assert_eq!(
+            value_error(&nested(MAX_EXPRESSION_DEPTH)).0,
+            ExpressionErrorKind::TooDeep
+        );
+    }
+}
+
+mod printing {
+    use super::*;
+
+    fn assert_round_trip_value(value: &Value) {
+        let expression = value.to_expression();
+        assert_eq!(&parse_value(&expression), value, "{expression}");
+    }
+
+    fn assert_round_trip_condition(condition: &Condition) {
+        let expression = condition.to_expression();
+        assert_eq!(&parse_condition(&expression), condition, "{expression}");
+    }
+
+    #[test]
+    fn minimal_parentheses() {
+        assert_eq!(
+            ((strength() - Value::TEN) / Value::TWO)
+                .floor()
+                .to_expression(),
+            "floor((Ability(Strength) - 10) / 2)"
+        );
+        assert_eq!(
+            (Value::ONE + Value::TWO * Value::from(3)).to_expression(),
+            "1 + 2 * 3"
+        );
+        assert_eq!(
+            (Value::ONE - (Value::TWO - Value::from(3))).to_expression(),
+            "1 - (2 - 3)"
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let elf = Condition::has(Attribute::Flag(Flag::Race(Race::Elf)));
+        let blocking = Condition::toggled(Toggle::Blocking);
+
+        assert_eq!(
+            (elf.clone() & blocking.clone()).to_expression(),
+            "has(Flag(Race(Elf))) and has(Toggle(Blocking))"
+        );
+        assert_eq!(
+            (!(elf | blocking)).to_expression(),
+            "not (has(Flag(Race(Elf))) or has(Toggle(Blocking)))"
+        );
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let values = [
+            Value::from(-5),
+            -strength(),
+            val!(2.5) * -strength(),
+            strength() - Value::from(-3),
+            Value::ONE + (Value::TWO + Value::from(3)),
+            (Value::TEN % Value::from(3)) / (Value::TWO * Value::ONE),
+            strength()
+                .min(Value::TEN)
+                .max(Value::ZERO)
+                .abs()
+                .round()
+                .ceil(),
+            Value::dice(strength() + Value::ONE, Value::from(6)),
+            Value::table(
+                strength(),
+                [
+                    (0, Value::ONE),
+                    (11, strength() * Value::TWO),
+                    (-2, Value::from(-3)),
+                ],
+            ),
+            strength().pow(Value::TWO).sign() * strength().clamp(Value::ZERO, Value::TEN),
+            Value::sum_all([strength(), Value::product_all([Value::TWO, strength()])]),
+            Value::product_all([] as [Value; 0]),
+            Value::condition(
+                Condition::toggled(Toggle::Blocking),
+                strength(),
+                Value::ZERO,
+            ) + Value::ONE,
+            Value::condition(
+                strength().greater_than(Value::TEN),
+                Value::condition(Condition::FALSE, Value::ONE, Value::TWO),
+                Value::condition(Condition::TRUE, Value::ONE, Value::TWO),
+            ),
+        ];
+
+        for value in &values {
+            assert_round_trip_value(value);
+        }
+    }
+
+    #[test]
+    fn conditions_round_trip() {
+        let a = Condition::has(Attribute::Debug(0));
+        let b = strength().greater_than(Value::TEN);
+        let c = Value::condition(a.clone(), Value::ONE, Value::TWO).equal_to(strength());
+
+        let conditions = [
+            a.clone() & (b.clone() & c.clone()),
+            (a.clone() | b.clone()) & c.clone(),
+            a.clone() ^ (b.clone() | c.clone()),
+            !!a.clone(),
+            Value::ZERO.greater_than(strength()),
+            strength().greater_than(Value::ONE) & Condition::FALSE,
+            Condition::all([a.clone(), Condition::any([b.clone(), c.clone()])]),
+            Condition::at_least(2, [a.clone(), b.clone(), c.clone()]) | Condition::any([]),
+            !(a & b) | !c,
+        ];
+
+        for condition in &conditions {
+            assert_round_trip_condition(condition);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Tests evaluating feat requirements against builds
+
+use builder::{
+    attribute::Attribute,
+    breakdowns::Breakdowns,
+    character::Character,
+    feat::{FeatRequirement, GetFeatRequirement, SpellFocusFeat, UnmetRequirement},
+    types::{
+        ability::Ability, player_class::PlayerClass, race::Race, skill::Skill,
+        spell_school::SpellSchool,
+    },
+};
+use rust_decimal::Decimal;
+
+fn fighter(levels: usize) -> Breakdowns {
+    Breakdowns::from_character(
+        &Character::new(Race::Human)
+            .with_levels(vec![PlayerClass::Fighter; levels])
+            .with_ability_score(Ability::Strength, 16),
+    )
+}
+
+mod requirements {
+    use super::*;
+
+    #[test]
+    fn class_level() {
+        let breakdowns = fighter(4);
+
+        assert!(FeatRequirement::ClassLevel(PlayerClass::Fighter, 4).is_satisfied(&breakdowns));
+        assert_eq!(
+            FeatRequirement::ClassLevel(PlayerClass::Fighter, 6).unmet_requirements(&breakdowns),
+            Some(UnmetRequirement::ClassLevel {
+                class: PlayerClass::Fighter,
+                required: 6,
+                current: 4.into()
+            })
+        );
+    }
+
+    #[test]
+    fn ability_score() {
+        let breakdowns = fighter(1);
+
+        assert!(FeatRequirement::AbilityScore(Ability::Strength, 16).is_satisfied(&breakdowns));
+        assert_eq!(
+            FeatRequirement::AbilityScore(Ability::Dexterity, 13).unmet_requirements(&breakdowns),
+            Some(UnmetRequirement::AbilityScore {
+                ability: Ability::Dexterity,
+                required: 13,
+                current: 8.into()
+            })
+        );
+    }
+
+    #[test]
+    fn base_attack_bonus() {
+        assert_eq!(
+            fighter(6).evaluate_attribute_from(Attribute::BaseAttackBonus),
+            6.into()
+        );
+        assert!(FeatRequirement::BaseAttackBonus(6).is_satisfied(&fighter(6)));
+
+        let wizard = Breakdowns::from_character(
+            &Character::new(Race::Human).with_levels([PlayerClass::Wizard; 5]),
+        );
+        assert_eq!(
+            FeatRequirement::BaseAttackBonus(6).unmet_requirements(&wizard),
+            Some(UnmetRequirement::BaseAttackBonus {
+                required: 6,
+                current: 2.into()
+            })
+        );
+    }
+
+    #[test]
+    fn skill_points() {
+        let requirement = FeatRequirement::SkillPoints(Skill::Concentration, 4);
+        let character = Character::new(Race::Human).with_level(PlayerClass::Wizard);
+
+        assert_eq!(
+            requirement.unmet_requirements(&Breakdowns::from_character(&character)),
+            Some(UnmetRequirement::SkillPoints {
+                skill: Skill::Concentration,
+                required: 4,
+                current: Decimal::ZERO
+            })
+        );
+
+        let character = character.with_skill_points(1, Skill::Concentration, 4);
+        assert!(requirement.is_satisfied(&Breakdowns::from_character(&character)));
+    }
+
+    #[test]
+    fn any_lists_every_option() {
+        let breakdowns = fighter(2);
+        let requirement = FeatRequirement::Any(vec![
+            FeatRequirement::ClassLevel(PlayerClass::Wizard, 1),
+            FeatRequirement::ClassLevel(PlayerClass::Paladin, 4),
+        ]);
+
+        let unmet = requirement
+            .unmet_requirements(&breakdowns)
+            .expect("Expected requirement to not be met");
+        assert!(matches!(&unmet, UnmetRequirement::Any(options) if options.len() == 2));
+        assert_eq!(unmet.leaves().len(), 2);
+
+        let requirement = FeatRequirement::Any(vec![
+            FeatRequirement::ClassLevel(PlayerClass::Wizard, 1),
+            FeatRequirement::ClassLevel(PlayerClass::Fighter, 2),
+        ]);
+        assert!(requirement.is_satisfied(&breakdowns));
+    }
+
+    #[test]
+    fn all_lists_only_failed_requirements() {
+        let breakdowns = fighter(2);
+        let requirement = FeatRequirement::All(vec![
+            FeatRequirement::ClassLevel(PlayerClass::Fighter, 2),
+            FeatRequirement::AbilityScore(Ability::Strength, 13),
+            FeatRequirement::BaseAttackBonus(4),
+        ]);
+
+        assert_eq!(
+            requirement.unmet_requirements(&breakdowns),
+            Some(UnmetRequirement::All(vec![
+                UnmetRequirement::BaseAttackBonus {
+                    required: 4,
+                    current: 2.into()
+                }
+            ]))
+        );
+    }
+
+    #[test]
+    fn not() {
+        let breakdowns = fighter(2);
+
+        assert!(
+            FeatRequirement::none(vec![FeatRequirement::ClassLevel(PlayerClass::Wizard, 1)])
+                .is_satisfied(&breakdowns)
+        );
+        assert_eq!(
+            FeatRequirement::Not(Box::new(FeatRequirement::ClassLevel(
+                PlayerClass::Fighter,
+                1
+            )))
+            .unmet_requirements(&breakdowns),
+            Some(UnmetRequirement::Not(FeatRequirement::ClassLevel(
+                PlayerClass::Fighter,
+                1
+            )))
+        );
+    }
+
+    #[test]
+    fn feats() {
+        let focus = SpellFocusFeat::SpellFocus(SpellSchool::Evocation);
+        let greater = SpellFocusFeat::GreaterSpellFocus(SpellSchool::Evocation);
+
+        let wizard = Character::new(Race::Human).with_level(PlayerClass::Wizard);
+        let breakdowns = Breakdowns::from_character(&wizard);
+        assert_eq!(focus.unmet_feat_requirements(&breakdowns), None);
+        assert_eq!(
+            greater.unmet_feat_requirements(&breakdowns),
+            Some(UnmetRequirement::Feat(focus.into()))
+        );
+
+        let breakdowns = Breakdowns::from_character(&wizard.with_feat(focus));
+        assert_eq!(greater.unmet_feat_requirements(&breakdowns), None);
+        assert!(focus.unmet_feat_requirements(&fighter(2)).is_some());
+    }
+
+    #[test]
+    fn display() {
+        let unmet = UnmetRequirement::ClassLevel {
+            class: PlayerClass::Fighter,
+            required: 6,
+            current: Decimal::from(4),
+        };
+        assert_eq!(unmet.to_string(), "Requires Fighter Level 6 (has 4)");
+    }
+}
@@ -657,7 +657,7 @@ mod weapons {
 
         #[test]
         fn sneak_attack_toggle_always_granted() {
-            let mut breakdowns = Breakdowns::new();
+            let breakdowns = Breakdowns::new();
             assert!(
                 breakdowns.evaluate_condition(&Condition::has(Attribute::Flag(Flag::HasToggle(
                     Toggle::SneakAttack
@@ -1084,7 +1084,7 @@ mod feats {
 
             #[test]
             fn completionist_not_given_by_default() {
-                let mut breakdowns = Breakdowns::new();
+                let breakdowns = Breakdowns::new();
 
                 assert_eq!(
                     breakdowns.evaluate_attribute_from(PastLifeFeat::RacialCompletionist),
@@ -1,6 +1,9 @@
 //! Loads and saves the data files to update their serialization
+//!
+//! Pass `--simplify` to also simplify the values and conditions of every bonus
 
 use std::{
+    env::args,
     fs::{File, OpenOptions},
     io::{BufReader, Write},
     path::PathBuf,
@@ -13,20 +16,29 @@ use serde::{Deserialize, Serialize};
 use utils::ron::pretty_config::compact_pretty_config;
 
 fn main() -> Result<()> {
+    let simplify = args().any(|arg| arg == "--simplify");
+
     let root: PathBuf = PathBuf::from_iter([".", "data", "data"]);
-    process_file::<Vec<ItemSet>>(root.join("item_sets.ron"))?;
+    process_file(root.join("item_sets.ron"), |item_sets: Vec<ItemSet>| {
+        if simplify {
+            item_sets.into_iter().map(ItemSet::simplify).collect()
+        } else {
+            item_sets
+        }
+    })?;
 
     Ok(())
 }
 
-fn process_file<T>(path: PathBuf) -> Result<()>
+fn process_file<T, F>(path: PathBuf, transform: F) -> Result<()>
 where
     for<'de> T: Deserialize<'de> + Serialize,
+    F: FnOnce(T) -> T,
 {
     // Read file
     let file = OpenOptions::new().read(true).open(path.clone())?;
     let reader = BufReader::new(file);
-    let data: T = from_reader(reader)?;
+    let data: T = transform(from_reader(reader)?);
 
     // Write file
     let mut file = File::create(path)?;
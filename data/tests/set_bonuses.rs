@@ -2,8 +2,12 @@
 
 mod utils;
 use anyhow::Result;
-use builder::attribute::AttributeDependencies;
-use data::load_item_sets;
+use builder::{
+    attribute::{Attribute, AttributeDependencies},
+    bonus::{Bonus, BonusSource, BonusType, Condition, Value},
+    breakdowns::Breakdowns,
+};
+use data::{load_item_sets, IncludeItemSets};
 pub use utils::*;
 
 #[test]
@@ -32,3 +36,52 @@ fn bonuses_have_valid_attributes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn bonuses_have_no_cycles() -> Result<()> {
+    let mut breakdowns = Breakdowns::new().with_item_sets()?;
+
+    breakdowns.insert_bonuses(load_item_sets()?.into_iter().map(|set_bonus| {
+        Bonus::new(
+            Attribute::ItemSet(set_bonus.name().clone()),
+            BonusType::Stacking,
+            10,
+            BonusSource::Debug(0),
+        )
+    }));
+
+    assert!(
+        breakdowns.cycle_errors().is_empty(),
+        "Item Sets have circular dependencies: {:?}",
+        breakdowns.cycle_errors()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bonuses_round_trip_expressions() -> Result<()> {
+    for set_bonus in load_item_sets()? {
+        for bonus in set_bonus.bonuses().values().flatten() {
+            let expression = bonus.value().to_expression();
+            assert_eq!(
+                &expression.parse::<Value>()?,
+                bonus.value(),
+                "Item Set [{}] has value that does not round trip [{expression}]",
+                set_bonus.name(),
+            );
+
+            if let Some(condition) = bonus.condition() {
+                let expression = condition.to_expression();
+                assert_eq!(
+                    &expression.parse::<Condition>()?,
+                    condition,
+                    "Item Set [{}] has condition that does not round trip [{expression}]",
+                    set_bonus.name(),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
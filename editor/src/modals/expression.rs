@@ -171,8 +171,28 @@ impl ModalExpression {
         self.add_selector(child)
     }
 
+    fn add_selector_values<I>(&mut self, values: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        values
+            .into_iter()
+            .map(|value| self.add_selector_value(value))
+            .collect()
+    }
+
+    fn add_selector_conditions<I>(&mut self, conditions: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = Condition>,
+    {
+        conditions
+            .into_iter()
+            .map(|condition| self.add_selector_condition(condition))
+            .collect()
+    }
+
     fn handle_change_type(&mut self, id: usize, selector_type: ModalExpressionType) {
-        let (val_a, val_b, cond_a, cond_b) = match selector_type {
+        let (val_a, val_b, val_c, cond_a, cond_b) = match selector_type {
             ModalExpressionType::Value(v) => match v {
                 ValueType::Add
                 | ValueType::Sub
@@ -181,27 +201,49 @@ impl ModalExpression {
                 | ValueType::Rem
                 | ValueType::Dice
                 | ValueType::Max
-                | ValueType::Min => (true, true, false, false),
-                ValueType::Ceil | ValueType::Round | ValueType::Abs | ValueType::Floor => {
-                    (true, false, false, false)
-                }
-                ValueType::If => (true, true, true, false),
-                _ => (false, false, false, false),
+                | ValueType::Min
+                | ValueType::Pow => (true, true, false, false, false),
+                ValueType::Ceil
+                | ValueType::Round
+                | ValueType::Abs
+                | ValueType::Floor
+                | ValueType::Sign
+                | ValueType::Table => (true, false, false, false, false),
+                ValueType::If => (true, true, false, true, false),
+                ValueType::Clamp => (true, true, true, false, false),
+                _ => (false, false, false, false, false),
             },
             ModalExpressionType::Condition(c) => match c {
-                ConditionType::Not => (false, false, true, false),
+                ConditionType::Not => (false, false, false, true, false),
                 ConditionType::GreaterThan
                 | ConditionType::LessThan
                 | ConditionType::EqualTo
                 | ConditionType::LessEqualTo
-                | ConditionType::GreaterEqualTo => (true, true, false, false),
-                ConditionType::True | ConditionType::False => (false, false, false, false),
+                | ConditionType::GreaterEqualTo => (true, true, false, false, false),
+                ConditionType::True
+                | ConditionType::False
+                | ConditionType::All
+                | ConditionType::Any
+                | ConditionType::AtLeast => (false, false, false, false, false),
                 ConditionType::And | ConditionType::Or | ConditionType::Xor => {
-                    (false, false, true, true)
+                    (false, false, false, true, true)
                 }
             },
         };
 
+        let values = matches!(
+            selector_type,
+            ModalExpressionType::Value(ValueType::Sum | ValueType::Product | ValueType::Table)
+        );
+        let table = selector_type == ModalExpressionType::Value(ValueType::Table);
+
+        let conditions = matches!(
+            selector_type,
+            ModalExpressionType::Condition(
+                ConditionType::All | ConditionType::Any | ConditionType::AtLeast
+            )
+        );
+
         let Some(mut selector) = self.selectors.remove(&id) else {
             return;
         };
@@ -224,6 +266,15 @@ impl ModalExpression {
             (_, val) => val,
         };
 
+        selector.value_c = match (val_c, selector.value_c) {
+            (true, None) => Some(self.add_selector_value(Value::ZERO)),
+            (false, Some(index)) => {
+                self.selectors.remove(&index);
+                None
+            }
+            (_, val) => val,
+        };
+
         selector.condition_a = match (cond_a, selector.condition_a) {
             (true, None) => Some(self.add_selector_condition(Condition::TRUE)),
             (false, Some(index)) => {
@@ -242,6 +293,32 @@ impl ModalExpression {
             (_, val) => val,
         };
 
+        if values && selector.values.is_empty() {
+            selector.values = self.add_selector_values([Value::ZERO, Value::ZERO]);
+        } else if !values {
+            for index in selector.values.drain(..) {
+                self.selectors.remove(&index);
+            }
+        }
+
+        let thresholds = if table { selector.values.len() } else { 0 };
+        selector
+            .thresholds
+            .resize(thresholds, Decimal::ZERO.to_string());
+
+        if conditions && selector.conditions.is_empty() {
+            selector.conditions = self.add_selector_conditions([Condition::TRUE, Condition::TRUE]);
+        } else if !conditions {
+            for index in selector.conditions.drain(..) {
+                self.selectors.remove(&index);
+            }
+        }
+
+        if selector_type == ModalExpressionType::Condition(ConditionType::AtLeast) {
+            selector.constant = Some(Decimal::ONE);
+            selector.constant_str = Decimal::ONE.to_string();
+        }
+
         selector.selector_type = selector_type;
 
         self.selectors.insert(id, selector);
@@ -265,8 +342,12 @@ impl From<ModalExpressionMessage> for Message {
 struct InternalSelector {
     value_a: Option<usize>,
     value_b: Option<usize>,
+    value_c: Option<usize>,
+    values: Vec<usize>,
+    thresholds: Vec<String>,
     condition_a: Option<usize>,
     condition_b: Option<usize>,
+    conditions: Vec<usize>,
     attribute: Option<Attribute>,
     constant: Option<Decimal>,
     constant_str: String,
@@ -285,6 +366,11 @@ pub enum ModalExpressionInternalMessage {
     SelectAttribute,
     OnAttributeSelected,
     ConstInput(String),
+    AddCondition,
+    RemoveCondition(usize),
+    AddValue,
+    RemoveValue(usize),
+    ThresholdInput(usize, String),
 }
 
 impl From<(usize, ModalExpressionInternalMessage)> for Message {
@@ -362,6 +448,70 @@ impl HandleMessage<ModalExpressionMessage> for App {
                         modal.update_cached();
                     }
 
+                    Command::none()
+                }
+                ModalExpressionInternalMessage::AddCondition => {
+                    let condition = modal.add_selector_condition(Condition::TRUE);
+                    if let Some(selector) = modal.selectors.get_mut(&id) {
+                        selector.conditions.push(condition);
+                    }
+                    modal.update_cached();
+
+                    Command::none()
+                }
+                ModalExpressionInternalMessage::RemoveCondition(index) => {
+                    let removed = modal
+                        .selectors
+                        .get_mut(&id)
+                        .filter(|selector| index < selector.conditions.len())
+                        .map(|selector| selector.conditions.remove(index));
+                    if let Some(condition) = removed {
+                        modal.selectors.remove(&condition);
+                    }
+                    modal.update_cached();
+
+                    Command::none()
+                }
+                ModalExpressionInternalMessage::AddValue => {
+                    let value = modal.add_selector_value(Value::ZERO);
+                    if let Some(selector) = modal.selectors.get_mut(&id) {
+                        selector.values.push(value);
+                        if selector.selector_type == ModalExpressionType::Value(ValueType::Table) {
+                            selector.thresholds.push(Decimal::ZERO.to_string());
+                        }
+                    }
+                    modal.update_cached();
+
+                    Command::none()
+                }
+                ModalExpressionInternalMessage::RemoveValue(index) => {
+                    let removed = modal
+                        .selectors
+                        .get_mut(&id)
+                        .filter(|selector| index < selector.values.len())
+                        .map(|selector| {
+                            if index < selector.thresholds.len() {
+                                selector.thresholds.remove(index);
+                            }
+                            selector.values.remove(index)
+                        });
+                    if let Some(value) = removed {
+                        modal.selectors.remove(&value);
+                    }
+                    modal.update_cached();
+
+                    Command::none()
+                }
+                ModalExpressionInternalMessage::ThresholdInput(index, input) => {
+                    if let Some(threshold) = modal
+                        .selectors
+                        .get_mut(&id)
+                        .and_then(|selector| selector.thresholds.get_mut(index))
+                    {
+                        *threshold = input;
+                        modal.update_cached();
+                    }
+
                     Command::none()
                 }
             },
@@ -389,6 +539,11 @@ impl ModalExpression {
         let val_b = selector
             .value_b
             .and_then(|id| self.handle_internal_view(id));
+        let val_c = selector
+            .value_c
+            .and_then(|id| self.handle_internal_view(id));
+        let values = self.handle_value_list_view(id, &selector.values, &selector.thresholds);
+        let conditions = self.handle_condition_list_view(id, &selector.conditions);
 
         Some(
             match selector.selector_type {
@@ -433,6 +588,7 @@ impl ModalExpression {
                             ValueType::Ceil => container(column([text("Ceil").into(), val_a?])),
                             ValueType::Round => container(column([text("Round").into(), val_a?])),
                             ValueType::Abs => container(column([text("Abs").into(), val_a?])),
+                            ValueType::Sign => container(column([text("Sign").into(), val_a?])),
                             ValueType::Add => {
                                 container(column([text("Add").into(), val_a?, val_b?]))
                             }
@@ -448,6 +604,12 @@ impl ModalExpression {
                             ValueType::Rem => {
                                 container(column([text("Remainder").into(), val_a?, val_b?]))
                             }
+                            ValueType::Pow => container(column([
+                                text("Base").into(),
+                                val_a?,
+                                text("Exponent").into(),
+                                val_b?,
+                            ])),
                             ValueType::If => container(column([
                                 text("If").into(),
                                 cond_a?,
@@ -462,6 +624,24 @@ impl ModalExpression {
                                 text("Size").into(),
                                 val_b?,
                             ])),
+                            ValueType::Clamp => container(column([
+                                text("Clamp").into(),
+                                val_a?,
+                                text("Min").into(),
+                                val_b?,
+                                text("Max").into(),
+                                val_c?,
+                            ])),
+                            ValueType::Sum => container(column([text("Sum").into(), values?])),
+                            ValueType::Product => {
+                                container(column([text("Product").into(), values?]))
+                            }
+                            ValueType::Table => container(column([
+                                text("Table").into(),
+                                val_a?,
+                                text("Steps").into(),
+                                values?,
+                            ])),
                         }
                         .into(),
                     ])
@@ -501,6 +681,20 @@ impl ModalExpression {
                             ConditionType::Or => container(column!(cond_a?, text("Or"), cond_b?)),
                             ConditionType::Xor =>
                                 container(column!(cond_a?, text("Exclusive Or"), cond_b?)),
+                            ConditionType::All => container(column!(text("All"), conditions?)),
+                            ConditionType::Any => container(column!(text("Any"), conditions?)),
+                            ConditionType::AtLeast => container(column!(
+                                row!(
+                                    text("At Least"),
+                                    text_input("Count", selector.constant_str.as_str()).on_input(
+                                        move |string| {
+                                            (id, ModalExpressionInternalMessage::ConstInput(string))
+                                                .into()
+                                        }
+                                    )
+                                ),
+                                conditions?
+                            )),
                         }
                     )
                 )),
@@ -510,6 +704,94 @@ impl ModalExpression {
     }
 }
 
+impl ModalExpression {
+    /// Shows each condition in a list, with buttons to add and remove conditions
+    fn handle_condition_list_view(
+        &self,
+        id: usize,
+        conditions: &[usize],
+    ) -> Option<Element<'_, <App as Application>::Message, <App as Application>::Theme, Renderer>>
+    {
+        let rows = conditions
+            .iter()
+            .enumerate()
+            .map(|(index, condition)| {
+                Some(
+                    row!(
+                        self.handle_internal_view(*condition)?,
+                        button("Remove")
+                            .on_press(
+                                (id, ModalExpressionInternalMessage::RemoveCondition(index)).into()
+                            )
+                            .style(theme::Button::Secondary)
+                    )
+                    .into(),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(
+            column(rows)
+                .push(
+                    button("Add Condition")
+                        .on_press((id, ModalExpressionInternalMessage::AddCondition).into()),
+                )
+                .into(),
+        )
+    }
+}
+
+impl ModalExpression {
+    /// Shows each value in a list, with buttons to add and remove values. If `thresholds` is not
+    /// empty, each value is shown with an input for its table threshold.
+    fn handle_value_list_view<'a>(
+        &'a self,
+        id: usize,
+        values: &[usize],
+        thresholds: &'a [String],
+    ) -> Option<Element<'a, <App as Application>::Message, <App as Application>::Theme, Renderer>>
+    {
+        let rows = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let threshold = thresholds.get(index).map(|threshold| {
+                    text_input("Threshold", threshold.as_str()).on_input(move |string| {
+                        (
+                            id,
+                            ModalExpressionInternalMessage::ThresholdInput(index, string),
+                        )
+                            .into()
+                    })
+                });
+
+                Some(
+                    row([])
+                        .push_maybe(threshold)
+                        .push(self.handle_internal_view(*value)?)
+                        .push(
+                            button("Remove")
+                                .on_press(
+                                    (id, ModalExpressionInternalMessage::RemoveValue(index)).into(),
+                                )
+                                .style(theme::Button::Secondary),
+                        )
+                        .into(),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(
+            column(rows)
+                .push(
+                    button("Add Value")
+                        .on_press((id, ModalExpressionInternalMessage::AddValue).into()),
+                )
+                .into(),
+        )
+    }
+}
+
 impl HandleView<App> for ModalExpression {
     fn handle_view<'a>(
         &'a self,
@@ -2,6 +2,7 @@ use core::fmt::{self, Display};
 
 use crate::modals::expression::ModalExpressionType;
 use builder::bonus::Condition;
+use rust_decimal::Decimal;
 
 use super::{InternalSelector, ModalExpression};
 
@@ -18,10 +19,13 @@ pub enum ConditionType {
     And,
     Or,
     Xor,
+    All,
+    Any,
+    AtLeast,
 }
 
 impl ConditionType {
-    pub const TYPES: [Self; 9] = [
+    pub const TYPES: [Self; 12] = [
         Self::Not,
         Self::GreaterThan,
         Self::LessThan,
@@ -31,6 +35,9 @@ impl ConditionType {
         Self::And,
         Self::Or,
         Self::Xor,
+        Self::All,
+        Self::Any,
+        Self::AtLeast,
     ];
 }
 
@@ -46,6 +53,9 @@ impl Display for ConditionType {
             Self::And => write!(f, "And"),
             Self::Or => write!(f, "Or"),
             Self::Xor => write!(f, "Xor"),
+            Self::All => write!(f, "All"),
+            Self::Any => write!(f, "Any"),
+            Self::AtLeast => write!(f, "At Least"),
             Self::GreaterEqualTo => write!(f, "Greater or Equal To"),
             Self::LessEqualTo => write!(f, "Less or Equal To"),
         }
@@ -67,6 +77,11 @@ impl ModalExpression {
         let cond_b = selector
             .condition_b
             .and_then(|id| self.get_internal_condition(id));
+        let conditions = selector
+            .conditions
+            .iter()
+            .map(|id| self.get_internal_condition(*id))
+            .collect::<Option<Vec<_>>>();
 
         Some(match condition_type {
             ConditionType::Not => !cond_a?,
@@ -80,6 +95,15 @@ impl ModalExpression {
             ConditionType::And => cond_a? & cond_b?,
             ConditionType::Or => cond_a? | cond_b?,
             ConditionType::Xor => cond_a? ^ cond_b?,
+            ConditionType::All => Condition::All(conditions?),
+            ConditionType::Any => Condition::Any(conditions?),
+            ConditionType::AtLeast => {
+                let count = selector
+                    .constant
+                    .filter(Decimal::is_integer)
+                    .and_then(|count| usize::try_from(count).ok())?;
+                Condition::AtLeast(count, conditions?)
+            }
         })
     }
 }
@@ -94,6 +118,8 @@ impl InternalSelector {
         let mut value_b = None;
         let mut condition_a = None;
         let mut condition_b = None;
+        let mut conditions = Vec::new();
+        let mut constant = None;
 
         let mut condition_type = ConditionType::True;
 
@@ -147,16 +173,33 @@ impl InternalSelector {
                 condition_b = Some(selector.add_selector_condition(*b));
                 condition_type = ConditionType::Xor;
             }
+            Condition::All(items) => {
+                conditions = selector.add_selector_conditions(items);
+                condition_type = ConditionType::All;
+            }
+            Condition::Any(items) => {
+                conditions = selector.add_selector_conditions(items);
+                condition_type = ConditionType::Any;
+            }
+            Condition::AtLeast(count, items) => {
+                conditions = selector.add_selector_conditions(items);
+                constant = Some(Decimal::from(count));
+                condition_type = ConditionType::AtLeast;
+            }
         }
 
         Self {
             value_a,
             value_b,
+            value_c: None,
+            values: Vec::new(),
+            thresholds: Vec::new(),
             condition_a,
             condition_b,
+            conditions,
             attribute: None,
-            constant: None,
-            constant_str: String::new(),
+            constant,
+            constant_str: constant.map(|val| val.to_string()).unwrap_or_default(),
             selector_type: ModalExpressionType::Condition(condition_type),
         }
     }
@@ -1,6 +1,10 @@
-use core::fmt::{self, Display};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
 
 use builder::bonus::Value;
+use rust_decimal::Decimal;
 
 use super::{InternalSelector, ModalExpression, ModalExpressionType};
 
@@ -14,17 +18,23 @@ pub enum ValueType {
     Ceil,
     Round,
     Abs,
+    Sign,
     Add,
     Sub,
     Mul,
     Div,
     Rem,
+    Pow,
     If,
     Dice,
+    Clamp,
+    Sum,
+    Product,
+    Table,
 }
 
 impl ValueType {
-    pub const TYPES: [Self; 15] = [
+    pub const TYPES: [Self; 21] = [
         Self::Const,
         Self::Attribute,
         Self::Min,
@@ -33,13 +43,19 @@ impl ValueType {
         Self::Ceil,
         Self::Round,
         Self::Abs,
+        Self::Sign,
         Self::Add,
         Self::Sub,
         Self::Mul,
         Self::Div,
         Self::Rem,
+        Self::Pow,
         Self::If,
         Self::Dice,
+        Self::Clamp,
+        Self::Sum,
+        Self::Product,
+        Self::Table,
     ];
 }
 
@@ -54,13 +70,19 @@ impl Display for ValueType {
             Self::Ceil => write!(f, "Ceil"),
             Self::Round => write!(f, "Round"),
             Self::Abs => write!(f, "Abs"),
+            Self::Sign => write!(f, "Sign"),
             Self::Add => write!(f, "Add"),
             Self::Sub => write!(f, "Sub"),
             Self::Mul => write!(f, "Mul"),
             Self::Div => write!(f, "Div"),
             Self::Rem => write!(f, "Rem"),
+            Self::Pow => write!(f, "Pow"),
             Self::If => write!(f, "If"),
             Self::Dice => write!(f, "Dice"),
+            Self::Clamp => write!(f, "Clamp"),
+            Self::Sum => write!(f, "Sum"),
+            Self::Product => write!(f, "Product"),
+            Self::Table => write!(f, "Table"),
         }
     }
 }
@@ -74,6 +96,12 @@ impl ModalExpression {
 
         let val_a = selector.value_a.and_then(|id| self.get_internal_value(id));
         let val_b = selector.value_b.and_then(|id| self.get_internal_value(id));
+        let val_c = selector.value_c.and_then(|id| self.get_internal_value(id));
+        let values = selector
+            .values
+            .iter()
+            .map(|id| self.get_internal_value(*id))
+            .collect::<Option<Vec<_>>>();
         let cond_a = selector
             .condition_a
             .and_then(|id| self.get_internal_condition(id));
@@ -87,13 +115,28 @@ impl ModalExpression {
             ValueType::Ceil => val_a?.ceil(),
             ValueType::Round => val_a?.round(),
             ValueType::Abs => val_a?.abs(),
+            ValueType::Sign => val_a?.sign(),
             ValueType::Add => val_a? + val_b?,
             ValueType::Sub => val_a? - val_b?,
             ValueType::Mul => val_a? * val_b?,
             ValueType::Div => val_a? / val_b?,
             ValueType::Rem => val_a? % val_b?,
+            ValueType::Pow => val_a?.pow(val_b?),
             ValueType::If => Value::condition(cond_a?, val_a?, val_b?),
             ValueType::Dice => Value::dice(val_a?, val_b?),
+            ValueType::Clamp => val_a?.clamp(val_b?, val_c?),
+            ValueType::Sum => Value::Sum(values?),
+            ValueType::Product => Value::Product(values?),
+            ValueType::Table => Value::Table {
+                key: Box::new(val_a?),
+                steps: selector
+                    .thresholds
+                    .iter()
+                    .map(|threshold| Decimal::from_str(threshold).ok())
+                    .zip(values?)
+                    .map(|(threshold, value)| Some((threshold?, value)))
+                    .collect::<Option<_>>()?,
+            },
         })
     }
 }
@@ -106,6 +149,9 @@ impl InternalSelector {
 
         let mut value_a = None;
         let mut value_b = None;
+        let mut value_c = None;
+        let mut values = Vec::new();
+        let mut thresholds = Vec::new();
         let mut condition_a = None;
         let mut constant = None;
         let mut attribute = None;
@@ -146,6 +192,10 @@ impl InternalSelector {
                 value_a = Some(selector.add_selector_value(*value));
                 value_type = ValueType::Abs;
             }
+            Value::Sign(value) => {
+                value_a = Some(selector.add_selector_value(*value));
+                value_type = ValueType::Sign;
+            }
             Value::Add(a, b) => {
                 value_a = Some(selector.add_selector_value(*a));
                 value_b = Some(selector.add_selector_value(*b));
@@ -171,6 +221,11 @@ impl InternalSelector {
                 value_b = Some(selector.add_selector_value(*b));
                 value_type = ValueType::Rem;
             }
+            Value::Pow(a, b) => {
+                value_a = Some(selector.add_selector_value(*a));
+                value_b = Some(selector.add_selector_value(*b));
+                value_type = ValueType::Pow;
+            }
             Value::If {
                 condition,
                 if_true,
@@ -186,13 +241,38 @@ impl InternalSelector {
                 value_b = Some(selector.add_selector_value(*size));
                 value_type = ValueType::Dice;
             }
+            Value::Clamp(value, min, max) => {
+                value_a = Some(selector.add_selector_value(*value));
+                value_b = Some(selector.add_selector_value(*min));
+                value_c = Some(selector.add_selector_value(*max));
+                value_type = ValueType::Clamp;
+            }
+            Value::Sum(items) => {
+                values = selector.add_selector_values(items);
+                value_type = ValueType::Sum;
+            }
+            Value::Product(items) => {
+                values = selector.add_selector_values(items);
+                value_type = ValueType::Product;
+            }
+            Value::Table { key, steps } => {
+                value_a = Some(selector.add_selector_value(*key));
+                let (steps_thresholds, steps_values): (Vec<_>, Vec<_>) = steps.into_iter().unzip();
+                thresholds = steps_thresholds.iter().map(ToString::to_string).collect();
+                values = selector.add_selector_values(steps_values);
+                value_type = ValueType::Table;
+            }
         }
 
         Self {
             value_a,
             value_b,
+            value_c,
+            values,
+            thresholds,
             condition_a,
             condition_b: None,
+            conditions: Vec::new(),
             attribute,
             constant,
             constant_str: constant.map(|val| val.to_string()).unwrap_or_default(),
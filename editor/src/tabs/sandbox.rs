@@ -8,7 +8,7 @@ use builder::{
     bonus::{BonusSource, BonusTemplate},
     breakdowns::{Breakdowns, DiceStrategy},
     equipment::set_bonus::ItemSet,
-    types::{self, toggle::Toggle},
+    types::{self, flag::Flag, toggle::Toggle},
 };
 use iced::{
     widget::{button, checkbox, column, container, pick_list, row, scrollable, slider, text},
@@ -18,6 +18,7 @@ use iced_aw::{TabBar, TabLabel};
 use itertools::chain;
 use rust_decimal::{prelude::FromPrimitive, Decimal};
 use ui::{error, font::nf_icon, warning, ExecuteMessage, HandleMessage, HandleView, ToColumn};
+use utils::enums::StaticValues;
 
 use crate::{modals::bonus_template::ModalBonus, App, Message};
 
@@ -55,7 +56,7 @@ impl Display for TabSandboxTab {
 impl TabSandbox {
     pub fn new() -> Self {
         Self {
-            breakdowns: Breakdowns::new(),
+            breakdowns: watched_breakdowns(),
             bonuses: Vec::new(),
             toggles: Vec::new(),
             slider: Vec::new(),
@@ -64,6 +65,21 @@ impl TabSandbox {
     }
 }
 
+/// Creates a new [`Breakdowns`] that watches every attribute shown in the toggles and sliders tabs
+fn watched_breakdowns() -> Breakdowns {
+    let mut breakdowns = Breakdowns::new();
+
+    breakdowns.watch_all(chain!(
+        Toggle::values().map(Attribute::Toggle),
+        Toggle::values().map(|toggle| Attribute::Flag(Flag::HasToggle(toggle))),
+        SliderAttribute::values().map(Attribute::Slider),
+        SliderAttribute::values().map(Attribute::SliderMax),
+        SliderAttribute::values().map(|slider| Attribute::Flag(Flag::HasSlider(slider))),
+    ));
+
+    breakdowns
+}
+
 #[derive(Debug, Clone)]
 pub enum TabSandboxMessage {
     NewBreakdowns,
@@ -83,6 +99,7 @@ pub enum TabSandboxMessage {
     SetSider(SliderAttribute, f32),
     RefreshSliders,
     SetDiceStrategy(DiceStrategy),
+    ApplyChanges,
 }
 
 type Msg = TabSandboxMessage;
@@ -103,7 +120,7 @@ impl HandleMessage<TabSandboxMessage> for App {
         match message {
             TabSandboxMessage::SetDiceStrategy(strategy) => {
                 tab.breakdowns.set_dice_strategy(strategy);
-                Command::none()
+                self.handle_message(Msg::ApplyChanges)
             }
             TabSandboxMessage::SetTab(t) => {
                 tab.tab = t;
@@ -117,7 +134,7 @@ impl HandleMessage<TabSandboxMessage> for App {
                     .cloned()
                     .collect::<Vec<_>>();
 
-                tab.breakdowns = Breakdowns::new();
+                tab.breakdowns = watched_breakdowns();
 
                 for attribute in tracked {
                     tab.breakdowns.add_breakdown(attribute);
@@ -144,7 +161,7 @@ impl HandleMessage<TabSandboxMessage> for App {
                 let dynamic_bonuses = item_sets.iter().cloned().map(ItemSet::to_dynamic_bonus);
                 tab.breakdowns.import_dynamic_bonuses(dynamic_bonuses);
 
-                Command::none()
+                self.handle_message(Msg::ApplyChanges)
             }
             TabSandboxMessage::OpenTrackAttributePrompt => {
                 let tracked = tab
@@ -251,11 +268,11 @@ impl HandleMessage<TabSandboxMessage> for App {
 
                 tab.breakdowns.insert_bonuses(bonuses);
 
-                self.handle_message(Msg::RefreshSliders)
+                self.handle_message(Msg::ApplyChanges)
             }
             TabSandboxMessage::SetToggle(toggle, value) => {
                 tab.breakdowns.insert_bonus(toggle.toggle_bonus(value));
-                self.handle_message(Msg::RefreshToggles)
+                self.handle_message(Msg::ApplyChanges)
             }
             TabSandboxMessage::RefreshToggles => {
                 tab.toggles = tab.breakdowns.get_active_toggles().collect();
@@ -266,7 +283,30 @@ impl HandleMessage<TabSandboxMessage> for App {
                     return self.handle_message(error!("Could not parse value {value}"));
                 };
                 tab.breakdowns.insert_bonus(slider.slider_bonus(value));
-                self.handle_message(TabSandboxMessage::RefreshSliders)
+                self.handle_message(Msg::ApplyChanges)
+            }
+            TabSandboxMessage::ApplyChanges => {
+                let changes = tab.breakdowns.take_changes();
+
+                let toggles = changes.attributes().any(|attribute| {
+                    matches!(
+                        attribute,
+                        Attribute::Toggle(_) | Attribute::Flag(Flag::HasToggle(_))
+                    )
+                });
+                let sliders = changes.attributes().any(|attribute| {
+                    matches!(
+                        attribute,
+                        Attribute::Slider(_)
+                            | Attribute::SliderMax(_)
+                            | Attribute::Flag(Flag::HasSlider(_))
+                    )
+                });
+
+                Command::batch(chain!(
+                    toggles.then(|| self.handle_message(Msg::RefreshToggles)),
+                    sliders.then(|| self.handle_message(Msg::RefreshSliders)),
+                ))
             }
             TabSandboxMessage::RefreshSliders => {
                 let values = tab.breakdowns.get_active_sliders().collect::<Vec<_>>();
@@ -407,6 +447,20 @@ impl HandleView<App> for TabSandbox {
                                                                 entry.bonus()
                                                             )
                                                         ),),
+                                                        bt.applied_penalty().as_ref().map(
+                                                            |entry| text(format!(
+                                                                "Penalty: {} {}",
+                                                                entry.value(),
+                                                                entry.bonus()
+                                                            ))
+                                                        ),
+                                                        bt.stacked().iter().map(|entry| text(
+                                                            format!(
+                                                                "Stacked: {} {}",
+                                                                entry.value(),
+                                                                entry.bonus()
+                                                            )
+                                                        )),
                                                         bt.overwritten().iter().map(|entry| text(
                                                             format!(
                                                                 "Overwritten: {} {}",
@@ -451,6 +505,23 @@ impl HandleView<App> for TabSandbox {
                                                 )
                                                 .into()
                                             ))
+                                            .chain(once(
+                                                row!(
+                                                    text("Suppressed"),
+                                                    breakdown
+                                                        .suppressed()
+                                                        .iter()
+                                                        .map(|entry| {
+                                                            text(format!(
+                                                                "{} {}",
+                                                                entry.value(),
+                                                                entry.bonus()
+                                                            ))
+                                                        })
+                                                        .to_column()
+                                                )
+                                                .into()
+                                            ))
                                     )
                                 )
                             })